@@ -78,6 +78,10 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialBundle<S> {
                 signature: self.credential.signature.to_unblinded(blinder),
                 revocation_handle: self.credential.revocation_handle,
                 revocation_index,
+                key_epoch: self.credential.key_epoch,
+                revocation_epoch: self.credential.revocation_epoch,
+                suspension_handle: self.credential.suspension_handle,
+                suspension_epoch: self.credential.suspension_epoch,
             },
         })
     }
@@ -1,4 +1,4 @@
-use crate::knox::accumulator::vb20::MembershipWitness;
+use crate::knox::accumulator::vb20::{MembershipWitness, NonMembershipWitness};
 use crate::knox::short_group_sig_core::short_group_traits::{
     BlindSignature, ShortGroupSignatureScheme,
 };
@@ -20,6 +20,18 @@ pub struct BlindCredential<S: ShortGroupSignatureScheme> {
     pub revocation_handle: MembershipWitness,
     /// The claim that is used for revocation
     pub revocation_label: String,
+    /// The issuer key epoch that signed this credential
+    #[serde(default)]
+    pub key_epoch: usize,
+    /// The revocation registry epoch of the revocation handle
+    #[serde(default)]
+    pub revocation_epoch: usize,
+    /// The suspension handle
+    #[serde(default)]
+    pub suspension_handle: Option<NonMembershipWitness>,
+    /// The suspension registry epoch of the suspension handle
+    #[serde(default)]
+    pub suspension_epoch: usize,
 }
 
 impl<S: ShortGroupSignatureScheme> BlindCredential<S> {
@@ -58,6 +70,10 @@ impl<S: ShortGroupSignatureScheme> BlindCredential<S> {
             signature,
             revocation_handle: self.revocation_handle,
             revocation_index,
+            key_epoch: self.key_epoch,
+            revocation_epoch: self.revocation_epoch,
+            suspension_handle: self.suspension_handle,
+            suspension_epoch: self.suspension_epoch,
         })
     }
 }
@@ -5,6 +5,7 @@ use crate::{
     claim::ClaimData,
     error::Error,
     issuer::{Issuer, IssuerPublic},
+    revocation_registry::RegistryStore,
     CredxResult,
 };
 use blsful::inner_types::*;
@@ -53,7 +54,7 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialRequest<S> {
         Ok((
             Self {
                 blind_signature_context: ctx,
-                blind_claim_labels: claims.iter().map(|(l, _)| l.clone()).collect(),
+                blind_claim_labels: claims.keys().cloned().collect(),
                 nonce,
             },
             blinder,
@@ -61,7 +62,7 @@ impl<S: ShortGroupSignatureScheme> BlindCredentialRequest<S> {
     }
 
     /// Verify the signing request is well-formed
-    pub fn verify(&self, issuer: &Issuer<S>) -> CredxResult<()> {
+    pub fn verify<R: RegistryStore>(&self, issuer: &Issuer<S, R>) -> CredxResult<()> {
         let mut known_messages =
             Vec::with_capacity(issuer.schema.claims.len() - self.blind_claim_labels.len());
         for label in &self.blind_claim_labels {
@@ -1,6 +1,9 @@
 mod data;
+mod date;
+mod decimal;
 mod enumeration;
 mod hashed;
+mod nested;
 mod number;
 mod revocation;
 mod scalar;
@@ -8,8 +11,11 @@ mod r#type;
 mod validator;
 
 pub use data::*;
+pub use date::*;
+pub use decimal::*;
 pub use enumeration::*;
 pub use hashed::*;
+pub use nested::*;
 pub use number::*;
 pub use r#type::*;
 pub use revocation::*;
@@ -1,7 +1,8 @@
 use super::*;
 use crate::{error::Error, CredxResult};
-use blsful::inner_types::Scalar;
+use blsful::inner_types::{ExpandMsgXmd, Scalar};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// Hashed utf8 string
 pub const HASHED_UTF8: &str = "ut8:";
@@ -15,6 +16,16 @@ pub const NUMBER: &str = "num:";
 pub const REVOCATION: &str = "rev:";
 /// Enumeration
 pub const ENUMERATION: &str = "enm:";
+/// Date
+pub const DATE: &str = "dte:";
+/// DateTime
+pub const DATETIME: &str = "dtm:";
+/// Decimal
+pub const DECIMAL: &str = "dec:";
+/// Absent optional claim
+pub const ABSENT: &str = "abs:";
+
+const ABSENT_CLAIM_DST: &[u8] = b"BLS12381_XMD:SHA-256_SSWU_RO_CREDX_ABSENT_CLAIM_";
 
 /// The type of claim data that can be signed
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Hash)]
@@ -29,6 +40,14 @@ pub enum ClaimData {
     Revocation(RevocationClaim),
     /// Data is from a list of unique values
     Enumeration(EnumerationClaim),
+    /// Data is a calendar date
+    Date(DateClaim),
+    /// Data is a point in time
+    DateTime(DateTimeClaim),
+    /// Data is a fixed-point decimal number
+    Decimal(DecimalClaim),
+    /// An optional claim that was not given
+    Absent,
 }
 
 impl From<HashedClaim> for ClaimData {
@@ -61,6 +80,24 @@ impl From<EnumerationClaim> for ClaimData {
     }
 }
 
+impl From<DateClaim> for ClaimData {
+    fn from(c: DateClaim) -> Self {
+        Self::Date(c)
+    }
+}
+
+impl From<DateTimeClaim> for ClaimData {
+    fn from(c: DateTimeClaim) -> Self {
+        Self::DateTime(c)
+    }
+}
+
+impl From<DecimalClaim> for ClaimData {
+    fn from(c: DecimalClaim) -> Self {
+        Self::Decimal(c)
+    }
+}
+
 impl ClaimData {
     /// Get the scalar to be signed
     pub fn to_scalar(&self) -> Scalar {
@@ -70,6 +107,10 @@ impl ClaimData {
             Self::Scalar(s) => s.to_scalar(),
             Self::Revocation(r) => r.to_scalar(),
             Self::Enumeration(e) => e.to_scalar(),
+            Self::Date(d) => d.to_scalar(),
+            Self::DateTime(d) => d.to_scalar(),
+            Self::Decimal(d) => d.to_scalar(),
+            Self::Absent => Self::absent_scalar(),
         }
     }
 
@@ -81,6 +122,14 @@ impl ClaimData {
             Self::Scalar(s) => s.value.to_be_bytes().to_vec(),
             Self::Revocation(r) => r.value.as_bytes().to_vec(),
             Self::Enumeration(e) => vec![e.value],
+            Self::Date(d) => (d.value as i64).to_be_bytes().to_vec(),
+            Self::DateTime(d) => (d.value as i64).to_be_bytes().to_vec(),
+            Self::Decimal(d) => {
+                let mut bytes = (d.value as i64).to_be_bytes().to_vec();
+                bytes.push(d.scale);
+                bytes
+            }
+            Self::Absent => Vec::new(),
         }
     }
 
@@ -134,10 +183,57 @@ impl ClaimData {
                 })?;
                 Ok(Self::Revocation(RevocationClaim { value: s }))
             }
+            ClaimType::Date | ClaimType::DateTime => {
+                let value = <[u8; 8]>::try_from(data)
+                    .map(i64::from_be_bytes)
+                    .map_err(|_| Error::InvalidClaimData("date claim size must be 8"))?
+                    as isize;
+                if claim_type == ClaimType::Date {
+                    Ok(Self::Date(DateClaim { value }))
+                } else {
+                    Ok(Self::DateTime(DateTimeClaim { value }))
+                }
+            }
+            ClaimType::Decimal => {
+                if data.len() != 9 {
+                    return Err(Error::InvalidClaimData("decimal claim size must be 9"));
+                }
+                let value = i64::from_be_bytes(<[u8; 8]>::try_from(&data[..8]).unwrap()) as isize;
+                Ok(Self::Decimal(DecimalClaim {
+                    value,
+                    scale: data[8],
+                }))
+            }
             _ => Err(Error::InvalidClaimData("unknown claim type")),
         }
     }
 
+    /// The type of this claim
+    pub fn claim_type(&self) -> ClaimType {
+        match self {
+            Self::Hashed(_) => ClaimType::Hashed,
+            Self::Number(_) => ClaimType::Number,
+            Self::Scalar(_) => ClaimType::Scalar,
+            Self::Revocation(_) => ClaimType::Revocation,
+            Self::Enumeration(_) => ClaimType::Enumeration,
+            Self::Date(_) => ClaimType::Date,
+            Self::DateTime(_) => ClaimType::DateTime,
+            Self::Decimal(_) => ClaimType::Decimal,
+            Self::Absent => ClaimType::Unknown,
+        }
+    }
+
+    /// The scalar signed for absent optional claims.
+    /// It's derived by hashing so it can't be the value of another claim
+    pub fn absent_scalar() -> Scalar {
+        Scalar::hash::<ExpandMsgXmd<sha2::Sha256>>(b"absent claim", ABSENT_CLAIM_DST)
+    }
+
+    /// [`true`] if this is an absent optional claim
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
     /// [`true`] if the claim is the right type
     /// [`false`] if the claim is the incorrect type
     pub fn is_type(&self, claim_type: ClaimType) -> bool {
@@ -146,7 +242,10 @@ impl ClaimData {
             | (Self::Number(_), ClaimType::Number)
             | (Self::Scalar(_), ClaimType::Scalar)
             | (Self::Revocation(_), ClaimType::Revocation)
-            | (Self::Enumeration(_), ClaimType::Enumeration) => true,
+            | (Self::Enumeration(_), ClaimType::Enumeration)
+            | (Self::Date(_), ClaimType::Date)
+            | (Self::DateTime(_), ClaimType::DateTime)
+            | (Self::Decimal(_), ClaimType::Decimal) => true,
             (_, _) => false,
         }
     }
@@ -184,6 +283,19 @@ impl ClaimData {
                 let data = serde_bare::to_vec(&e).unwrap();
                 s.push_str(&hex::encode(data.as_slice()))
             }
+            ClaimData::Date(DateClaim { value }) => {
+                s.push_str(DATE);
+                s.push_str(&value.to_string());
+            }
+            ClaimData::DateTime(DateTimeClaim { value }) => {
+                s.push_str(DATETIME);
+                s.push_str(&value.to_string());
+            }
+            ClaimData::Decimal(d) => {
+                s.push_str(DECIMAL);
+                s.push_str(&d.to_decimal_string());
+            }
+            ClaimData::Absent => s.push_str(ABSENT),
         }
         s
     }
@@ -233,6 +345,20 @@ impl ClaimData {
                     })?;
                 Ok(ClaimData::Enumeration(e))
             }
+            DATE => {
+                let value = s[4..]
+                    .parse::<isize>()
+                    .map_err(|_| Error::InvalidClaimData("unable to deserialize date claim"))?;
+                Ok(ClaimData::Date(DateClaim { value }))
+            }
+            DATETIME => {
+                let value = s[4..]
+                    .parse::<isize>()
+                    .map_err(|_| Error::InvalidClaimData("unable to deserialize datetime claim"))?;
+                Ok(ClaimData::DateTime(DateTimeClaim { value }))
+            }
+            DECIMAL => Ok(ClaimData::Decimal(DecimalClaim::from_str(&s[4..])?)),
+            ABSENT => Ok(ClaimData::Absent),
             _ => Err(Error::InvalidClaimData("unknown claim type")),
         }
     }
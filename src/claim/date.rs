@@ -0,0 +1,204 @@
+use super::{Claim, ClaimType};
+use crate::error::Error;
+use crate::utils::get_num_scalar;
+use blsful::inner_types::Scalar;
+use chrono::{DateTime, NaiveDate, Utc};
+use core::{
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
+use serde::{Deserialize, Serialize};
+
+/// A claim that is a calendar date.
+///
+/// The value is the number of days since the Unix epoch 1970-01-01
+/// so dates can be compared with range proofs.
+#[derive(Copy, Clone, Eq, Debug, Deserialize, Serialize)]
+pub struct DateClaim {
+    /// The number of days since 1970-01-01
+    pub value: isize,
+}
+
+impl PartialEq for DateClaim {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Hash for DateClaim {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl Display for DateClaim {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.to_date() {
+            Some(d) => write!(f, "DateClaim {{ {} }}", d),
+            None => write!(f, "DateClaim {{ {} }}", self.value),
+        }
+    }
+}
+
+impl From<NaiveDate> for DateClaim {
+    fn from(date: NaiveDate) -> Self {
+        Self {
+            value: (date - DateTime::UNIX_EPOCH.date_naive()).num_days() as isize,
+        }
+    }
+}
+
+impl From<isize> for DateClaim {
+    fn from(value: isize) -> Self {
+        Self { value }
+    }
+}
+
+impl Claim for DateClaim {
+    type Value = isize;
+
+    fn get_type(&self) -> ClaimType {
+        ClaimType::Date
+    }
+
+    fn to_scalar(&self) -> Scalar {
+        get_num_scalar(self.value)
+    }
+
+    fn get_value(&self) -> Self::Value {
+        self.value
+    }
+}
+
+impl DateClaim {
+    /// Parse a RFC3339 date in the format of `YYYY-MM-DD`
+    pub fn parse_rfc3339_date<S: AsRef<str>>(date: S) -> Result<Self, Error> {
+        let dt = NaiveDate::parse_from_str(date.as_ref(), "%Y-%m-%d")
+            .map_err(|_| Error::InvalidClaimData("Invalid RFC3339 date"))?;
+        Ok(Self::from(dt))
+    }
+
+    /// The date for this claim, [`None`] if it's outside the supported calendar range
+    pub fn to_date(&self) -> Option<NaiveDate> {
+        DateTime::UNIX_EPOCH
+            .date_naive()
+            .checked_add_signed(chrono::Duration::try_days(self.value as i64)?)
+    }
+
+    /// The date `years` before `date`.
+    /// February 29th becomes February 28th in years that aren't leap years.
+    pub fn years_before(date: NaiveDate, years: u32) -> Option<NaiveDate> {
+        date.checked_sub_months(chrono::Months::new(years.checked_mul(12)?))
+    }
+}
+
+/// A claim that is a point in time.
+///
+/// The value is the number of seconds since the Unix epoch 1970-01-01T00:00:00Z
+/// so timestamps can be compared with range proofs.
+#[derive(Copy, Clone, Eq, Debug, Deserialize, Serialize)]
+pub struct DateTimeClaim {
+    /// The number of seconds since 1970-01-01T00:00:00Z
+    pub value: isize,
+}
+
+impl PartialEq for DateTimeClaim {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Hash for DateTimeClaim {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl Display for DateTimeClaim {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.to_datetime() {
+            Some(d) => write!(f, "DateTimeClaim {{ {} }}", d.to_rfc3339()),
+            None => write!(f, "DateTimeClaim {{ {} }}", self.value),
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for DateTimeClaim {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        Self {
+            value: datetime.timestamp() as isize,
+        }
+    }
+}
+
+impl From<isize> for DateTimeClaim {
+    fn from(value: isize) -> Self {
+        Self { value }
+    }
+}
+
+impl Claim for DateTimeClaim {
+    type Value = isize;
+
+    fn get_type(&self) -> ClaimType {
+        ClaimType::DateTime
+    }
+
+    fn to_scalar(&self) -> Scalar {
+        get_num_scalar(self.value)
+    }
+
+    fn get_value(&self) -> Self::Value {
+        self.value
+    }
+}
+
+impl DateTimeClaim {
+    /// Parse a RFC3339 datetime in the format of `YYYY-MM-DDTHH:MM:SSZ`.
+    /// Fractions of a second are truncated.
+    pub fn parse_rfc3339_datetime<S: AsRef<str>>(datetime: S) -> Result<Self, Error> {
+        let dt = DateTime::parse_from_rfc3339(datetime.as_ref())
+            .map_err(|_| Error::InvalidClaimData("Invalid RFC3339 datetime"))?;
+        Ok(Self::from(dt.with_timezone(&Utc)))
+    }
+
+    /// The point in time for this claim, [`None`] if it's outside the supported range
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.value as i64, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date() {
+        let claim = DateClaim::parse_rfc3339_date("1970-01-01").unwrap();
+        assert_eq!(claim.value, 0);
+        let claim = DateClaim::parse_rfc3339_date("1969-12-31").unwrap();
+        assert_eq!(claim.value, -1);
+        let claim = DateClaim::parse_rfc3339_date("2000-02-17").unwrap();
+        assert_eq!(claim.value, 11004);
+        assert_eq!(claim.to_date(), NaiveDate::from_ymd_opt(2000, 2, 17));
+        assert!(DateClaim::parse_rfc3339_date("2001-02-29").is_err());
+
+        let leap = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(
+            DateClaim::years_before(leap, 18),
+            NaiveDate::from_ymd_opt(2006, 2, 28)
+        );
+    }
+
+    #[test]
+    fn test_datetime() {
+        let claim = DateTimeClaim::parse_rfc3339_datetime("2021-01-01T00:00:00Z").unwrap();
+        assert_eq!(claim.value, 1609459200);
+        let claim = DateTimeClaim::parse_rfc3339_datetime("2021-01-01T02:00:00+02:00").unwrap();
+        assert_eq!(claim.value, 1609459200);
+        assert_eq!(
+            claim.to_datetime().unwrap().to_rfc3339(),
+            "2021-01-01T00:00:00+00:00"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use super::{Claim, ClaimType};
+use crate::error::Error;
+use crate::utils::get_num_scalar;
+use blsful::inner_types::Scalar;
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+use serde::{Deserialize, Serialize};
+
+/// The largest supported number of fractional digits
+pub const MAX_DECIMAL_SCALE: u8 = 18;
+
+/// A claim that is a fixed-point decimal number.
+///
+/// The number is `value` / 10^`scale`, i.e. 123.45 is `value` 12345 with a `scale` of 2.
+/// Issuers convert the claim to the scale in the schema so the `value`
+/// can be compared with range proofs.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub struct DecimalClaim {
+    /// The unscaled value
+    pub value: isize,
+    /// The number of fractional digits in `value`
+    pub scale: u8,
+}
+
+impl Display for DecimalClaim {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "DecimalClaim {{ {} }}", self.to_decimal_string())
+    }
+}
+
+impl From<isize> for DecimalClaim {
+    fn from(value: isize) -> Self {
+        Self { value, scale: 0 }
+    }
+}
+
+impl FromStr for DecimalClaim {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const ERR: Error = Error::InvalidClaimData("invalid decimal");
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(d) => (true, d),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() || !integer.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ERR);
+        }
+        if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ERR);
+        }
+        let scale = u8::try_from(fraction.len()).map_err(|_| ERR)?;
+        if scale > MAX_DECIMAL_SCALE {
+            return Err(ERR);
+        }
+        let mut value = 0i64;
+        for b in integer.bytes().chain(fraction.bytes()) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((b - b'0') as i64))
+                .ok_or(ERR)?;
+        }
+        if negative {
+            value = -value;
+        }
+        Ok(Self {
+            value: value as isize,
+            scale,
+        })
+    }
+}
+
+impl Claim for DecimalClaim {
+    type Value = isize;
+
+    fn get_type(&self) -> ClaimType {
+        ClaimType::Decimal
+    }
+
+    fn to_scalar(&self) -> Scalar {
+        get_num_scalar(self.value)
+    }
+
+    fn get_value(&self) -> Self::Value {
+        self.value
+    }
+}
+
+impl DecimalClaim {
+    /// Create a new decimal claim that is `value` / 10^`scale`
+    pub fn new(value: isize, scale: u8) -> Self {
+        Self { value, scale }
+    }
+
+    /// Convert to the same number with `scale` fractional digits.
+    /// [`None`] if the number can't be represented exactly at `scale`
+    pub fn rescale(&self, scale: u8) -> Option<Self> {
+        if scale > MAX_DECIMAL_SCALE {
+            return None;
+        }
+        let value = if scale >= self.scale {
+            let factor = 10isize.checked_pow((scale - self.scale) as u32)?;
+            self.value.checked_mul(factor)?
+        } else {
+            let factor = 10isize.checked_pow((self.scale - scale) as u32)?;
+            if self.value % factor != 0 {
+                return None;
+            }
+            self.value / factor
+        };
+        Some(Self { value, scale })
+    }
+
+    /// The number as a decimal string with `scale` fractional digits
+    pub fn to_decimal_string(&self) -> String {
+        let sign = if self.value < 0 { "-" } else { "" };
+        let digits = self.value.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return format!("{}{}", sign, digits);
+        }
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let claim = DecimalClaim::from_str("123.45").unwrap();
+        assert_eq!(claim, DecimalClaim::new(12345, 2));
+        assert_eq!(claim.to_decimal_string(), "123.45");
+        let claim = DecimalClaim::from_str("-0.050").unwrap();
+        assert_eq!(claim, DecimalClaim::new(-50, 3));
+        assert_eq!(claim.to_decimal_string(), "-0.050");
+        assert_eq!(
+            DecimalClaim::from_str("42").unwrap(),
+            DecimalClaim::new(42, 0)
+        );
+        assert!(DecimalClaim::from_str("").is_err());
+        assert!(DecimalClaim::from_str(".5").is_err());
+        assert!(DecimalClaim::from_str("1.2.3").is_err());
+        assert!(DecimalClaim::from_str("1e5").is_err());
+        assert!(DecimalClaim::from_str("0.1234567890123456789").is_err());
+        assert!(DecimalClaim::from_str("99999999999999999999").is_err());
+    }
+
+    #[test]
+    fn test_rescale() {
+        let claim = DecimalClaim::new(12345, 2);
+        assert_eq!(claim.rescale(4), Some(DecimalClaim::new(1234500, 4)));
+        assert_eq!(claim.rescale(1), None);
+        assert_eq!(
+            DecimalClaim::new(12340, 3).rescale(2),
+            Some(DecimalClaim::new(1234, 2))
+        );
+        assert_eq!(DecimalClaim::new(isize::MAX, 0).rescale(1), None);
+    }
+}
@@ -0,0 +1,33 @@
+use super::ClaimData;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Claim data that keeps the structure of object and array claims
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum NestedClaimData {
+    /// A single claim
+    Claim(ClaimData),
+    /// The claims of an object by property name
+    Object(BTreeMap<String, NestedClaimData>),
+    /// The claims of an array by index.
+    /// Indices that are not present were not disclosed
+    Array(BTreeMap<usize, NestedClaimData>),
+}
+
+impl From<ClaimData> for NestedClaimData {
+    fn from(value: ClaimData) -> Self {
+        Self::Claim(value)
+    }
+}
+
+impl From<BTreeMap<String, NestedClaimData>> for NestedClaimData {
+    fn from(value: BTreeMap<String, NestedClaimData>) -> Self {
+        Self::Object(value)
+    }
+}
+
+impl From<Vec<NestedClaimData>> for NestedClaimData {
+    fn from(value: Vec<NestedClaimData>) -> Self {
+        Self::Array(value.into_iter().enumerate().collect())
+    }
+}
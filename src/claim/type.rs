@@ -6,10 +6,11 @@ use std::fmt::{Display, Error as FmtError, Formatter};
 use std::str::FromStr;
 
 /// The claim type
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 #[repr(u8)]
 pub enum ClaimType {
     /// The case where its none of the others
+    #[default]
     Unknown = 0,
     /// Hashed claims
     Hashed = 1,
@@ -21,6 +22,16 @@ pub enum ClaimType {
     Revocation = 4,
     /// Enumeration based claims
     Enumeration = 5,
+    /// Object claims made up of named claims
+    Object = 6,
+    /// Array claims made up of indexed claims
+    Array = 7,
+    /// Calendar date claims
+    Date = 8,
+    /// Timestamp claims
+    DateTime = 9,
+    /// Fixed-point decimal claims
+    Decimal = 10,
 }
 
 impl FromStr for ClaimType {
@@ -33,6 +44,11 @@ impl FromStr for ClaimType {
             "number" => Ok(Self::Number),
             "scalar" => Ok(Self::Scalar),
             "revocation" => Ok(Self::Revocation),
+            "object" => Ok(Self::Object),
+            "array" => Ok(Self::Array),
+            "date" => Ok(Self::Date),
+            "datetime" => Ok(Self::DateTime),
+            "decimal" => Ok(Self::Decimal),
             _ => Err("invalid type".to_string()),
         }
     }
@@ -46,6 +62,11 @@ impl Display for ClaimType {
             Self::Number => write!(f, "Number"),
             Self::Revocation => write!(f, "Revocation"),
             Self::Scalar => write!(f, "Scalar"),
+            Self::Object => write!(f, "Object"),
+            Self::Array => write!(f, "Array"),
+            Self::Date => write!(f, "Date"),
+            Self::DateTime => write!(f, "DateTime"),
+            Self::Decimal => write!(f, "Decimal"),
             _ => Err(FmtError),
         }
     }
@@ -59,6 +80,11 @@ impl From<u8> for ClaimType {
             3 => Self::Scalar,
             4 => Self::Revocation,
             5 => Self::Enumeration,
+            6 => Self::Object,
+            7 => Self::Array,
+            8 => Self::Date,
+            9 => Self::DateTime,
+            10 => Self::Decimal,
             _ => Self::Unknown,
         }
     }
@@ -1,4 +1,4 @@
-use crate::claim::ClaimData;
+use crate::claim::{ClaimData, DateClaim, DateTimeClaim};
 use crate::error::Error;
 use crate::CredxResult;
 use regex::Regex;
@@ -19,6 +19,24 @@ pub enum ClaimValidatorType {
     Regex = 3,
     /// The any one list type
     AnyOne = 4,
+    /// The date range type
+    DateRange = 5,
+    /// The datetime range type
+    DateTimeRange = 6,
+    /// The all of type
+    And = 7,
+    /// The at least one of type
+    Or = 8,
+    /// The negation type
+    Not = 9,
+    /// The prefix type
+    Prefix = 10,
+    /// The suffix type
+    Suffix = 11,
+    /// The checksum type
+    Checksum = 12,
+    /// The deny list type
+    NotAnyOf = 13,
 }
 
 impl From<u8> for ClaimValidatorType {
@@ -28,6 +46,15 @@ impl From<u8> for ClaimValidatorType {
             2 => Self::Range,
             3 => Self::Regex,
             4 => Self::AnyOne,
+            5 => Self::DateRange,
+            6 => Self::DateTimeRange,
+            7 => Self::And,
+            8 => Self::Or,
+            9 => Self::Not,
+            10 => Self::Prefix,
+            11 => Self::Suffix,
+            12 => Self::Checksum,
+            13 => Self::NotAnyOf,
             _ => Self::Unknown,
         }
     }
@@ -63,8 +90,157 @@ pub enum ClaimValidator {
     Regex(Regex),
     /// The claim data must be one of these
     AnyOne(Vec<ClaimData>),
+    /// The Date claim data must be between `min` and `max`
+    DateRange {
+        /// The earliest inclusive date
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<DateClaim>,
+        /// The latest inclusive date
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<DateClaim>,
+    },
+    /// The DateTime claim data must be between `min` and `max`
+    DateTimeRange {
+        /// The earliest inclusive point in time
+        #[serde(skip_serializing_if = "Option::is_none")]
+        min: Option<DateTimeClaim>,
+        /// The latest inclusive point in time
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max: Option<DateTimeClaim>,
+    },
+    /// The claim data must meet all of these validators
+    And(Vec<ClaimValidator>),
+    /// The claim data must meet at least one of these validators,
+    /// validators that don't apply to the claim type are skipped
+    Or(Vec<ClaimValidator>),
+    /// The claim data must not meet this validator
+    Not(Box<ClaimValidator>),
+    /// The Hashed claim data must start with this value
+    Prefix(String),
+    /// The Hashed claim data must end with this value
+    Suffix(String),
+    /// The Hashed claim data must have a valid check digit
+    Checksum(ChecksumAlgorithm),
+    /// The claim data must not be any of these
+    NotAnyOf(Vec<ClaimData>),
+}
+
+/// The check digit algorithms for the checksum validator
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[repr(u8)]
+pub enum ChecksumAlgorithm {
+    /// The Luhn mod 10 algorithm used by payment card numbers
+    Luhn = 1,
+    /// ISO 7064 MOD 11-2 where the check character can be `X`
+    Iso7064Mod11_2 = 2,
+    /// ISO 7064 MOD 97-10 where letters are `A` = 10 to `Z` = 35 as used by IBANs
+    Iso7064Mod97_10 = 3,
 }
 
+impl ChecksumAlgorithm {
+    /// [`true`] if `value` including its check characters is valid
+    pub fn is_valid(&self, value: &str) -> bool {
+        if value.is_empty() {
+            return false;
+        }
+        match self {
+            Self::Luhn => {
+                let mut sum = 0;
+                for (i, c) in value.chars().rev().enumerate() {
+                    let d = match c.to_digit(10) {
+                        Some(d) => d,
+                        None => return false,
+                    };
+                    sum += if i % 2 == 1 {
+                        let d = d * 2;
+                        if d > 9 {
+                            d - 9
+                        } else {
+                            d
+                        }
+                    } else {
+                        d
+                    };
+                }
+                value.len() > 1 && sum % 10 == 0
+            }
+            Self::Iso7064Mod11_2 => {
+                let last = value.len() - 1;
+                let mut p = 0;
+                for (i, c) in value.chars().enumerate() {
+                    let d = match c {
+                        'X' | 'x' if i == last => 10,
+                        _ => match c.to_digit(10) {
+                            Some(d) => d,
+                            None => return false,
+                        },
+                    };
+                    p = (p * 2 + d) % 11;
+                }
+                value.len() > 1 && p == 1
+            }
+            Self::Iso7064Mod97_10 => {
+                let mut p = 0;
+                for c in value.chars() {
+                    let d = match c.to_digit(36) {
+                        Some(d) => d,
+                        None => return false,
+                    };
+                    p = if d < 10 { p * 10 + d } else { p * 100 + d } % 97;
+                }
+                value.len() > 2 && p == 1
+            }
+        }
+    }
+}
+
+impl PartialEq for ClaimValidator {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Length { min, max },
+                Self::Length {
+                    min: other_min,
+                    max: other_max,
+                },
+            ) => min == other_min && max == other_max,
+            (
+                Self::Range { min, max },
+                Self::Range {
+                    min: other_min,
+                    max: other_max,
+                },
+            ) => min == other_min && max == other_max,
+            (Self::Regex(rx), Self::Regex(other_rx)) => rx.as_str() == other_rx.as_str(),
+            (Self::AnyOne(claims), Self::AnyOne(other_claims)) => claims == other_claims,
+            (
+                Self::DateRange { min, max },
+                Self::DateRange {
+                    min: other_min,
+                    max: other_max,
+                },
+            ) => min == other_min && max == other_max,
+            (
+                Self::DateTimeRange { min, max },
+                Self::DateTimeRange {
+                    min: other_min,
+                    max: other_max,
+                },
+            ) => min == other_min && max == other_max,
+            (Self::And(v), Self::And(other_v)) => v == other_v,
+            (Self::Or(v), Self::Or(other_v)) => v == other_v,
+            (Self::Not(v), Self::Not(other_v)) => v == other_v,
+            (Self::Prefix(p), Self::Prefix(other_p)) => p == other_p,
+            (Self::Suffix(s), Self::Suffix(other_s)) => s == other_s,
+            (Self::Checksum(a), Self::Checksum(other_a)) => a == other_a,
+            (Self::NotAnyOf(claims), Self::NotAnyOf(other_claims)) => claims == other_claims,
+            (_, _) => false,
+        }
+    }
+}
+
+impl Eq for ClaimValidator {}
+
 impl ClaimValidator {
     /// [`Some(true)`] if the claim is the right type and meets the validator requirements
     /// [`Some(false)`] if the claim is the right type but doesn't meet the requirements
@@ -103,6 +279,53 @@ impl ClaimValidator {
                 _ => None,
             },
             Self::AnyOne(claims) => Some(claims.iter().any(|c| c == claim)),
+            Self::DateRange { min, max } => match claim {
+                ClaimData::Date(d) => {
+                    let min = min.map_or(isize::MIN, |m| m.value);
+                    let max = max.map_or(isize::MAX, |m| m.value);
+                    Some(min <= d.value && d.value <= max)
+                }
+                _ => None,
+            },
+            Self::DateTimeRange { min, max } => match claim {
+                ClaimData::DateTime(d) => {
+                    let min = min.map_or(isize::MIN, |m| m.value);
+                    let max = max.map_or(isize::MAX, |m| m.value);
+                    Some(min <= d.value && d.value <= max)
+                }
+                _ => None,
+            },
+            Self::And(validators) => {
+                let mut result = true;
+                for v in validators {
+                    result &= v.is_valid(claim)?;
+                }
+                Some(result)
+            }
+            Self::Or(validators) => validators
+                .iter()
+                .filter_map(|v| v.is_valid(claim))
+                .reduce(|a, b| a || b),
+            Self::Not(v) => v.is_valid(claim).map(|b| !b),
+            Self::Prefix(prefix) => match claim {
+                ClaimData::Hashed(h) => Some(h.value.starts_with(prefix.as_bytes())),
+                ClaimData::Revocation(h) => Some(h.value.starts_with(prefix.as_str())),
+                _ => None,
+            },
+            Self::Suffix(suffix) => match claim {
+                ClaimData::Hashed(h) => Some(h.value.ends_with(suffix.as_bytes())),
+                ClaimData::Revocation(h) => Some(h.value.ends_with(suffix.as_str())),
+                _ => None,
+            },
+            Self::Checksum(algorithm) => match claim {
+                ClaimData::Hashed(h) => match core::str::from_utf8(&h.value) {
+                    Err(_) => None,
+                    Ok(s) => Some(algorithm.is_valid(s)),
+                },
+                ClaimData::Revocation(h) => Some(algorithm.is_valid(&h.value)),
+                _ => None,
+            },
+            Self::NotAnyOf(claims) => Some(claims.iter().all(|c| c != claim)),
         }
     }
 
@@ -158,6 +381,107 @@ impl ClaimValidator {
                     );
                 }
             }
+            Self::DateRange { min, max } => {
+                transcript.append_message(
+                    b"claim validator type",
+                    &[ClaimValidatorType::DateRange as u8],
+                );
+                transcript.append_message(
+                    b"claim validator date range - min",
+                    &min.map_or_else(Vec::new, |d| Uint::from(d.value).to_vec()),
+                );
+                transcript.append_message(
+                    b"claim validator date range - max",
+                    &max.map_or_else(Vec::new, |d| Uint::from(d.value).to_vec()),
+                );
+            }
+            Self::DateTimeRange { min, max } => {
+                transcript.append_message(
+                    b"claim validator type",
+                    &[ClaimValidatorType::DateTimeRange as u8],
+                );
+                transcript.append_message(
+                    b"claim validator datetime range - min",
+                    &min.map_or_else(Vec::new, |d| Uint::from(d.value).to_vec()),
+                );
+                transcript.append_message(
+                    b"claim validator datetime range - max",
+                    &max.map_or_else(Vec::new, |d| Uint::from(d.value).to_vec()),
+                );
+            }
+            Self::And(validators) => {
+                transcript
+                    .append_message(b"claim validator type", &[ClaimValidatorType::And as u8]);
+                Self::add_validators_challenge_contribution(validators, transcript);
+            }
+            Self::Or(validators) => {
+                transcript.append_message(b"claim validator type", &[ClaimValidatorType::Or as u8]);
+                Self::add_validators_challenge_contribution(validators, transcript);
+            }
+            Self::Not(v) => {
+                transcript
+                    .append_message(b"claim validator type", &[ClaimValidatorType::Not as u8]);
+                v.add_challenge_contribution(transcript);
+            }
+            Self::Prefix(prefix) => {
+                transcript
+                    .append_message(b"claim validator type", &[ClaimValidatorType::Prefix as u8]);
+                transcript.append_message(b"claim validator prefix", prefix.as_bytes());
+            }
+            Self::Suffix(suffix) => {
+                transcript
+                    .append_message(b"claim validator type", &[ClaimValidatorType::Suffix as u8]);
+                transcript.append_message(b"claim validator suffix", suffix.as_bytes());
+            }
+            Self::Checksum(algorithm) => {
+                transcript.append_message(
+                    b"claim validator type",
+                    &[ClaimValidatorType::Checksum as u8],
+                );
+                transcript
+                    .append_message(b"claim validator checksum algorithm", &[*algorithm as u8]);
+            }
+            Self::NotAnyOf(set) => {
+                transcript.append_message(
+                    b"claim validator type",
+                    &[ClaimValidatorType::NotAnyOf as u8],
+                );
+                transcript.append_message(
+                    b"claim validator not any of length",
+                    &Uint::from(set.len()).to_vec(),
+                );
+                for (index, c) in set.iter().enumerate() {
+                    transcript.append_message(
+                        b"claim validator not any of claim index",
+                        &Uint::from(index).to_vec(),
+                    );
+                    transcript.append_message(
+                        b"claim validator not any of claim raw data",
+                        &c.to_bytes(),
+                    );
+                    transcript.append_message(
+                        b"claim validator not any of claim mapped data",
+                        &c.to_scalar().to_be_bytes(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn add_validators_challenge_contribution(
+        validators: &[ClaimValidator],
+        transcript: &mut merlin::Transcript,
+    ) {
+        transcript.append_message(
+            b"claim validator validators length",
+            &Uint::from(validators.len()).to_vec(),
+        );
+        for (index, v) in validators.iter().enumerate() {
+            transcript.append_message(
+                b"claim validator validators index",
+                &Uint::from(index).to_vec(),
+            );
+            v.add_challenge_contribution(transcript);
         }
     }
 
@@ -167,3 +491,95 @@ impl ClaimValidator {
         Ok(Self::Regex(rx))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claim::HashedClaim;
+
+    #[test]
+    fn test_checksums() {
+        assert!(ChecksumAlgorithm::Luhn.is_valid("79927398713"));
+        assert!(ChecksumAlgorithm::Luhn.is_valid("4111111111111111"));
+        assert!(!ChecksumAlgorithm::Luhn.is_valid("79927398710"));
+        assert!(!ChecksumAlgorithm::Luhn.is_valid("7992 7398 713"));
+        assert!(!ChecksumAlgorithm::Luhn.is_valid("0"));
+
+        assert!(ChecksumAlgorithm::Iso7064Mod11_2.is_valid("079X"));
+        assert!(ChecksumAlgorithm::Iso7064Mod11_2.is_valid("0000000121032683"));
+        assert!(!ChecksumAlgorithm::Iso7064Mod11_2.is_valid("0794"));
+        assert!(!ChecksumAlgorithm::Iso7064Mod11_2.is_valid("07X9"));
+
+        // GB82WEST12345698765432 with the country code and check digits moved to the end
+        assert!(ChecksumAlgorithm::Iso7064Mod97_10.is_valid("WEST12345698765432GB82"));
+        assert!(!ChecksumAlgorithm::Iso7064Mod97_10.is_valid("WEST12345698765432GB83"));
+        assert!(!ChecksumAlgorithm::Iso7064Mod97_10.is_valid("WEST-12345698765432GB82"));
+    }
+
+    #[test]
+    fn test_composite_validators() {
+        let validator = ClaimValidator::And(vec![
+            ClaimValidator::Checksum(ChecksumAlgorithm::Luhn),
+            ClaimValidator::Prefix("4".to_string()),
+            ClaimValidator::Not(Box::new(ClaimValidator::AnyOne(vec![HashedClaim::from(
+                "4111111111111111",
+            )
+            .into()]))),
+        ]);
+        assert_eq!(
+            validator.is_valid(&HashedClaim::from("4012888888881881").into()),
+            Some(true)
+        );
+        assert_eq!(
+            validator.is_valid(&HashedClaim::from("4111111111111111").into()),
+            Some(false)
+        );
+        assert_eq!(
+            validator.is_valid(&HashedClaim::from("5555555555554444").into()),
+            Some(false)
+        );
+        assert_eq!(
+            validator.is_valid(&crate::claim::NumberClaim::from(4).into()),
+            None
+        );
+
+        let validator = ClaimValidator::Or(vec![
+            ClaimValidator::Suffix("@example.com".to_string()),
+            ClaimValidator::Suffix("@example.org".to_string()),
+        ]);
+        assert_eq!(
+            validator.is_valid(&HashedClaim::from("john@example.org").into()),
+            Some(true)
+        );
+        assert_eq!(
+            validator.is_valid(&HashedClaim::from("john@example.net").into()),
+            Some(false)
+        );
+
+        let validator = ClaimValidator::NotAnyOf(vec![HashedClaim::from("KP").into()]);
+        assert_eq!(
+            validator.is_valid(&HashedClaim::from("KP").into()),
+            Some(false)
+        );
+        assert_eq!(
+            validator.is_valid(&HashedClaim::from("AU").into()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let validator = ClaimValidator::And(vec![
+            ClaimValidator::Checksum(ChecksumAlgorithm::Iso7064Mod97_10),
+            ClaimValidator::Not(Box::new(ClaimValidator::Prefix("00".to_string()))),
+            ClaimValidator::Suffix("X".to_string()),
+        ]);
+        let json = serde_json::to_string(&validator).unwrap();
+        assert_eq!(
+            json,
+            r#"{"And":[{"Checksum":"Iso7064Mod97_10"},{"Not":{"Prefix":"00"}},{"Suffix":"X"}]}"#
+        );
+        let validator2: ClaimValidator = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&validator2).unwrap(), json);
+    }
+}
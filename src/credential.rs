@@ -1,15 +1,19 @@
 mod bundle;
 mod membership;
 mod offer;
+mod request;
 mod schema;
+mod validation;
 
 pub use bundle::*;
 pub use membership::*;
 pub use offer::*;
+pub use request::*;
 pub use schema::*;
+pub use validation::*;
 
 use super::claim::*;
-use crate::knox::accumulator::vb20::MembershipWitness;
+use crate::knox::accumulator::vb20::{MembershipWitness, NonMembershipWitness};
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use serde::{Deserialize, Serialize};
 
@@ -24,4 +28,16 @@ pub struct Credential<S: ShortGroupSignatureScheme> {
     pub revocation_handle: MembershipCredential,
     /// The claim that is used for revocation
     pub revocation_index: usize,
+    /// The issuer key epoch that signed this credential
+    #[serde(default)]
+    pub key_epoch: usize,
+    /// The revocation registry epoch of the revocation handle
+    #[serde(default)]
+    pub revocation_epoch: usize,
+    /// The suspension handle
+    #[serde(default)]
+    pub suspension_handle: Option<NonMembershipWitness>,
+    /// The suspension registry epoch of the suspension handle
+    #[serde(default)]
+    pub suspension_epoch: usize,
 }
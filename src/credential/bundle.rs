@@ -1,6 +1,11 @@
-use crate::knox::accumulator::vb20::Accumulator;
+use crate::error::Error;
+use crate::knox::accumulator::vb20::{Accumulator, Coefficient, Element, NonMembershipWitness};
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
-use crate::{credential::*, issuer::*};
+use crate::revocation_registry::{
+    RegistryEpoch, RegistryUpdate, WitnessRefreshRequest, WitnessRefreshService,
+};
+use crate::{credential::*, issuer::*, CredxResult};
+use blsful::inner_types::Scalar;
 use serde::{Deserialize, Serialize};
 
 /// A credential and the issuer's information
@@ -30,4 +35,147 @@ impl<S: ShortGroupSignatureScheme> CredentialBundle<S> {
         self.credential.revocation_handle = revocation_handle;
         self.issuer.revocation_registry = revocation_registry;
     }
+
+    /// Replace the revocation handle with a new one the issuer made at `registry`
+    pub fn replace_revocation_handle(
+        &mut self,
+        revocation_handle: MembershipWitness,
+        registry: RegistryEpoch,
+    ) {
+        self.credential.revocation_handle = revocation_handle;
+        self.credential.revocation_epoch = registry.epoch;
+        self.issuer.revocation_registry = registry.value;
+        self.issuer.revocation_epoch = registry.epoch;
+    }
+
+    /// Bring the revocation handle up to the latest registry epoch.
+    /// Updates at or before the current epoch are skipped, the rest must be consecutive.
+    /// Fails if the credential has been revoked.
+    pub fn apply_registry_updates(&mut self, updates: &[RegistryUpdate]) -> CredxResult<()> {
+        let pending = pending_updates(updates, self.credential.revocation_epoch)?;
+        let latest = match pending.last() {
+            Some(latest) => *latest,
+            None => return Ok(()),
+        };
+        let deltas = pending
+            .iter()
+            .map(|u| (&u.additions, &u.deletions, &u.coefficients))
+            .collect::<Vec<_>>();
+        self.apply_revocation_deltas(&deltas, latest.epoch, latest.value)
+    }
+
+    fn apply_revocation_deltas<A, D, C>(
+        &mut self,
+        deltas: &[(A, D, C)],
+        epoch: usize,
+        value: Accumulator,
+    ) -> CredxResult<()>
+    where
+        A: AsRef<[Element]>,
+        D: AsRef<[Element]>,
+        C: AsRef<[Coefficient]>,
+    {
+        let y = Element(self.credential.claims[self.credential.revocation_index].to_scalar());
+        let handle = self
+            .credential
+            .revocation_handle
+            .multi_batch_update(y, deltas);
+        if !handle.verify(y, self.issuer.revocation_verifying_key, value) {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        self.credential.revocation_handle = handle;
+        self.credential.revocation_epoch = epoch;
+        self.issuer.revocation_registry = value;
+        self.issuer.revocation_epoch = epoch;
+        Ok(())
+    }
+
+    /// Replace the suspension handle with a new one the issuer made
+    /// at suspension registry `epoch`
+    pub fn replace_suspension_handle(
+        &mut self,
+        suspension_handle: NonMembershipWitness,
+        suspension_registry: Accumulator,
+        epoch: usize,
+    ) {
+        self.credential.suspension_handle = Some(suspension_handle);
+        self.credential.suspension_epoch = epoch;
+        self.issuer.suspension_registry = suspension_registry;
+        self.issuer.suspension_epoch = epoch;
+    }
+
+    /// Bring the suspension handle up to the latest suspension registry epoch.
+    /// Updates at or before the current epoch are skipped, the rest must be consecutive.
+    /// Fails if the credential is suspended.
+    pub fn apply_suspension_updates(&mut self, updates: &[RegistryUpdate]) -> CredxResult<()> {
+        let pending = pending_updates(updates, self.credential.suspension_epoch)?;
+        let latest = match pending.last() {
+            Some(latest) => *latest,
+            None => return Ok(()),
+        };
+        let mut handle = self
+            .credential
+            .suspension_handle
+            .ok_or(Error::InvalidRevocationHandleUpdate)?;
+
+        let y = Element(self.credential.claims[self.credential.revocation_index].to_scalar());
+        let deltas = pending
+            .iter()
+            .map(|u| (&u.additions, &u.deletions, &u.coefficients))
+            .collect::<Vec<_>>();
+        handle.multi_batch_update_assign(y, &deltas);
+        // A zero d means the element is in the suspension registry
+        if handle.d == Scalar::ZERO
+            || !handle.verify(y, self.issuer.revocation_verifying_key, latest.value)
+        {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        self.credential.suspension_handle = Some(handle);
+        self.credential.suspension_epoch = latest.epoch;
+        self.issuer.suspension_registry = latest.value;
+        self.issuer.suspension_epoch = latest.epoch;
+        Ok(())
+    }
+
+    /// Bring the revocation handle up to the latest registry epoch
+    /// using the update from a witness refresh service.
+    /// The latest registry value must be signed by the credential issuer.
+    pub fn refresh_revocation_handle(
+        &mut self,
+        service: &impl WitnessRefreshService,
+    ) -> CredxResult<()> {
+        let epoch = self.credential.revocation_epoch;
+        let response = service.refresh(&WitnessRefreshRequest { epoch })?;
+        response.verify(&self.issuer.id, &self.issuer.registry_verifying_key)?;
+        if response.epoch != epoch {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        match &response.update {
+            Some(u) => self.apply_revocation_deltas(
+                &[(&u.additions, &u.deletions, &u.coefficients)],
+                response.latest.epoch,
+                response.latest.value,
+            ),
+            None if self.issuer.revocation_registry == response.latest.value => Ok(()),
+            None => Err(Error::InvalidRevocationHandleUpdate),
+        }
+    }
+}
+
+/// The updates after `epoch` in epoch order, which must be consecutive
+pub(super) fn pending_updates(
+    updates: &[RegistryUpdate],
+    epoch: usize,
+) -> CredxResult<Vec<&RegistryUpdate>> {
+    let mut pending = updates
+        .iter()
+        .filter(|u| u.epoch > epoch)
+        .collect::<Vec<_>>();
+    pending.sort_by_key(|u| u.epoch);
+    for (i, u) in pending.iter().enumerate() {
+        if u.epoch != epoch + i + 1 {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+    }
+    Ok(pending)
 }
@@ -1,3 +1,4 @@
+use super::bundle::pending_updates;
 use crate::knox::accumulator::vb20;
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -14,10 +15,50 @@ pub type MembershipRegistry = vb20::Accumulator;
 /// A membership credential
 pub type MembershipCredential = vb20::MembershipWitness;
 
+/// A non-membership credential
+pub type NonMembershipCredential = vb20::NonMembershipWitness;
+
 /// A membership claim in the registry
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MembershipClaim(pub vb20::Element);
 
+/// A membership credential and the issuer's information
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MembershipBundle {
+    /// The issuer information that gave this credential
+    pub issuer: MembershipIssuerPublic,
+    /// The member in the registry
+    pub member: MembershipClaim,
+    /// The membership credential
+    pub credential: MembershipCredential,
+}
+
+impl MembershipBundle {
+    /// Bring the membership credential up to the latest registry epoch.
+    /// Updates at or before the current epoch are skipped, the rest must be consecutive.
+    /// Fails if the member has been removed.
+    pub fn apply_registry_updates(&mut self, updates: &[RegistryUpdate]) -> CredxResult<()> {
+        let pending = pending_updates(updates, self.issuer.epoch)?;
+        let latest = match pending.last() {
+            Some(latest) => *latest,
+            None => return Ok(()),
+        };
+
+        let deltas = pending
+            .iter()
+            .map(|u| (&u.additions, &u.deletions, &u.coefficients))
+            .collect::<Vec<_>>();
+        let credential = self.credential.multi_batch_update(self.member.0, &deltas);
+        if !credential.verify(self.member.0, self.issuer.verification_key, latest.value) {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        self.credential = credential;
+        self.issuer.registry = latest.value;
+        self.issuer.epoch = latest.epoch;
+        Ok(())
+    }
+}
+
 impl From<ClaimData> for MembershipClaim {
     fn from(value: ClaimData) -> Self {
         Self::from(&value)
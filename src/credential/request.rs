@@ -0,0 +1,25 @@
+use crate::claim::ClaimData;
+use crate::credential::CredentialSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A request to sign a credential with claims by schema label
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct CredentialRequest {
+    /// The id of the schema the claims are for.
+    /// The issuer checks this matches its schema if set
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub schema_id: Option<String>,
+    /// The claims by schema label
+    pub claims: BTreeMap<String, ClaimData>,
+}
+
+impl CredentialRequest {
+    /// Create a new request for the schema
+    pub fn new(schema: &CredentialSchema, claims: BTreeMap<String, ClaimData>) -> Self {
+        Self {
+            schema_id: Some(schema.id.clone()),
+            claims,
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use crate::claim::*;
+use crate::credential::{ClaimValidationFailure, ClaimValidationReason, ValidationReport};
 use crate::error::Error;
 use crate::{random_string, utils::*, CredxResult};
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use log::debug;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uint_zigzag::Uint;
 
 /// A credential schema
@@ -31,22 +33,38 @@ pub struct CredentialSchema {
     pub claim_indices: IndexSet<String>,
     /// The claims that can be signed
     pub claims: Vec<ClaimSchema>,
+    /// The claims as supplied before object and array claims were expanded.
+    /// Empty if the schema has no object or array claims
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub nested_claims: Vec<ClaimSchema>,
 }
 
 impl CredentialSchema {
-    /// Create a new credential schema
+    /// Create a new credential schema.
+    ///
+    /// Object and array claims are expanded into their individual claims
+    /// which are labeled using JSON pointers e.g. `/address/street` or `/nationalities/0`.
     pub fn new(
         label: Option<&str>,
         description: Option<&str>,
         blind_claims: &[&str],
         claims: &[ClaimSchema],
     ) -> CredxResult<Self> {
-        let claims = claims.to_vec();
         if claims.is_empty() {
             return Err(Error::InvalidClaimData(
                 "cannot create a schema with an empty claims list",
             ));
         }
+        let mut expanded_claims = Vec::with_capacity(claims.len());
+        for claim in claims {
+            claim.expand_into(claim.root_label(), &mut expanded_claims)?;
+        }
+        let nested_claims = if claims.iter().any(|c| c.is_nested()) {
+            claims.to_vec()
+        } else {
+            Vec::new()
+        };
+        let claims = expanded_claims;
 
         let id = random_string(16, rand::thread_rng());
         let mut claim_indices = IndexSet::new();
@@ -70,6 +88,7 @@ impl CredentialSchema {
             claim_indices,
             label: label.map(|l| l.to_string()),
             description: description.map(|d| d.to_string()),
+            nested_claims,
         };
         debug!(
             "Credential Schema: {}",
@@ -77,6 +96,106 @@ impl CredentialSchema {
         );
         Ok(schema)
     }
+
+    /// Check the claims in schema order against the schema claim types and validators.
+    /// [`Error::ClaimValidation`] lists every claim that failed
+    pub fn validate(&self, claims: &[ClaimData]) -> CredxResult<()> {
+        if claims.len() != self.claims.len() {
+            return Err(Error::InvalidClaimData("claims.len != schema.claims.len"));
+        }
+        let failures = claims
+            .iter()
+            .zip(&self.claims)
+            .flat_map(|(c, t)| t.validate(c))
+            .collect::<Vec<_>>();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ClaimValidation(ValidationReport { failures }))
+        }
+    }
+
+    /// Convert claims by label into the claim list in the order expected when signing.
+    /// Claims that aren't given use the schema default or are absent if optional.
+    /// [`Error::ClaimValidation`] lists every missing and unknown label
+    pub fn resolve_claims(
+        &self,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<Vec<ClaimData>> {
+        let mut failures = Vec::new();
+        let mut resolved = Vec::with_capacity(self.claims.len());
+        for claim in &self.claims {
+            match claims.get(&claim.label).or(claim.default.as_ref()) {
+                Some(c) => resolved.push(c.clone()),
+                None if claim.optional => resolved.push(ClaimData::Absent),
+                None => failures.push(ClaimValidationFailure {
+                    label: claim.label.clone(),
+                    validator: None,
+                    reason: ClaimValidationReason::MissingClaim,
+                }),
+            }
+        }
+        for label in claims.keys() {
+            if !self.claim_indices.contains(label) {
+                failures.push(ClaimValidationFailure {
+                    label: label.clone(),
+                    validator: None,
+                    reason: ClaimValidationReason::UnknownClaim,
+                });
+            }
+        }
+        if failures.is_empty() {
+            Ok(resolved)
+        } else {
+            Err(Error::ClaimValidation(ValidationReport { failures }))
+        }
+    }
+
+    /// Convert nested claims into the claim list in the order expected when signing
+    pub fn flatten_claims(
+        &self,
+        claims: &BTreeMap<String, NestedClaimData>,
+    ) -> CredxResult<Vec<ClaimData>> {
+        let roots = self.root_claims();
+        if claims.len() != roots.len() {
+            return Err(Error::InvalidClaimData(
+                "nested claims.len != schema.nested_claims.len",
+            ));
+        }
+        let mut flattened = Vec::with_capacity(self.claims.len());
+        for root in roots {
+            let claim = claims
+                .get(&root.label)
+                .ok_or(Error::InvalidClaimData("claim not found in nested claims"))?;
+            root.flatten_into(claim, &mut flattened)?;
+        }
+        Ok(flattened)
+    }
+
+    /// Rebuild the nested structure for the claims keyed by label
+    /// such as the disclosed messages in a presentation.
+    /// Claims that are not present are omitted.
+    pub fn nest_claims(
+        &self,
+        claims: &IndexMap<String, ClaimData>,
+    ) -> BTreeMap<String, NestedClaimData> {
+        let mut nested = BTreeMap::new();
+        for root in self.root_claims() {
+            if let Some(claim) = root.nest(&root.root_label(), claims) {
+                nested.insert(root.label.clone(), claim);
+            }
+        }
+        nested
+    }
+
+    fn root_claims(&self) -> &[ClaimSchema] {
+        if self.nested_claims.is_empty() {
+            &self.claims
+        } else {
+            &self.nested_claims
+        }
+    }
+
     /// Add data to the transcript
     pub fn add_challenge_contribution(&self, transcript: &mut merlin::Transcript) {
         let label = self
@@ -122,7 +241,7 @@ impl CredentialSchema {
 }
 
 /// A claim schema
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ClaimSchema {
     /// The claim type
     pub claim_type: ClaimType,
@@ -133,9 +252,221 @@ pub struct ClaimSchema {
     /// The claim data validators
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub validators: Vec<ClaimValidator>,
+    /// The claims that make up an object or array claim
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub properties: Vec<ClaimSchema>,
+    /// The number of fractional digits for decimal claims
+    #[serde(skip_serializing_if = "is_zero", default)]
+    pub scale: u8,
+    /// The value used when the claim is not given when signing by label,
+    /// revocation claims can't have one since each credential needs its own
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default: Option<ClaimData>,
+    /// Can the claim be [`ClaimData::Absent`]
+    #[serde(skip_serializing_if = "is_false", default)]
+    pub optional: bool,
 }
 
 impl ClaimSchema {
+    /// Create an object claim from its properties
+    pub fn object(label: &str, properties: &[ClaimSchema]) -> Self {
+        Self {
+            claim_type: ClaimType::Object,
+            label: label.to_string(),
+            print_friendly: properties.iter().all(|p| p.print_friendly),
+            properties: properties.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Create an array claim with `length` items that all use the `item` schema
+    pub fn array(label: &str, item: &ClaimSchema, length: usize) -> Self {
+        let properties = (0..length)
+            .map(|i| ClaimSchema {
+                label: i.to_string(),
+                ..item.clone()
+            })
+            .collect();
+        Self {
+            claim_type: ClaimType::Array,
+            label: label.to_string(),
+            print_friendly: item.print_friendly,
+            properties,
+            ..Default::default()
+        }
+    }
+
+    /// Create a decimal claim with `scale` fractional digits
+    pub fn decimal(label: &str, scale: u8) -> Self {
+        Self {
+            claim_type: ClaimType::Decimal,
+            label: label.to_string(),
+            print_friendly: true,
+            scale,
+            ..Default::default()
+        }
+    }
+
+    /// Convert the claim to the form that is signed.
+    /// Decimal claims are converted to the schema scale and
+    /// an error is returned if that would lose precision
+    pub fn normalize(&self, claim: &ClaimData) -> CredxResult<ClaimData> {
+        match claim {
+            ClaimData::Decimal(d) if self.claim_type == ClaimType::Decimal => d
+                .rescale(self.scale)
+                .map(ClaimData::Decimal)
+                .ok_or(Error::InvalidClaimData(
+                    "decimal claim cannot be represented at the schema scale",
+                )),
+            _ => Ok(claim.clone()),
+        }
+    }
+
+    /// [`true`] if this is an object or array claim
+    pub fn is_nested(&self) -> bool {
+        matches!(self.claim_type, ClaimType::Object | ClaimType::Array)
+    }
+
+    /// The label this claim has when it's at the top level of a schema
+    fn root_label(&self) -> String {
+        if self.is_nested() {
+            format!("/{}", json_pointer_segment(&self.label))
+        } else {
+            self.label.clone()
+        }
+    }
+
+    /// The JSON pointer to the property at `index`
+    fn property_label(&self, label: &str, index: usize) -> String {
+        match self.claim_type {
+            ClaimType::Array => format!("{}/{}", label, index),
+            _ => format!(
+                "{}/{}",
+                label,
+                json_pointer_segment(&self.properties[index].label)
+            ),
+        }
+    }
+
+    fn expand_into(&self, label: String, claims: &mut Vec<ClaimSchema>) -> CredxResult<()> {
+        if !self.is_nested() {
+            if !self.properties.is_empty() {
+                return Err(Error::InvalidClaimData(
+                    "only object and array claims can have properties",
+                ));
+            }
+            if self.optional && self.claim_type == ClaimType::Revocation {
+                return Err(Error::InvalidClaimData(
+                    "revocation claims cannot be optional",
+                ));
+            }
+            if self.default.is_some() && self.claim_type == ClaimType::Revocation {
+                return Err(Error::InvalidClaimData(
+                    "revocation claims cannot have a default",
+                ));
+            }
+            if let Some(default) = &self.default {
+                if !default.is_type(self.claim_type) {
+                    return Err(Error::InvalidClaimData(
+                        "claim default is not the correct type",
+                    ));
+                }
+            }
+            claims.push(ClaimSchema {
+                label,
+                ..self.clone()
+            });
+            return Ok(());
+        }
+        if self.properties.is_empty() {
+            return Err(Error::InvalidClaimData(
+                "object and array claims must have at least one property",
+            ));
+        }
+        for (index, property) in self.properties.iter().enumerate() {
+            property.expand_into(self.property_label(&label, index), claims)?;
+        }
+        Ok(())
+    }
+
+    fn flatten_into(
+        &self,
+        claim: &NestedClaimData,
+        claims: &mut Vec<ClaimData>,
+    ) -> CredxResult<()> {
+        match (self.claim_type, claim) {
+            (ClaimType::Object, NestedClaimData::Object(values)) => {
+                if values.len() != self.properties.len() {
+                    return Err(Error::InvalidClaimData(
+                        "object claim properties do not match the schema",
+                    ));
+                }
+                for property in &self.properties {
+                    let value = values
+                        .get(&property.label)
+                        .ok_or(Error::InvalidClaimData("object claim property not found"))?;
+                    property.flatten_into(value, claims)?;
+                }
+                Ok(())
+            }
+            (ClaimType::Array, NestedClaimData::Array(values)) => {
+                if values.len() != self.properties.len() {
+                    return Err(Error::InvalidClaimData(
+                        "array claim length does not match the schema",
+                    ));
+                }
+                for (index, property) in self.properties.iter().enumerate() {
+                    let value = values
+                        .get(&index)
+                        .ok_or(Error::InvalidClaimData("array claim item not found"))?;
+                    property.flatten_into(value, claims)?;
+                }
+                Ok(())
+            }
+            (ClaimType::Object | ClaimType::Array, _)
+            | (_, NestedClaimData::Object(_))
+            | (_, NestedClaimData::Array(_)) => Err(Error::InvalidClaimData(
+                "nested claim does not match the schema structure",
+            )),
+            (_, NestedClaimData::Claim(c)) => {
+                claims.push(c.clone());
+                Ok(())
+            }
+        }
+    }
+
+    fn nest(&self, label: &str, claims: &IndexMap<String, ClaimData>) -> Option<NestedClaimData> {
+        match self.claim_type {
+            ClaimType::Object => {
+                let values = self
+                    .properties
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, property)| {
+                        property
+                            .nest(&self.property_label(label, index), claims)
+                            .map(|v| (property.label.clone(), v))
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                (!values.is_empty()).then_some(NestedClaimData::Object(values))
+            }
+            ClaimType::Array => {
+                let values = self
+                    .properties
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, property)| {
+                        property
+                            .nest(&self.property_label(label, index), claims)
+                            .map(|v| (index, v))
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                (!values.is_empty()).then_some(NestedClaimData::Array(values))
+            }
+            _ => claims.get(label).cloned().map(NestedClaimData::Claim),
+        }
+    }
+
     /// [`Some(true)`] if the claim is the right type and meets the validator requirements
     /// [`Some(false)`] if the claim is the right type but doesn't meet the requirements
     /// [`None`] if the claim is the incorrect type
@@ -150,6 +481,52 @@ impl ClaimSchema {
         Some(result)
     }
 
+    /// Check the claim against the claim type and validators and
+    /// return every failure
+    pub fn validate(&self, claim: &ClaimData) -> Vec<ClaimValidationFailure> {
+        let failure = |validator: Option<&ClaimValidator>, reason| ClaimValidationFailure {
+            label: self.label.clone(),
+            validator: validator.cloned(),
+            reason,
+        };
+        if claim.is_absent() {
+            return if self.optional {
+                Vec::new()
+            } else {
+                vec![failure(None, ClaimValidationReason::NotOptional)]
+            };
+        }
+        if !claim.is_type(self.claim_type) {
+            return vec![failure(
+                None,
+                ClaimValidationReason::WrongType {
+                    expected: self.claim_type,
+                    found: claim.claim_type(),
+                },
+            )];
+        }
+        let claim = match self.normalize(claim) {
+            Ok(c) => c,
+            Err(_) => {
+                return vec![failure(
+                    None,
+                    ClaimValidationReason::PrecisionLoss { scale: self.scale },
+                )]
+            }
+        };
+        self.validators
+            .iter()
+            .filter_map(|v| match v.is_valid(&claim) {
+                Some(true) => None,
+                Some(false) => Some(failure(Some(v), ClaimValidationReason::NotValid)),
+                None => Some(failure(
+                    Some(v),
+                    ClaimValidationReason::ValidatorNotApplicable,
+                )),
+            })
+            .collect()
+    }
+
     /// Add data to the transcript
     pub fn add_challenge_contribution(&self, transcript: &mut merlin::Transcript) {
         transcript.append_message(b"claim label", self.label.as_bytes());
@@ -163,9 +540,31 @@ impl ClaimSchema {
             transcript.append_message(b"claim validator index", &Uint::from(index).to_vec());
             validator.add_challenge_contribution(transcript);
         }
+        if self.claim_type == ClaimType::Decimal {
+            transcript.append_message(b"claim scale", &[self.scale]);
+        }
+        if let Some(default) = &self.default {
+            transcript.append_message(b"claim default", &default.to_bytes());
+        }
+        if self.optional {
+            transcript.append_message(b"claim optional", &[1u8]);
+        }
     }
 }
 
+fn is_zero(scale: &u8) -> bool {
+    *scale == 0
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+/// Escape a JSON pointer reference token as described in RFC 6901
+fn json_pointer_segment(s: &str) -> String {
+    s.replace('~', "~0").replace('/', "~1")
+}
+
 #[test]
 fn test_serialize() {
     let string = r#"{"id":"63e8b522-3ef6-4c45-92f1-47cad2449523","label":"FinclusiveKYCSchema","description":"","blind_claims":[],"claim_indices":["credential_id","first_name","last_name","address1","address2","city","state","postal_cost","iso_country_code","date_of_birth","phone_number","phone_number_type","email_address","tax_id_number","document_id","document_url","document_identification_number","document_type","document_file_name","document_content","document_iso_country_code"],"claims":[{"claim_type":"Revocation","label":"credential_id","print_friendly":false},{"claim_type":"Hashed","label":"first_name","print_friendly":true,"validators":[{"Length":{"max":64}}]},{"claim_type":"Hashed","label":"last_name","print_friendly":true,"validators":[{"Length":{"max":64}}]},{"claim_type":"Hashed","label":"address1","print_friendly":true},{"claim_type":"Hashed","label":"address2","print_friendly":true},{"claim_type":"Hashed","label":"city","print_friendly":true},{"claim_type":"Hashed","label":"state","print_friendly":true},{"claim_type":"Hashed","label":"postal_code","print_friendly":true},{"claim_type":"Hashed","label":"iso_country_code","print_friendly":true},{"claim_type":"Number","label":"date_of_birth","print_friendly":true,"validators":[{"Range":{"min":0,"max":65000}}]},{"claim_type":"Hashed","label":"phone_number","print_friendly":true,"validators":[{"Regex":"\\d{10,15}"}]},{"claim_type":"Number","label":"phone_number_type","print_friendly":true},{"claim_type":"Hashed","label":"email_address","print_friendly":true},{"claim_type":"Hashed","label":"tax_id_number","print_friendly":true},{"claim_type":"Hashed","label":"document_id","print_friendly":true},{"claim_type":"Hashed","label":"document_url","print_friendly":true},{"claim_type":"Hashed","label":"document_identification_number","print_friendly":true},{"claim_type":"Hashed","label":"document_type","print_friendly":true},{"claim_type":"Hashed","label":"document_file_name","print_friendly":true},{"claim_type":"Hashed","label":"document_content","print_friendly":false},{"claim_type":"Number","label":"document_iso_country_code","print_friendly":true}]}"#;
@@ -0,0 +1,113 @@
+use crate::claim::{ClaimType, ClaimValidator};
+use core::fmt::{self, Display, Formatter};
+use serde::{Deserialize, Serialize};
+
+/// Why a claim failed validation
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum ClaimValidationReason {
+    /// The claim is not the type in the schema
+    WrongType {
+        /// The type in the schema
+        expected: ClaimType,
+        /// The type of the claim
+        found: ClaimType,
+    },
+    /// The claim doesn't meet the validator requirements
+    NotValid,
+    /// The validator can't be applied to this type of claim
+    ValidatorNotApplicable,
+    /// The decimal claim can't be represented at the schema scale
+    PrecisionLoss {
+        /// The schema scale
+        scale: u8,
+    },
+    /// The claim was not given and the schema has no default
+    MissingClaim,
+    /// The claim label is not in the schema
+    UnknownClaim,
+    /// The claim is absent but not optional in the schema
+    NotOptional,
+}
+
+impl Display for ClaimValidationReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongType { expected, found } => {
+                write!(f, "expected a {:?} claim but found {:?}", expected, found)
+            }
+            Self::NotValid => write!(f, "claim is not valid"),
+            Self::ValidatorNotApplicable => {
+                write!(f, "validator cannot be applied to this claim type")
+            }
+            Self::PrecisionLoss { scale } => {
+                write!(f, "decimal claim has more than {} fractional digits", scale)
+            }
+            Self::MissingClaim => write!(f, "claim is missing and has no default"),
+            Self::UnknownClaim => write!(f, "claim is not in the schema"),
+            Self::NotOptional => write!(f, "claim is absent but not optional"),
+        }
+    }
+}
+
+/// A claim that failed validation
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ClaimValidationFailure {
+    /// The claim label
+    pub label: String,
+    /// The validator that failed, [`None`] if the claim failed before validators are checked
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub validator: Option<ClaimValidator>,
+    /// Why the claim failed
+    pub reason: ClaimValidationReason,
+}
+
+impl Display for ClaimValidationFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.validator {
+            Some(v) => write!(
+                f,
+                "claim '{}' failed validator {}: {}",
+                self.label,
+                serde_json::to_string(v).map_err(|_| fmt::Error)?,
+                self.reason
+            ),
+            None => write!(f, "claim '{}': {}", self.label, self.reason),
+        }
+    }
+}
+
+/// The claims that failed validation against a credential schema
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ValidationReport {
+    /// Every failure in schema claim order
+    pub failures: Vec<ClaimValidationFailure>,
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut sep = "";
+        for failure in &self.failures {
+            write!(f, "{}{}", sep, failure)?;
+            sep = "\n";
+        }
+        Ok(())
+    }
+}
+
+impl ValidationReport {
+    /// [`true`] if no claims failed validation
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The labels of the claims that failed validation
+    pub fn labels(&self) -> Vec<&str> {
+        let mut labels: Vec<&str> = Vec::with_capacity(self.failures.len());
+        for failure in &self.failures {
+            if labels.last() != Some(&failure.label.as_str()) {
+                labels.push(&failure.label);
+            }
+        }
+        labels
+    }
+}
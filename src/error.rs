@@ -1,3 +1,6 @@
+use crate::credential::ValidationReport;
+use crate::presentation::SelectionReport;
+
 /// Errors created by this library
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Error {
@@ -13,6 +16,8 @@ pub enum Error {
     InvalidSigningOperation,
     /// Invalid claim data
     InvalidClaimData(&'static str),
+    /// Claims that failed the schema validation
+    ClaimValidation(ValidationReport),
     /// Invalid public key
     InvalidPublicKey,
     /// Invalid data for creating a signature proof
@@ -23,6 +28,12 @@ pub enum Error {
     InvalidBulletproofRange,
     /// Invalid binary or text data
     DeserializationError,
+    /// No wallet credential satisfies a signature statement
+    CredentialSelection(SelectionReport),
+    /// The accumulator state was not signed by the expected key
+    InvalidAccumulatorStateSignature,
+    /// The revocation registry store failed to read or write
+    RegistryStorage(String),
     /// A generic error message
     General(&'static str),
 }
@@ -1,9 +1,22 @@
-use super::{credential::CredentialSchema, error::Error, revocation_registry::RevocationRegistry};
+mod ledger;
+mod membership;
+
+pub use ledger::*;
+pub use membership::*;
+
+use super::{
+    credential::CredentialSchema,
+    error::Error,
+    revocation_registry::{
+        MemoryRegistryStore, RegistryStore, RegistryUpdate, RevocationRegistry,
+        SignedAccumulatorState, SuspensionRegistry,
+    },
+};
 use crate::blind::{BlindCredential, BlindCredentialBundle, BlindCredentialRequest};
-use crate::claim::{Claim, ClaimData, RevocationClaim};
-use crate::credential::{Credential, CredentialBundle};
+use crate::claim::{Claim, ClaimData, NestedClaimData, RevocationClaim};
+use crate::credential::{Credential, CredentialBundle, CredentialRequest, ValidationReport};
 use crate::knox::{
-    accumulator::vb20::{self, Accumulator, Element, MembershipWitness},
+    accumulator::vb20::{self, Accumulator, Element, MembershipWitness, NonMembershipWitness},
     short_group_sig_core::short_group_traits::{
         PublicKey as _, SecretKey as _, ShortGroupSignatureScheme, Signature,
     },
@@ -11,14 +24,20 @@ use crate::knox::{
 };
 use crate::{random_string, CredxResult};
 use blsful::{inner_types::*, *};
+use chrono::Utc;
 use log::debug;
+use rand_chacha::ChaChaRng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
 use std::collections::BTreeMap;
 use std::num::NonZeroUsize;
+use uint_zigzag::Uint;
+use zeroize::Zeroize;
 
 /// An issuer of a credential
 #[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct Issuer<S: ShortGroupSignatureScheme> {
+pub struct Issuer<S: ShortGroupSignatureScheme, R = MemoryRegistryStore> {
     /// The issuer's unique id
     pub id: String,
     /// The schema for this issuer
@@ -29,8 +48,28 @@ pub struct Issuer<S: ShortGroupSignatureScheme> {
     pub revocation_key: vb20::SecretKey,
     /// The verifiable decryption key for this issuer
     pub verifiable_decryption_key: SecretKey<Bls12381G2Impl>,
+    /// The key for signing published registry states
+    pub registry_signing_key: SecretKey<Bls12381G2Impl>,
     /// The revocation registry for this issuer
-    pub revocation_registry: RevocationRegistry,
+    pub revocation_registry: RevocationRegistry<R>,
+    /// The suspension registry for this issuer
+    #[serde(default)]
+    pub suspension_registry: SuspensionRegistry,
+    /// The epoch of the current signing key
+    #[serde(default)]
+    pub key_epoch: usize,
+    /// The verifying keys of previous epochs
+    #[serde(
+        default = "Vec::new",
+        bound(
+            serialize = "IssuerKeyEpoch<S>: Serialize",
+            deserialize = "IssuerKeyEpoch<S>: Deserialize<'de>"
+        )
+    )]
+    pub key_history: Vec<IssuerKeyEpoch<S>>,
+    /// The credentials signed by this issuer
+    #[serde(default)]
+    pub ledger: IssuanceLedger,
 }
 
 /// The public data for an issuer
@@ -46,18 +85,57 @@ pub struct IssuerPublic<S: ShortGroupSignatureScheme> {
     pub revocation_verifying_key: vb20::PublicKey,
     /// The verifiable encryption key for this issuer
     pub verifiable_encryption_key: PublicKey<Bls12381G2Impl>,
+    /// The key for verifying signed registry states
+    pub registry_verifying_key: PublicKey<Bls12381G2Impl>,
     /// The revocation registry for this issuer
     pub revocation_registry: Accumulator,
+    /// The revocation registry epoch
+    #[serde(default)]
+    pub revocation_epoch: usize,
+    /// The suspension registry for this issuer
+    #[serde(default)]
+    pub suspension_registry: Accumulator,
+    /// The suspension registry epoch
+    #[serde(default)]
+    pub suspension_epoch: usize,
+    /// The epoch of the current verifying key
+    #[serde(default)]
+    pub key_epoch: usize,
+    /// The verifying keys of previous epochs
+    #[serde(
+        default = "Vec::new",
+        bound(
+            serialize = "IssuerKeyEpoch<S>: Serialize",
+            deserialize = "IssuerKeyEpoch<S>: Deserialize<'de>"
+        )
+    )]
+    pub key_history: Vec<IssuerKeyEpoch<S>>,
 }
 
-impl<S: ShortGroupSignatureScheme> From<&Issuer<S>> for IssuerPublic<S> {
-    fn from(i: &Issuer<S>) -> Self {
+/// A verifying key used by an issuer in a previous key epoch
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IssuerKeyEpoch<S: ShortGroupSignatureScheme> {
+    /// The key epoch
+    pub epoch: usize,
+    /// The credential verifying key for this epoch
+    pub verifying_key: S::PublicKey,
+    /// When this key started signing as seconds since the unix epoch,
+    /// `None` if it was the issuer's initial key
+    pub valid_from: Option<u64>,
+    /// When this key stopped signing as seconds since the unix epoch
+    pub valid_until: u64,
+    /// Credentials signed with a retired key are no longer accepted
+    pub retired: bool,
+}
+
+impl<S: ShortGroupSignatureScheme, R: RegistryStore> From<&Issuer<S, R>> for IssuerPublic<S> {
+    fn from(i: &Issuer<S, R>) -> Self {
         i.get_public()
     }
 }
 
-impl<S: ShortGroupSignatureScheme> From<&mut Issuer<S>> for IssuerPublic<S> {
-    fn from(i: &mut Issuer<S>) -> Self {
+impl<S: ShortGroupSignatureScheme, R: RegistryStore> From<&mut Issuer<S, R>> for IssuerPublic<S> {
+    fn from(i: &mut Issuer<S, R>) -> Self {
         i.get_public()
     }
 }
@@ -65,25 +143,76 @@ impl<S: ShortGroupSignatureScheme> From<&mut Issuer<S>> for IssuerPublic<S> {
 impl<S: ShortGroupSignatureScheme> Issuer<S> {
     /// Create a new Issuer
     pub fn new(schema: &CredentialSchema) -> (IssuerPublic<S>, Self) {
-        let id = random_string(16, rand::thread_rng());
+        Self::new_with_rng(schema, rand::thread_rng())
+    }
+
+    /// Create a new Issuer where all keys and the issuer id are
+    /// derived from a seed drawn from `rng`
+    pub fn new_with_rng(
+        schema: &CredentialSchema,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> (IssuerPublic<S>, Self) {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        let res = Self::new_with_seed(schema, &seed);
+        seed.zeroize();
+        res
+    }
+
+    /// Create a new Issuer deterministically from `seed`.
+    ///
+    /// Each key, the revocation registry, and the issuer id are derived
+    /// from the seed using a separate domain so the same seed always
+    /// rebuilds the same issuer.
+    pub fn new_with_seed(schema: &CredentialSchema, seed: &[u8]) -> (IssuerPublic<S>, Self) {
+        Self::new_with_store(schema, seed, MemoryRegistryStore::default())
+            .expect("in memory registry store doesn't fail")
+    }
+}
+
+impl<S: ShortGroupSignatureScheme, R: RegistryStore> Issuer<S, R> {
+    /// Create a new Issuer deterministically from `seed` with the
+    /// revocation registry kept in `store`.
+    /// A store that already has a registry history continues from its latest value.
+    pub fn new_with_store(
+        schema: &CredentialSchema,
+        seed: &[u8],
+        store: R,
+    ) -> CredxResult<(IssuerPublic<S>, Self)> {
+        let id = random_string(16, Self::seeded_rng(b"CREDX-ISSUER-ID-", seed));
         let (verifying_key, signing_key) = S::new_keys(
             NonZeroUsize::new(schema.claims.len()).expect("non-zero"),
-            rand::thread_rng(),
+            Self::seeded_rng(b"CREDX-ISSUER-SIGNING-KEY-", seed),
         )
         .unwrap();
-        let (pubkkey, seckey) = Knox::new_bls381g1_keys(rand::thread_rng());
+        let (pubkkey, seckey) =
+            Knox::new_bls381g1_keys(Self::seeded_rng(b"CREDX-ISSUER-REVOCATION-KEY-", seed));
         let revocation_verifying_key = vb20::PublicKey(pubkkey.0);
         let revocation_key = vb20::SecretKey(seckey.0);
-        let (verifiable_encryption_key, verifiable_decryption_key) =
-            Knox::new_bls381g2_keys(rand::thread_rng());
-        let revocation_registry = RevocationRegistry::new(rand::thread_rng());
+        let (verifiable_encryption_key, verifiable_decryption_key) = Knox::new_bls381g2_keys(
+            Self::seeded_rng(b"CREDX-ISSUER-VERIFIABLE-ENCRYPTION-KEY-", seed),
+        );
+        let (registry_verifying_key, registry_signing_key) = Knox::new_bls381g2_keys(
+            Self::seeded_rng(b"CREDX-ISSUER-REGISTRY-SIGNING-KEY-", seed),
+        );
+        let revocation_registry = RevocationRegistry::with_store(
+            store,
+            Self::seeded_rng(b"CREDX-ISSUER-REVOCATION-REGISTRY-", seed),
+        )?;
+        let suspension_registry = SuspensionRegistry::new();
         let issuer_public = IssuerPublic {
             id: id.clone(),
             schema: schema.clone(),
             verifying_key,
             revocation_verifying_key,
             verifiable_encryption_key,
+            registry_verifying_key,
             revocation_registry: revocation_registry.value,
+            revocation_epoch: revocation_registry.epoch,
+            suspension_registry: suspension_registry.value,
+            suspension_epoch: suspension_registry.epoch,
+            key_epoch: 0,
+            key_history: Vec::new(),
         };
         debug!(
             "Credential Definition: {:}",
@@ -95,61 +224,45 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
             signing_key,
             revocation_key,
             verifiable_decryption_key,
+            registry_signing_key,
             revocation_registry,
+            suspension_registry,
+            key_epoch: 0,
+            key_history: Vec::new(),
+            ledger: IssuanceLedger::default(),
         };
-        (issuer_public, issuer)
+        Ok((issuer_public, issuer))
+    }
+
+    fn seeded_rng(dst: &[u8], seed: &[u8]) -> ChaChaRng {
+        let mut reader = sha3::Shake256::default()
+            .chain(dst)
+            .chain(seed)
+            .finalize_xof();
+        let mut okm = [0u8; 32];
+        reader.read(&mut okm);
+        ChaChaRng::from_seed(okm)
     }
 
-    /// Sign the claims into a credential
+    /// Sign the claims into a credential.
+    /// The credential is recorded in the ledger with a random id,
+    /// use [`Issuer::sign_credential_with_id`] to choose the id
     pub fn sign_credential(&mut self, claims: &[ClaimData]) -> CredxResult<CredentialBundle<S>> {
-        // Check if claim data matches schema and validators
-        if claims.len() != self.schema.claims.len() {
-            return Err(Error::InvalidClaimData("claims.len != schema.claims.len"));
-        }
-        let mut revocation_element_index = None;
-        let mut revocation_claim = None;
-        for (i, (c, t)) in claims.iter().zip(&self.schema.claims).enumerate() {
-            if !c.is_type(t.claim_type) {
-                return Err(Error::InvalidClaimData("claim is not the correct type"));
-            }
-            match t.is_valid(c) {
-                Some(b) => {
-                    if !b {
-                        return Err(Error::InvalidClaimData("claim is not valid"));
-                    }
-                }
-                None => {
-                    return Err(Error::InvalidClaimData(
-                        "claim is not correct type to validate",
-                    ))
-                }
-            };
-            if let ClaimData::Revocation(rc) = c {
-                if revocation_claim.is_some() {
-                    return Err(Error::InvalidClaimData("multiple revocation claims found"));
-                }
-                revocation_element_index = Some(i);
-                revocation_claim = Some(rc);
-            }
-        }
-        let revocation_element_index = revocation_element_index.ok_or(Error::InvalidClaimData(
-            "revocation element index not found",
-        ))?;
-        let revocation_claim =
-            revocation_claim.ok_or(Error::InvalidClaimData("revocation claim not found"))?;
+        let id = random_string(16, rand::thread_rng());
+        self.sign_credential_with_id(&id, claims)
+    }
 
-        // This data has already been revoked
-        if !self
-            .revocation_registry
-            .active
-            .contains(&revocation_claim.value)
-            && self
-                .revocation_registry
-                .elements
-                .contains(&revocation_claim.value)
-        {
-            return Err(Error::InvalidClaimData("This claim is already revoked"));
+    /// Sign the claims into a credential and record it in the ledger as `id`
+    pub fn sign_credential_with_id(
+        &mut self,
+        id: &str,
+        claims: &[ClaimData],
+    ) -> CredxResult<CredentialBundle<S>> {
+        if self.ledger.contains(id) {
+            return Err(Error::InvalidClaimData("credential id is already in use"));
         }
+        let (claims, revocation_element_index, revocation_value) = self.prepare_claims(claims)?;
+        let suspension_handle = self.suspension_handle(&revocation_value)?;
 
         let attributes: Vec<Scalar> = claims.iter().map(|c| c.to_scalar()).collect();
         let revocation_id = Element(attributes[revocation_element_index]);
@@ -159,20 +272,24 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
             &self.revocation_key,
         );
         self.revocation_registry
-            .active
-            .insert(revocation_claim.value.clone());
-        self.revocation_registry
-            .elements
-            .insert(revocation_claim.value.clone());
+            .add(std::slice::from_ref(&revocation_value))?;
+        self.record_issuance(id.to_string(), revocation_value);
         let signature = S::Signature::create(&self.signing_key, &attributes)
             .map_err(|_| Error::InvalidSigningOperation)?;
+        let key_epoch = self.key_epoch;
+        let revocation_epoch = self.revocation_registry.epoch;
+        let suspension_epoch = self.suspension_registry.epoch;
         let credential_bundle = CredentialBundle {
             issuer: IssuerPublic::from(self),
             credential: Credential {
-                claims: claims.to_vec(),
+                claims,
                 signature,
                 revocation_handle: witness,
                 revocation_index: revocation_element_index,
+                key_epoch,
+                revocation_epoch,
+                suspension_handle: Some(suspension_handle),
+                suspension_epoch,
             },
         };
         debug!(
@@ -182,21 +299,172 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         Ok(credential_bundle)
     }
 
-    /// Blind sign a credential where only a subset of the claims are known
+    /// Sign many credentials at once.
+    ///
+    /// Every set of claims is validated before any are signed and the
+    /// signing runs in parallel. The revocation registry and ledger are only
+    /// updated once all the credentials are signed, so either every
+    /// credential is issued or none are.
+    /// The credentials are recorded in the ledger with random ids,
+    /// use [`Issuer::sign_credentials_batch_with_ids`] to choose the ids.
+    pub fn sign_credentials_batch(
+        &mut self,
+        claims: &[Vec<ClaimData>],
+    ) -> CredxResult<Vec<CredentialBundle<S>>>
+    where
+        S::SecretKey: Sync,
+        S::Signature: Send,
+    {
+        let ids = claims
+            .iter()
+            .map(|_| random_string(16, rand::thread_rng()))
+            .collect::<Vec<_>>();
+        self.sign_credentials_batch_with_ids(&ids, claims)
+    }
+
+    /// Sign many credentials at once like [`Issuer::sign_credentials_batch`]
+    /// and record each in the ledger with the id at the same position in `ids`
+    pub fn sign_credentials_batch_with_ids(
+        &mut self,
+        ids: &[String],
+        claims: &[Vec<ClaimData>],
+    ) -> CredxResult<Vec<CredentialBundle<S>>>
+    where
+        S::SecretKey: Sync,
+        S::Signature: Send,
+    {
+        use rayon::prelude::*;
+
+        if ids.len() != claims.len() {
+            return Err(Error::InvalidClaimData("ids.len != claims.len"));
+        }
+        if ids
+            .iter()
+            .enumerate()
+            .any(|(i, id)| self.ledger.contains(id) || ids[..i].contains(id))
+        {
+            return Err(Error::InvalidClaimData("credential id is already in use"));
+        }
+        let prepared = claims
+            .iter()
+            .map(|c| self.prepare_claims(c))
+            .collect::<CredxResult<Vec<_>>>()?;
+
+        let signing_key = &self.signing_key;
+        let revocation_key = &self.revocation_key;
+        let revocation_registry = self.revocation_registry.value;
+        let suspension_registry = &self.suspension_registry;
+        let key_epoch = self.key_epoch;
+        let revocation_epoch = self.revocation_registry.epoch;
+        let suspension_epoch = self.suspension_registry.epoch;
+        let signed = prepared
+            .into_par_iter()
+            .map(|(claims, revocation_index, revocation_value)| {
+                let suspension_handle = suspension_registry
+                    .witness(revocation_key, &revocation_value)
+                    .map_err(|_| Error::InvalidClaimData("This claim is suspended"))?;
+                let attributes: Vec<Scalar> = claims.iter().map(|c| c.to_scalar()).collect();
+                let revocation_handle = MembershipWitness::new(
+                    Element(attributes[revocation_index]),
+                    revocation_registry,
+                    revocation_key,
+                );
+                let signature = S::Signature::create(signing_key, &attributes)
+                    .map_err(|_| Error::InvalidSigningOperation)?;
+                let credential = Credential {
+                    claims,
+                    signature,
+                    revocation_handle,
+                    revocation_index,
+                    key_epoch,
+                    revocation_epoch,
+                    suspension_handle: Some(suspension_handle),
+                    suspension_epoch,
+                };
+                Ok((credential, revocation_value))
+            })
+            .collect::<CredxResult<Vec<_>>>()?;
+
+        let revocation_values = signed.iter().map(|(_, v)| v.clone()).collect::<Vec<_>>();
+        self.revocation_registry.add(&revocation_values)?;
+        for (id, value) in ids.iter().zip(revocation_values) {
+            self.record_issuance(id.clone(), value);
+        }
+        debug!("Signed {} credentials in a batch", signed.len());
+        let issuer = self.get_public();
+        Ok(signed
+            .into_iter()
+            .map(|(credential, _)| CredentialBundle {
+                issuer: issuer.clone(),
+                credential,
+            })
+            .collect())
+    }
+
+    /// Sign claims by schema label instead of position.
+    /// Claims that aren't given use the schema default or are absent if optional
+    pub fn sign_credential_map(
+        &mut self,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        let claims = self.schema.resolve_claims(claims)?;
+        self.sign_credential(&claims)
+    }
+
+    /// Sign the claims in a credential request
+    pub fn sign_credential_request(
+        &mut self,
+        request: &CredentialRequest,
+    ) -> CredxResult<CredentialBundle<S>> {
+        if let Some(schema_id) = &request.schema_id {
+            if *schema_id != self.schema.id {
+                return Err(Error::InvalidClaimData(
+                    "credential request is for a different schema",
+                ));
+            }
+        }
+        self.sign_credential_map(&request.claims)
+    }
+
+    /// Sign claims that follow the object and array structure of the schema
+    pub fn sign_nested_credential(
+        &mut self,
+        claims: &BTreeMap<String, NestedClaimData>,
+    ) -> CredxResult<CredentialBundle<S>> {
+        let claims = self.schema.flatten_claims(claims)?;
+        self.sign_credential(&claims)
+    }
+
+    /// Blind sign a credential where only a subset of the claims are known.
+    /// The credential is recorded in the ledger with a random id,
+    /// use [`Issuer::blind_sign_credential_with_id`] to choose the id
     pub fn blind_sign_credential(
         &mut self,
         request: &BlindCredentialRequest<S>,
         claims: &BTreeMap<String, ClaimData>,
     ) -> CredxResult<BlindCredentialBundle<S>> {
+        let id = random_string(16, rand::thread_rng());
+        self.blind_sign_credential_with_id(&id, request, claims)
+    }
+
+    /// Blind sign a credential and record it in the ledger as `id`
+    pub fn blind_sign_credential_with_id(
+        &mut self,
+        id: &str,
+        request: &BlindCredentialRequest<S>,
+        claims: &BTreeMap<String, ClaimData>,
+    ) -> CredxResult<BlindCredentialBundle<S>> {
+        if self.ledger.contains(id) {
+            return Err(Error::InvalidClaimData("credential id is already in use"));
+        }
         if request.blind_claim_labels.len() + claims.len() != self.schema.claims.len() {
             return Err(Error::InvalidClaimData(
                 "blind_claims.len + known_claims.len != schema.claims.len",
             ));
         }
-
-        let mut messages = Vec::with_capacity(claims.len());
-        let mut revocation_label = None;
-        let mut revocation_claim = None;
+        // Check if claim data matches schema and validators
+        let mut failures = Vec::new();
+        let mut normalized_claims = BTreeMap::new();
         for (label, c) in claims {
             let index = self
                 .schema
@@ -204,22 +472,22 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
                 .get_index_of(label)
                 .ok_or(Error::InvalidClaimData("claim not found in schema"))?;
             let t = &self.schema.claims[index];
-
-            if !c.is_type(t.claim_type) {
-                return Err(Error::InvalidClaimData("claim is not the correct type"));
+            let mut claim_failures = t.validate(c);
+            if claim_failures.is_empty() {
+                normalized_claims.insert(label.clone(), t.normalize(c)?);
+            } else {
+                failures.append(&mut claim_failures);
             }
-            match t.is_valid(c) {
-                Some(b) => {
-                    if !b {
-                        return Err(Error::InvalidClaimData("claim is not valid"));
-                    }
-                }
-                None => {
-                    return Err(Error::InvalidClaimData(
-                        "claim is not correct type to validate",
-                    ))
-                }
-            };
+        }
+        if !failures.is_empty() {
+            return Err(Error::ClaimValidation(ValidationReport { failures }));
+        }
+        let claims = normalized_claims;
+
+        let mut messages = Vec::with_capacity(claims.len());
+        let mut revocation_label = None;
+        let mut revocation_claim = None;
+        for (label, c) in &claims {
             messages.push((
                 self.schema
                     .claim_indices
@@ -238,18 +506,14 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
             revocation_claim.ok_or(Error::InvalidClaimData("revocation claim not found"))?;
 
         // This data has already been revoked
-        if !self
-            .revocation_registry
-            .active
-            .contains(&revocation_claim.value)
-            && self
-                .revocation_registry
-                .elements
-                .contains(&revocation_claim.value)
+        if !self.revocation_registry.is_active(&revocation_claim.value)
+            && self.revocation_registry.contains(&revocation_claim.value)
         {
             return Err(Error::InvalidClaimData("This claim is already revoked"));
         }
 
+        let suspension_handle = self.suspension_handle(&revocation_claim.value)?;
+
         let revocation_id = Element(revocation_claim.to_scalar());
         let witness = MembershipWitness::new(
             revocation_id,
@@ -257,11 +521,8 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
             &self.revocation_key,
         );
         self.revocation_registry
-            .active
-            .insert(revocation_claim.value.clone());
-        self.revocation_registry
-            .elements
-            .insert(revocation_claim.value.clone());
+            .add(std::slice::from_ref(&revocation_claim.value))?;
+        self.record_issuance(id.to_string(), revocation_claim.value.clone());
 
         let signature = S::blind_sign(
             &request.blind_signature_context,
@@ -270,13 +531,20 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
             request.nonce,
         )
         .map_err(|_| Error::InvalidSigningOperation)?;
+        let key_epoch = self.key_epoch;
+        let revocation_epoch = self.revocation_registry.epoch;
+        let suspension_epoch = self.suspension_registry.epoch;
         let blind_credential_bundle = BlindCredentialBundle {
             issuer: IssuerPublic::from(self),
             credential: BlindCredential {
-                claims: claims.clone(),
+                claims,
                 signature,
                 revocation_handle: witness,
                 revocation_label,
+                key_epoch,
+                revocation_epoch,
+                suspension_handle: Some(suspension_handle),
+                suspension_epoch,
             },
         };
         debug!(
@@ -286,12 +554,30 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         Ok(blind_credential_bundle)
     }
 
+    /// Sign the current revocation registry value for publication
+    pub fn sign_revocation_registry(&self) -> CredxResult<SignedAccumulatorState> {
+        self.sign_revocation_registry_epoch(self.revocation_registry.epoch)
+    }
+
+    /// Sign the revocation registry value at `epoch` for publication
+    pub fn sign_revocation_registry_epoch(
+        &self,
+        epoch: usize,
+    ) -> CredxResult<SignedAccumulatorState> {
+        let value = self
+            .revocation_registry
+            .value_at(epoch)
+            .ok_or(Error::General("unknown revocation registry epoch"))?
+            .value;
+        SignedAccumulatorState::new(&self.id, epoch, value, &self.registry_signing_key)
+    }
+
     /// Update a revocation handle
     pub fn update_revocation_handle(
         &self,
         claim: RevocationClaim,
     ) -> CredxResult<MembershipWitness> {
-        if !self.revocation_registry.active.contains(&claim.value) {
+        if !self.revocation_registry.is_active(&claim.value) {
             return Err(Error::InvalidRevocationRegistryRevokeOperation);
         }
 
@@ -302,11 +588,184 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         ))
     }
 
+    /// Update a suspension handle
+    pub fn update_suspension_handle(
+        &self,
+        claim: RevocationClaim,
+    ) -> CredxResult<NonMembershipWitness> {
+        if !self.revocation_registry.contains(&claim.value) {
+            return Err(Error::InvalidRevocationRegistryRevokeOperation);
+        }
+        self.suspension_registry
+            .witness(&self.revocation_key, &claim.value)
+    }
+
     /// Revoke a credential and update this issue's revocation registry
     /// A list of all revoked claims should be kept externally.
-    pub fn revoke_credentials(&mut self, claims: &[RevocationClaim]) -> CredxResult<()> {
+    /// The returned update should be published so holders can update their revocation handles.
+    pub fn revoke_credentials(
+        &mut self,
+        claims: &[RevocationClaim],
+    ) -> CredxResult<RegistryUpdate> {
         let c: Vec<_> = claims.iter().map(|c| c.value.clone()).collect();
-        self.revocation_registry.revoke(&self.revocation_key, &c)
+        let update = self.revocation_registry.revoke(&self.revocation_key, &c)?;
+        self.ledger.set_status(&c, CredentialStatus::Revoked);
+        Ok(update)
+    }
+
+    /// The credentials signed by this issuer in issuance order
+    pub fn list_issued(&self) -> Vec<&IssuanceRecord> {
+        self.ledger.iter().collect()
+    }
+
+    /// Permanently revoke the credential with the ledger `id`.
+    /// Suspended credentials can be revoked as well.
+    pub fn revoke_credential(&mut self, id: &str) -> CredxResult<RegistryUpdate> {
+        let record = self
+            .ledger
+            .get(id)
+            .ok_or(Error::InvalidRevocationRegistryRevokeOperation)?;
+        if record.status == CredentialStatus::Revoked {
+            return Err(Error::InvalidRevocationRegistryRevokeOperation);
+        }
+        let value = [record.revocation_value.clone()];
+        let update = self
+            .revocation_registry
+            .revoke(&self.revocation_key, &value)?;
+        self.ledger.set_status(&value, CredentialStatus::Revoked);
+        Ok(update)
+    }
+
+    /// Add the credential with the ledger `id` to the suspension registry.
+    /// The returned update should be published so holders can update their suspension handles.
+    pub fn suspend_credential(&mut self, id: &str) -> CredxResult<RegistryUpdate> {
+        let value = self.ledger_revocation_value(id, CredentialStatus::Active)?;
+        let update = self
+            .suspension_registry
+            .suspend(&self.revocation_key, &value)?;
+        self.ledger.set_status(&value, CredentialStatus::Suspended);
+        Ok(update)
+    }
+
+    /// Lift the suspension of the credential with the ledger `id`.
+    /// Other holders apply the returned update to their suspension handles.
+    /// The holder of the credential keeps the same signature and gets a new
+    /// suspension handle from [`Issuer::update_suspension_handle`].
+    pub fn reinstate_credential(&mut self, id: &str) -> CredxResult<RegistryUpdate> {
+        let value = self.ledger_revocation_value(id, CredentialStatus::Suspended)?;
+        let update = self
+            .suspension_registry
+            .lift(&self.revocation_key, &value)?;
+        self.ledger.set_status(&value, CredentialStatus::Active);
+        Ok(update)
+    }
+
+    fn ledger_revocation_value(
+        &self,
+        id: &str,
+        status: CredentialStatus,
+    ) -> CredxResult<[String; 1]> {
+        match self.ledger.get(id) {
+            Some(record) if record.status == status => Ok([record.revocation_value.clone()]),
+            _ => Err(Error::InvalidRevocationRegistryRevokeOperation),
+        }
+    }
+
+    /// Check the claims against the schema and return them normalized
+    /// with the revocation claim index and value
+    fn prepare_claims(&self, claims: &[ClaimData]) -> CredxResult<(Vec<ClaimData>, usize, String)> {
+        // Check if claim data matches schema and validators
+        self.schema.validate(claims)?;
+        let claims = claims
+            .iter()
+            .zip(&self.schema.claims)
+            .map(|(c, t)| t.normalize(c))
+            .collect::<CredxResult<Vec<_>>>()?;
+        let mut revocation_element_index = None;
+        let mut revocation_claim = None;
+        for (i, c) in claims.iter().enumerate() {
+            if let ClaimData::Revocation(rc) = c {
+                if revocation_claim.is_some() {
+                    return Err(Error::InvalidClaimData("multiple revocation claims found"));
+                }
+                revocation_element_index = Some(i);
+                revocation_claim = Some(rc);
+            }
+        }
+        let revocation_element_index = revocation_element_index.ok_or(Error::InvalidClaimData(
+            "revocation element index not found",
+        ))?;
+        let revocation_value = revocation_claim
+            .ok_or(Error::InvalidClaimData("revocation claim not found"))?
+            .value
+            .clone();
+
+        // This data has already been revoked
+        if !self.revocation_registry.is_active(&revocation_value)
+            && self.revocation_registry.contains(&revocation_value)
+        {
+            return Err(Error::InvalidClaimData("This claim is already revoked"));
+        }
+        Ok((claims, revocation_element_index, revocation_value))
+    }
+
+    fn suspension_handle(&self, value: &str) -> CredxResult<NonMembershipWitness> {
+        self.suspension_registry
+            .witness(&self.revocation_key, value)
+            .map_err(|_| Error::InvalidClaimData("This claim is suspended"))
+    }
+
+    fn record_issuance(&mut self, id: String, revocation_value: String) {
+        self.ledger.insert(IssuanceRecord {
+            id,
+            revocation_value,
+            issued: Utc::now().timestamp() as u64,
+            schema_id: self.schema.id.clone(),
+            status: CredentialStatus::Active,
+        });
+    }
+
+    /// Replace the signing key with a new one and start a new key epoch.
+    /// The previous verifying key is kept in the key history so credentials
+    /// signed with it continue to verify until it is retired.
+    pub fn rotate_signing_key(&mut self) -> CredxResult<IssuerPublic<S>> {
+        self.rotate_signing_key_with_rng(rand::thread_rng())
+    }
+
+    /// Replace the signing key with one generated from `rng` and start a new key epoch
+    pub fn rotate_signing_key_with_rng(
+        &mut self,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<IssuerPublic<S>> {
+        let (_, signing_key) = S::new_keys(
+            NonZeroUsize::new(self.schema.claims.len()).expect("non-zero"),
+            rng,
+        )?;
+        let now = Utc::now().timestamp() as u64;
+        let valid_from = self.key_history.last().map(|k| k.valid_until);
+        self.key_history.push(IssuerKeyEpoch {
+            epoch: self.key_epoch,
+            verifying_key: self.signing_key.public_key(),
+            valid_from,
+            valid_until: now,
+            retired: false,
+        });
+        self.signing_key = signing_key;
+        self.key_epoch += 1;
+        Ok(self.get_public())
+    }
+
+    /// Retire the verifying key from a previous epoch so credentials signed
+    /// with it are no longer accepted. The current epoch cannot be retired,
+    /// rotate the signing key first.
+    pub fn retire_key_epoch(&mut self, epoch: usize) -> CredxResult<IssuerPublic<S>> {
+        let key = self
+            .key_history
+            .iter_mut()
+            .find(|k| k.epoch == epoch)
+            .ok_or(Error::General("key epoch not found in key history"))?;
+        key.retired = true;
+        Ok(self.get_public())
     }
 
     fn get_public(&self) -> IssuerPublic<S> {
@@ -314,18 +773,41 @@ impl<S: ShortGroupSignatureScheme> Issuer<S> {
         let revocation_verifying_key = vb20::PublicKey::from(&self.revocation_key);
         let verifiable_encryption_key =
             PublicKey::<Bls12381G2Impl>::from(&self.verifiable_decryption_key);
+        let registry_verifying_key = PublicKey::<Bls12381G2Impl>::from(&self.registry_signing_key);
         IssuerPublic {
             id: self.id.clone(),
             schema: self.schema.clone(),
             verifying_key,
             revocation_verifying_key,
             verifiable_encryption_key,
+            registry_verifying_key,
             revocation_registry: self.revocation_registry.value,
+            revocation_epoch: self.revocation_registry.epoch,
+            suspension_registry: self.suspension_registry.value,
+            suspension_epoch: self.suspension_registry.epoch,
+            key_epoch: self.key_epoch,
+            key_history: self.key_history.clone(),
         }
     }
 }
 
 impl<S: ShortGroupSignatureScheme> IssuerPublic<S> {
+    /// Get the verifying key for the key epoch `epoch`
+    /// as long as it has not been retired
+    pub fn verifying_key_for_epoch(&self, epoch: usize) -> CredxResult<&S::PublicKey> {
+        if epoch == self.key_epoch {
+            return Ok(&self.verifying_key);
+        }
+        self.key_history
+            .iter()
+            .find(|k| k.epoch == epoch && !k.retired)
+            .map(|k| &k.verifying_key)
+            .ok_or(Error::InvalidPresentationData(format!(
+                "issuer '{}' has no active verifying key for key epoch '{}'",
+                self.id, epoch
+            )))
+    }
+
     /// Add data to transcript
     pub fn add_challenge_contribution(&self, transcript: &mut merlin::Transcript) {
         transcript.append_message(b"issuer id", self.id.as_bytes());
@@ -341,10 +823,31 @@ impl<S: ShortGroupSignatureScheme> IssuerPublic<S> {
             b"issuer revocation registry",
             self.revocation_registry.0.to_bytes().as_ref(),
         );
+        transcript.append_message(
+            b"issuer suspension registry",
+            self.suspension_registry.0.to_bytes().as_ref(),
+        );
         transcript.append_message(
             b"issuer verifiable encryption key",
             self.verifiable_encryption_key.0.to_bytes().as_ref(),
         );
+        transcript.append_message(
+            b"issuer registry verifying key",
+            self.registry_verifying_key.0.to_bytes().as_ref(),
+        );
+        transcript.append_message(b"issuer key epoch", &Uint::from(self.key_epoch).to_vec());
+        transcript.append_message(
+            b"issuer key history length",
+            &Uint::from(self.key_history.len()).to_vec(),
+        );
+        for k in &self.key_history {
+            transcript.append_message(b"issuer key history epoch", &Uint::from(k.epoch).to_vec());
+            transcript.append_message(
+                b"issuer key history verifying key",
+                k.verifying_key.to_bytes().as_slice(),
+            );
+            transcript.append_message(b"issuer key history retired", &[u8::from(k.retired)]);
+        }
         self.schema.add_challenge_contribution(transcript);
     }
 }
@@ -0,0 +1,82 @@
+use crate::utils::*;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// The revocation status of an issued credential
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CredentialStatus {
+    /// The credential is in the revocation registry
+    Active,
+    /// The credential is temporarily removed from the revocation registry
+    Suspended,
+    /// The credential is permanently removed from the revocation registry
+    Revoked,
+}
+
+/// An entry in the issuance ledger
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuanceRecord {
+    /// The issuer assigned credential id
+    pub id: String,
+    /// The revocation claim value of the credential
+    pub revocation_value: String,
+    /// When the credential was issued as seconds since the unix epoch
+    pub issued: u64,
+    /// The id of the credential schema
+    pub schema_id: String,
+    /// The revocation status
+    pub status: CredentialStatus,
+}
+
+/// The credentials signed by an issuer in issuance order
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct IssuanceLedger {
+    #[serde(
+        serialize_with = "serialize_indexmap",
+        deserialize_with = "deserialize_indexmap"
+    )]
+    records: IndexMap<String, IssuanceRecord>,
+}
+
+impl IssuanceLedger {
+    /// The record for credential `id`
+    pub fn get(&self, id: &str) -> Option<&IssuanceRecord> {
+        self.records.get(id)
+    }
+
+    /// The record for the credential with the revocation claim `value`
+    pub fn find_by_revocation_value(&self, value: &str) -> Option<&IssuanceRecord> {
+        self.records.values().find(|r| r.revocation_value == value)
+    }
+
+    /// Every record in issuance order
+    pub fn iter(&self) -> impl Iterator<Item = &IssuanceRecord> {
+        self.records.values()
+    }
+
+    /// The number of issued credentials
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// [`true`] if no credentials have been issued
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub(crate) fn contains(&self, id: &str) -> bool {
+        self.records.contains_key(id)
+    }
+
+    pub(crate) fn insert(&mut self, record: IssuanceRecord) {
+        self.records.insert(record.id.clone(), record);
+    }
+
+    pub(crate) fn set_status(&mut self, revocation_values: &[String], status: CredentialStatus) {
+        for r in self.records.values_mut() {
+            if revocation_values.contains(&r.revocation_value) {
+                r.status = status;
+            }
+        }
+    }
+}
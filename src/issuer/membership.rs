@@ -0,0 +1,158 @@
+use crate::claim::ClaimData;
+use crate::credential::{
+    MembershipBundle, MembershipClaim, MembershipCredential, MembershipRegistry,
+    MembershipSigningKey, MembershipVerificationKey,
+};
+use crate::error::Error;
+use crate::knox::{
+    accumulator::vb20::{self, Element},
+    Knox,
+};
+use crate::revocation_registry::RegistryUpdate;
+use crate::{random_string, CredxResult};
+use chrono::Utc;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// An issuer of set membership credentials.
+///
+/// The registry holds the current members, holders prove their
+/// claim is a member with a [`crate::statement::MembershipStatement`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MembershipIssuer {
+    /// The issuer's unique id
+    pub id: String,
+    /// The registry update key for this issuer
+    pub signing_key: MembershipSigningKey,
+    /// The registry value
+    pub registry: MembershipRegistry,
+    /// The number of updates applied to the registry value
+    pub epoch: usize,
+    /// The current members
+    pub members: Vec<Element>,
+}
+
+/// The public data for a membership issuer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MembershipIssuerPublic {
+    /// The issuer's unique id
+    pub id: String,
+    /// The registry verification key for this issuer
+    pub verification_key: MembershipVerificationKey,
+    /// The registry value
+    pub registry: MembershipRegistry,
+    /// The registry epoch
+    pub epoch: usize,
+}
+
+impl From<&MembershipIssuer> for MembershipIssuerPublic {
+    fn from(i: &MembershipIssuer) -> Self {
+        i.get_public()
+    }
+}
+
+impl From<&mut MembershipIssuer> for MembershipIssuerPublic {
+    fn from(i: &mut MembershipIssuer) -> Self {
+        i.get_public()
+    }
+}
+
+impl MembershipIssuer {
+    /// Create a new membership issuer with no members
+    pub fn new() -> (MembershipIssuerPublic, Self) {
+        Self::new_with_rng(rand::thread_rng())
+    }
+
+    /// Create a new membership issuer with no members using `rng`
+    pub fn new_with_rng(mut rng: impl RngCore + CryptoRng) -> (MembershipIssuerPublic, Self) {
+        let id = random_string(16, &mut rng);
+        let (_, secret_key) = Knox::new_bls381g1_keys(&mut rng);
+        let issuer = Self {
+            id,
+            signing_key: vb20::SecretKey(secret_key.0),
+            registry: MembershipRegistry::random(rng),
+            epoch: 0,
+            members: Vec::new(),
+        };
+        (issuer.get_public(), issuer)
+    }
+
+    /// Is the claim a current member
+    pub fn is_member(&self, claim: &ClaimData) -> bool {
+        self.members.contains(&MembershipClaim::from(claim).0)
+    }
+
+    /// Add the claims to the registry
+    /// and return the update for holders
+    pub fn add_members(&mut self, claims: &[ClaimData]) -> CredxResult<RegistryUpdate> {
+        let additions = claims
+            .iter()
+            .map(|c| MembershipClaim::from(c).0)
+            .collect::<Vec<_>>();
+        if additions
+            .iter()
+            .enumerate()
+            .any(|(i, e)| self.members.contains(e) || additions[..i].contains(e))
+        {
+            return Err(Error::InvalidClaimData("claim is already a member"));
+        }
+        self.members.extend_from_slice(&additions);
+        Ok(self.update(additions, Vec::new()))
+    }
+
+    /// Remove the claims from the registry
+    /// and return the update for holders
+    pub fn remove_members(&mut self, claims: &[ClaimData]) -> CredxResult<RegistryUpdate> {
+        let deletions = claims
+            .iter()
+            .map(|c| MembershipClaim::from(c).0)
+            .collect::<Vec<_>>();
+        if deletions
+            .iter()
+            .enumerate()
+            .any(|(i, e)| !self.members.contains(e) || deletions[..i].contains(e))
+        {
+            return Err(Error::InvalidClaimData("claim is not a member"));
+        }
+        self.members.retain(|e| !deletions.contains(e));
+        Ok(self.update(Vec::new(), deletions))
+    }
+
+    /// Issue a membership credential for a current member
+    pub fn issue(&self, claim: &ClaimData) -> CredxResult<MembershipBundle> {
+        let member = MembershipClaim::from(claim);
+        if !self.members.contains(&member.0) {
+            return Err(Error::InvalidClaimData("claim is not a member"));
+        }
+        let credential = MembershipCredential::new(member.0, self.registry, &self.signing_key);
+        Ok(MembershipBundle {
+            issuer: self.get_public(),
+            member,
+            credential,
+        })
+    }
+
+    fn update(&mut self, additions: Vec<Element>, deletions: Vec<Element>) -> RegistryUpdate {
+        let coefficients = self
+            .registry
+            .update_assign(&self.signing_key, &additions, &deletions);
+        self.epoch += 1;
+        RegistryUpdate {
+            epoch: self.epoch,
+            timestamp: Utc::now().timestamp() as u64,
+            value: self.registry,
+            additions,
+            deletions,
+            coefficients,
+        }
+    }
+
+    fn get_public(&self) -> MembershipIssuerPublic {
+        MembershipIssuerPublic {
+            id: self.id.clone(),
+            verification_key: MembershipVerificationKey::from(&self.signing_key),
+            registry: self.registry,
+            epoch: self.epoch,
+        }
+    }
+}
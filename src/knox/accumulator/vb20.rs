@@ -115,6 +115,25 @@ impl core::ops::MulAssign<Scalar> for PolynomialG1 {
     }
 }
 
+impl core::ops::MulAssign<&Polynomial> for PolynomialG1 {
+    fn mul_assign(&mut self, rhs: &Polynomial) {
+        if self.0.is_empty() || rhs.0.is_empty() {
+            return;
+        }
+        let orig = core::mem::take(&mut self.0);
+        // M + N - 1
+        self.0
+            .resize(orig.len() + rhs.0.len() - 1, G1Projective::IDENTITY);
+
+        // Calculate product
+        for (i, item) in orig.iter().enumerate() {
+            for (j, jitem) in rhs.0.iter().enumerate() {
+                self.0[i + j] += item * jitem;
+            }
+        }
+    }
+}
+
 /// A Polynomial for scalars
 #[derive(Default)]
 pub struct Polynomial(pub Vec<Scalar>);
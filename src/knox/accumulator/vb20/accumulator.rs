@@ -322,7 +322,7 @@ mod tests {
         let mut items: Vec<Element> = (0..10_000_000).map(|_| Element::random()).collect();
         let mut acc = Accumulator::with_elements(&key, items.as_slice());
 
-        let y = items.last().unwrap().clone();
+        let y = *items.last().unwrap();
         let mut witness = MembershipWitness::new(y, acc, &key);
         let params = ProofParams::new(pk, None);
         let proof_message = crate::knox::short_group_sig_core::ProofMessage::Hidden(
@@ -10,6 +10,7 @@ use blsful::inner_types::*;
 
 use crate::knox::accumulator::vb20::Error;
 use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 
 /// Section 8 in <https://eprint.iacr.org/2020/777>
@@ -253,6 +254,24 @@ impl MembershipProof {
     /// The size of the proof in bytes
     pub const BYTES: usize = 304;
 
+    /// Simulate a proof for a member whose Schnorr response is `s_y`.
+    ///
+    /// Every value is random, [`MembershipProof::finalize`] computes the
+    /// commitments that make it verify for any challenge. Only sound when the
+    /// challenge is fixed by the other branches of a disjunction.
+    pub fn simulate(s_y: Scalar, mut rng: impl RngCore + CryptoRng) -> Self {
+        Self {
+            e_c: G1Projective::random(&mut rng),
+            t_sigma: G1Projective::random(&mut rng),
+            t_rho: G1Projective::random(&mut rng),
+            s_sigma: Scalar::random(&mut rng),
+            s_rho: Scalar::random(&mut rng),
+            s_delta_sigma: Scalar::random(&mut rng),
+            s_delta_rho: Scalar::random(&mut rng),
+            s_y,
+        }
+    }
+
     /// Generate the structure that can be used in the challenge hash
     /// returns a struct to avoid recomputing
     pub fn finalize(
@@ -603,21 +622,21 @@ impl NonMembershipProofCommitting {
 }
 
 /// A ZKP non-membership proof
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, Deserialize, Serialize)]
 pub struct NonMembershipProof {
-    e_c: G1Projective,
-    e_d: G1Projective,
-    e_dm1: G1Projective,
-    t_sigma: G1Projective,
-    t_rho: G1Projective,
-    s_sigma: Scalar,
-    s_rho: Scalar,
-    s_delta_sigma: Scalar,
-    s_delta_rho: Scalar,
-    s_u: Scalar,
-    s_v: Scalar,
-    s_w: Scalar,
-    s_y: Scalar,
+    pub(crate) e_c: G1Projective,
+    pub(crate) e_d: G1Projective,
+    pub(crate) e_dm1: G1Projective,
+    pub(crate) t_sigma: G1Projective,
+    pub(crate) t_rho: G1Projective,
+    pub(crate) s_sigma: Scalar,
+    pub(crate) s_rho: Scalar,
+    pub(crate) s_delta_sigma: Scalar,
+    pub(crate) s_delta_rho: Scalar,
+    pub(crate) s_u: Scalar,
+    pub(crate) s_v: Scalar,
+    pub(crate) s_w: Scalar,
+    pub(crate) s_y: Scalar,
 }
 
 impl NonMembershipProof {
@@ -841,14 +860,14 @@ mod tests {
 
     #[test]
     fn basic_nonmembership_proof() {
-        let mut rng = rand_core::OsRng;
+        let rng = rand_core::OsRng;
         let sk = SecretKey::new(None);
         let pk = PublicKey::from(&sk);
         let proof_params = ProofParams::new(pk, None);
         let blinding_factor = Some(Element::from(generate_fr(
             SALT,
             Some(b"basic_nonmembership_proof_blinding_factor"),
-            &mut rng,
+            rng,
         )));
         let elements = [
             Element::hash(b"1"),
@@ -881,7 +900,7 @@ mod tests {
     fn growing_accumulator() {
         use core::convert::TryFrom;
 
-        let mut rng = rand_core::OsRng;
+        let rng = rand_core::OsRng;
         let sk = SecretKey::try_from(&[
             83, 88, 211, 208, 98, 73, 80, 160, 247, 119, 30, 138, 197, 40, 149, 84, 224, 194, 132,
             99, 42, 220, 247, 225, 118, 194, 100, 61, 247, 72, 186, 15,
@@ -913,7 +932,7 @@ mod tests {
         let blinding_factor = Some(Element::from(generate_fr(
             SALT,
             Some(b"basic_nonmembership_proof_blinding_factor"),
-            &mut rng,
+            rng,
         )));
 
         let elements = [
@@ -3,7 +3,7 @@ use super::{
     dad,
     error::Error,
     key::{PublicKey, SecretKey},
-    PolynomialG1,
+    Polynomial, PolynomialG1,
 };
 use blsful::inner_types::*;
 use core::{convert::TryFrom, fmt};
@@ -490,6 +490,62 @@ where
     }
 }
 
+/// Combine the deltas of consecutive epochs into the additions, deletions
+/// and coefficients of a single batch update.
+///
+/// Updating a witness with the result gives the same witness as
+/// [`MembershipWitness::multi_batch_update`] with `deltas` but the
+/// polynomial is computed once for every witness instead of by each holder.
+pub fn combine_deltas<A, D, C>(
+    deltas: &[(A, D, C)],
+) -> (Vec<Element>, Vec<Element>, Vec<Coefficient>)
+where
+    A: AsRef<[Element]>,
+    D: AsRef<[Element]>,
+    C: AsRef<[Coefficient]>,
+{
+    let one = Scalar::ONE;
+    let m1 = -one;
+
+    //〈Υy,Ω〉 as a polynomial in y
+    let mut poly = PolynomialG1::with_capacity(deltas.len());
+
+    // Ωi->j+1 = ∑ 1..t (dAt * dDt-1) · Ω
+    for (i, (_, _, coefficients)) in deltas.iter().enumerate() {
+        let mut factor = Polynomial::with_capacity(deltas.len());
+        factor.push(one);
+        // ∏^(t-1)_(h=i+1) dDh
+        for (_, dels, _) in deltas.iter().take(i) {
+            for d in dels.as_ref() {
+                factor *= &[d.0, m1];
+            }
+        }
+        // ∏^(j+1)_(k=t+1) dAk
+        for (adds, _, _) in deltas.iter().skip(i + 1) {
+            for a in adds.as_ref() {
+                factor *= &[a.0, m1];
+            }
+        }
+        let mut pp = PolynomialG1(coefficients.as_ref().iter().map(|c| c.0).collect());
+        pp *= &factor;
+        poly += pp;
+    }
+
+    let additions = deltas
+        .iter()
+        .flat_map(|(adds, _, _)| adds.as_ref().iter().copied())
+        .collect();
+    let deletions = deltas
+        .iter()
+        .flat_map(|(_, dels, _)| dels.as_ref().iter().copied())
+        .collect();
+    (
+        additions,
+        deletions,
+        poly.0.into_iter().map(Coefficient).collect(),
+    )
+}
+
 /// Computes the compressed delta needed to update a witness
 pub fn evaluate_delta<A, D, C>(
     y: Element,
@@ -583,7 +639,7 @@ mod tests {
         let mut wit = MembershipWitness::new(elements[3], acc, &key);
         assert!(wit.verify(y, pubkey, acc));
 
-        let data = vec![
+        let data = [
             Element::hash(b"1"),
             Element::hash(b"2"),
             Element::hash(b"3"),
@@ -629,7 +685,7 @@ mod tests {
 
         assert!(wit.verify(y, pubkey, acc));
 
-        let data = vec![
+        let data = [
             Element::hash(b"1"),
             Element::hash(b"2"),
             Element::hash(b"3"),
@@ -656,4 +712,70 @@ mod tests {
         );
         assert!(wit.verify(y, pubkey, acc));
     }
+
+    #[test]
+    fn membership_combined_batch_update() {
+        let key = SecretKey::new(Some(b"1234567890"));
+        let pubkey = PublicKey::from(&key);
+        let elements = [
+            Element::hash(b"3"),
+            Element::hash(b"4"),
+            Element::hash(b"5"),
+            Element::hash(b"6"),
+            Element::hash(b"7"),
+            Element::hash(b"8"),
+            Element::hash(b"9"),
+            Element::hash(b"10"),
+            Element::hash(b"11"),
+            Element::hash(b"12"),
+            Element::hash(b"13"),
+            Element::hash(b"14"),
+            Element::hash(b"15"),
+            Element::hash(b"16"),
+            Element::hash(b"17"),
+            Element::hash(b"18"),
+            Element::hash(b"19"),
+            Element::hash(b"20"),
+        ];
+
+        let y = elements[3];
+        let mut acc = Accumulator::with_elements(&key, &elements);
+        let mut wit = MembershipWitness::new(elements[3], acc, &key);
+
+        assert!(wit.verify(y, pubkey, acc));
+
+        let data = [
+            Element::hash(b"1"),
+            Element::hash(b"2"),
+            Element::hash(b"3"),
+            Element::hash(b"4"),
+            Element::hash(b"5"),
+        ];
+        let adds1 = &data[0..2];
+        let dels1 = &data[2..5];
+        let coeffs1 = acc.update_assign(&key, adds1, dels1);
+
+        let dels2 = &elements[8..10];
+        let coeffs2 = acc.update_assign(&key, &[], dels2);
+
+        let dels3 = &elements[11..14];
+        let coeffs3 = acc.update_assign(&key, &[], dels3);
+
+        let adds4 = &[Element::hash(b"21")];
+        let coeffs4 = acc.update_assign(&key, adds4, &[]);
+
+        let deltas = [
+            (adds1, dels1, coeffs1.as_slice()),
+            (&[], dels2, coeffs2.as_slice()),
+            (&[], dels3, coeffs3.as_slice()),
+            (adds4, &[], coeffs4.as_slice()),
+        ];
+        // One batch update with the combined deltas matches updating epoch by epoch
+        let (additions, deletions, coefficients) = combine_deltas(&deltas);
+        let combined = wit.batch_update(y, &additions, &deletions, &coefficients);
+
+        wit.multi_batch_update_assign(y, &deltas);
+        assert!(wit.verify(y, pubkey, acc));
+        assert_eq!(combined, wit);
+    }
 }
@@ -26,15 +26,11 @@ impl BlindSignatureTrait for BlindSignature {
             .map(|(idx, _)| *idx)
             .max()
             .ok_or(Error::General("No messages"))?;
-        if max_idx >= sk.max_messages {
-            return Err(Error::General("Invalid message index"));
-        }
         let expanded_pub_key = PublicKey::from(sk);
         let domain = super::signature::domain_calculation(&expanded_pub_key);
-        let (points, scalars): (Vec<G1Projective>, Vec<Scalar>) = msgs
-            .iter()
-            .map(|(i, m)| (expanded_pub_key.y[*i], *m))
-            .unzip();
+        let generators = expanded_pub_key.generators(max_idx + 1);
+        let (points, scalars): (Vec<G1Projective>, Vec<Scalar>) =
+            msgs.iter().map(|(i, m)| (generators[*i], *m)).unzip();
 
         let e = super::signature::compute_e(sk, &scalars, domain);
 
@@ -33,17 +33,16 @@ impl BlindSignatureContextTrait for BlindSignatureContext {
         nonce: Scalar,
     ) -> CredxResult<bool> {
         let pk = PublicKey::from(sk);
-        let mut known = BTreeSet::new();
-        let mut points = Vec::with_capacity(pk.y.len());
-        for idx in known_messages {
-            if *idx >= pk.y.len() {
-                return Err(Error::InvalidSignatureProofData);
-            }
-            known.insert(*idx);
+        let known = known_messages.iter().copied().collect::<BTreeSet<_>>();
+        // Every message is either known or has a proof
+        let count = pk.y.len().max(known.len() + self.proofs.len());
+        if known.iter().any(|idx| *idx >= count) {
+            return Err(Error::InvalidSignatureProofData);
         }
-        for i in 0..pk.y.len() {
+        let mut points = Vec::with_capacity(count);
+        for (i, y) in pk.generators(count).iter().enumerate() {
             if !known.contains(&i) {
-                points.push(pk.y[i]);
+                points.push(*y);
             }
         }
         points.push(self.commitment);
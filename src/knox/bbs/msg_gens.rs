@@ -2,6 +2,7 @@ use blsful::inner_types::G1Projective;
 use elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, Expander};
 use sha2::Sha256;
 use std::num::NonZeroUsize;
+use std::ops::Range;
 
 /// The message generators used for signing and proofs
 #[derive(Debug, Clone)]
@@ -15,6 +16,15 @@ impl MessageGenerators {
 
     /// Create a new set of message generators using a specific API ID
     pub fn with_api_id(count: NonZeroUsize, api_id: Option<&[u8]>) -> Self {
+        Self::range_with_api_id(0..count.get(), api_id)
+    }
+
+    /// Create the message generators for the indices in `range` using a specific API ID.
+    ///
+    /// Each generator only depends on the API ID and its index so these are
+    /// the same points [`MessageGenerators::with_api_id`] returns at those indices
+    /// and generators can be derived as they are needed.
+    pub fn range_with_api_id(range: Range<usize>, api_id: Option<&[u8]>) -> Self {
         const SEED_DST: &[u8] = b"SIG_GENERATOR_SEED_";
         const GENERATOR_DST: &[u8] = b"SIG_GENERATOR_DST_";
         const GENERATOR_SEED: &[u8] = b"SIG_GENERATOR_SEED_";
@@ -38,8 +48,7 @@ impl MessageGenerators {
             .copied()
             .collect::<Vec<u8>>();
 
-        let count = count.get();
-        let mut generators = Vec::with_capacity(count);
+        let mut generators = Vec::with_capacity(range.len());
 
         let binding = [seed_dst.as_slice()];
         let mut v = [0u8; 40];
@@ -49,7 +58,7 @@ impl MessageGenerators {
         v_expander.fill_bytes(&mut v[..32]);
 
         let mut inner_v = [0u8; 32];
-        for i in 0..count {
+        for i in range {
             v[32..].copy_from_slice(&(i as u64).to_be_bytes());
             let mut inner_v_expander = ExpandMsgXmd::<Sha256>::expand_message(&[&v], &binding, 32)
                 .expect("Failed to expand message");
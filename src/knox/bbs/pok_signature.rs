@@ -35,7 +35,8 @@ impl ProofOfSignatureKnowledgeContribution for PokSignature {
         let r_inv = Option::from((-r).invert()).ok_or(Error::InvalidPresentationData("an error occurred when creating a signature proof of knowledge, the random value `r` was zero".to_string()))?;
         let r_inv_e = r_inv * signature.e;
 
-        let b = G1Projective::GENERATOR + G1Projective::sum_of_products(&public_key.y, &msgs);
+        let generators = public_key.generators(msgs.len());
+        let b = G1Projective::GENERATOR + G1Projective::sum_of_products(&generators, &msgs);
 
         let a_bar = signature.a * r;
         let b_bar = b * r - a_bar * signature.e;
@@ -46,11 +47,11 @@ impl ProofOfSignatureKnowledgeContribution for PokSignature {
         for (i, m) in messages.iter().enumerate() {
             match m {
                 ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(msg)) => {
-                    proof.commit_random(public_key.y[i], &mut rng);
+                    proof.commit_random(generators[i], &mut rng);
                     hidden_messages.push(*msg);
                 }
                 ProofMessage::Hidden(HiddenMessage::ExternalBlinding(msg, n)) => {
-                    proof.commit(public_key.y[i], *n);
+                    proof.commit(generators[i], *n);
                     hidden_messages.push(*msg);
                 }
                 ProofMessage::Revealed(_) => {}
@@ -7,8 +7,9 @@ use blsful::inner_types::{
 };
 use bulletproofs::inner_types::G1Projective;
 use elliptic_curve::group::Curve;
-use elliptic_curve::{Group, PrimeField};
+use elliptic_curve::{Field, Group, PrimeField};
 use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -48,34 +49,7 @@ impl ProofOfSignatureKnowledge for PokSignatureProof {
         if public_key.is_invalid().into() {
             return Err(Error::General("Invalid public key"));
         }
-        let mut points = Vec::with_capacity(public_key.y.len() + 3);
-        let mut msgs = Vec::with_capacity(revealed_messages.len());
-        let mut known = BTreeSet::new();
-        for (idx, msg) in revealed_messages {
-            if *idx >= public_key.y.len() {
-                continue;
-            }
-            known.insert(*idx);
-            points.push(public_key.y[*idx]);
-            msgs.push(*msg);
-        }
-        let lhs = -G1Projective::sum_of_products(&points, &msgs) - G1Projective::GENERATOR;
-        points.clear();
-        msgs.clear();
-
-        for (idx, y) in public_key.y.iter().enumerate() {
-            if known.contains(&idx) {
-                continue;
-            }
-            points.push(*y);
-        }
-
-        points.push(self.a_bar);
-        points.push(self.b_bar);
-        points.push(lhs);
-        let mut scalars = self.proof.clone();
-        scalars.push(-challenge);
-        let commitment = G1Projective::sum_of_products(&points, &scalars);
+        let commitment = self.schnorr_commitment(public_key, revealed_messages, challenge);
         if self.t != commitment {
             return Err(Error::General("Invalid proof - invalid messages"));
         }
@@ -107,7 +81,8 @@ impl ProofOfSignatureKnowledge for PokSignatureProof {
         public_key: &Self::PublicKey,
         rvl_msgs: &[(usize, Scalar)],
     ) -> CredxResult<BTreeMap<usize, Scalar>> {
-        if public_key.y.len() < rvl_msgs.len() {
+        let count = self.message_count(public_key, rvl_msgs.len());
+        if count < rvl_msgs.len() {
             return Err(Error::General("Proof error"));
         }
         if public_key.is_invalid().unwrap_u8() == 1u8 {
@@ -116,7 +91,7 @@ impl ProofOfSignatureKnowledge for PokSignatureProof {
 
         let mut hidden = BTreeMap::new();
         let mut j = 0;
-        for i in 0..public_key.y.len() {
+        for i in 0..count {
             if j < rvl_msgs.len() && rvl_msgs[j].0 == i {
                 j += 1;
                 continue;
@@ -130,9 +105,109 @@ impl ProofOfSignatureKnowledge for PokSignatureProof {
 
         Ok(hidden)
     }
+
+    fn can_simulate(public_key: &Self::PublicKey) -> bool {
+        // Keys from before `h_x` was added have the identity. A wrong `h_x` would make
+        // simulated proofs fail and reveal which branch is real so check it is x·H
+        if (public_key.is_invalid() | public_key.h_x.is_identity()).into() {
+            return false;
+        }
+        let h = PublicKey::simulation_generator(&public_key.w);
+        multi_miller_loop(&[
+            (&h.to_affine(), &G2Prepared::from(public_key.w.to_affine())),
+            (
+                &public_key.h_x.to_affine(),
+                &G2Prepared::from(-G2Affine::generator()),
+            ),
+        ])
+        .final_exponentiation()
+        .is_identity()
+        .into()
+    }
+
+    fn simulate(
+        public_key: &Self::PublicKey,
+        revealed_messages: &[(usize, Scalar)],
+        challenge: Scalar,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Self> {
+        if !Self::can_simulate(public_key) {
+            return Err(Error::General("Invalid public key"));
+        }
+        // A_bar = k·H and B_bar = k·(x·H) satisfy the pairing check
+        // and look like a randomized signature
+        let k = Scalar::random(&mut rng);
+        let hidden_count = public_key.y.len()
+            - revealed_messages
+                .iter()
+                .filter(|(idx, _)| *idx < public_key.y.len())
+                .map(|(idx, _)| *idx)
+                .collect::<BTreeSet<_>>()
+                .len();
+        let mut proof = Self {
+            a_bar: PublicKey::simulation_generator(&public_key.w) * k,
+            b_bar: public_key.h_x * k,
+            t: G1Projective::IDENTITY,
+            proof: (0..hidden_count + 2)
+                .map(|_| Scalar::random(&mut rng))
+                .collect(),
+        };
+        proof.t = proof.schnorr_commitment(public_key, revealed_messages, challenge);
+        Ok(proof)
+    }
 }
 
 impl PokSignatureProof {
+    /// The number of signed messages, at least the number the key was created for.
+    /// A key can sign more messages than that in which case each hidden message has a response
+    fn message_count(&self, public_key: &PublicKey, revealed_count: usize) -> usize {
+        public_key
+            .y
+            .len()
+            .max(self.proof.len().saturating_sub(2) + revealed_count)
+    }
+
+    /// The Schnorr commitment the responses open to for `challenge`
+    fn schnorr_commitment(
+        &self,
+        public_key: &PublicKey,
+        revealed_messages: &[(usize, Scalar)],
+        challenge: Scalar,
+    ) -> G1Projective {
+        let revealed = revealed_messages
+            .iter()
+            .map(|(idx, _)| *idx)
+            .collect::<BTreeSet<_>>();
+        let generators = public_key.generators(self.message_count(public_key, revealed.len()));
+        let mut points = Vec::with_capacity(generators.len() + 3);
+        let mut msgs = Vec::with_capacity(revealed_messages.len());
+        let mut known = BTreeSet::new();
+        for (idx, msg) in revealed_messages {
+            if *idx >= generators.len() {
+                continue;
+            }
+            known.insert(*idx);
+            points.push(generators[*idx]);
+            msgs.push(*msg);
+        }
+        let lhs = -G1Projective::sum_of_products(&points, &msgs) - G1Projective::GENERATOR;
+        points.clear();
+
+        for (idx, y) in generators.iter().enumerate() {
+            if known.contains(&idx) {
+                continue;
+            }
+            points.push(*y);
+        }
+
+        points.push(self.a_bar);
+        points.push(self.b_bar);
+        points.push(lhs);
+        let mut scalars = self.proof.clone();
+        scalars.push(-challenge);
+        G1Projective::sum_of_products(&points, &scalars)
+    }
+
     /// Store the proof as a sequence of bytes
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut buffer = Vec::with_capacity(48 * 3 + 32 * self.proof.len());
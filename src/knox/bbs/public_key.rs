@@ -4,6 +4,7 @@ use crate::{
 };
 use blsful::inner_types::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::num::NonZeroUsize;
 use subtle::Choice;
 
@@ -14,13 +15,17 @@ use subtle::Choice;
 pub struct CompressedPublicKey {
     pub(crate) x: G2Projective,
     pub(crate) max_messages: usize,
+    #[serde(default)]
+    pub(crate) h_x: G1Projective,
 }
 
 impl From<&SecretKey> for CompressedPublicKey {
     fn from(sk: &SecretKey) -> Self {
+        let x = G2Projective::GENERATOR * sk.x;
         Self {
-            x: G2Projective::GENERATOR * sk.x,
+            x,
             max_messages: sk.max_messages,
+            h_x: PublicKey::simulation_generator(&x) * sk.x,
         }
     }
 }
@@ -92,10 +97,25 @@ impl CompressedPublicKey {
 }
 
 /// Public key which includes the generators for each message
+///
+/// `y` holds the generators for the number of messages the key was created for.
+/// Each generator only depends on `w` and its index, see
+/// [`MessageGenerators::range_with_api_id`], so generators past those are
+/// derived when needed and the key can sign any number of messages.
+///
+/// `h_x` is `x·H` where `H` is hashed from `w` so has no known discrete log.
+/// It lets a prover simulate a proof of knowledge for the branches of a disjunction
+/// it holds no signature for. Publishing it doesn't weaken unforgeability,
+/// the q-SDH assumption BBS relies on already hands the adversary `x` times a G1 generator
+/// and a reduction can program `H` as a known multiple of that generator.
+/// Keys created before `h_x` was added deserialize with the identity and
+/// can't be used in disjunctions.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct PublicKey {
     pub(crate) y: Vec<G1Projective>,
     pub(crate) w: G2Projective,
+    #[serde(default)]
+    pub(crate) h_x: G1Projective,
 }
 
 impl PublicKeyTrait for PublicKey {
@@ -120,7 +140,33 @@ impl PublicKey {
     pub fn new(public_key: CompressedPublicKey) -> Self {
         let count = NonZeroUsize::new(public_key.max_messages).expect("non-zero");
         let y = MessageGenerators::with_api_id(count, Some(&public_key.x.to_compressed())).0;
-        Self { y, w: public_key.x }
+        Self {
+            y,
+            w: public_key.x,
+            h_x: public_key.h_x,
+        }
+    }
+
+    /// The generators for the first `count` messages
+    pub(crate) fn generators(&self, count: usize) -> Cow<'_, [G1Projective]> {
+        if count <= self.y.len() {
+            return Cow::Borrowed(&self.y[..count]);
+        }
+        let mut y = self.y.clone();
+        y.extend(
+            MessageGenerators::range_with_api_id(
+                self.y.len()..count,
+                Some(&self.w.to_compressed()),
+            )
+            .0,
+        );
+        Cow::Owned(y)
+    }
+
+    /// The generator used for simulating proofs, derived from `w`
+    pub(crate) fn simulation_generator(w: &G2Projective) -> G1Projective {
+        const SIMULATION_DST: &[u8] = b"BBS_SIMULATION_GENERATOR_DST_";
+        G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(&w.to_compressed(), SIMULATION_DST)
     }
 
     /// Create a new public key from a secret key
@@ -134,6 +180,7 @@ impl PublicKey {
         CompressedPublicKey {
             x: self.w,
             max_messages: self.y.len(),
+            h_x: self.h_x,
         }
     }
 
@@ -31,9 +31,6 @@ impl ShortGroupSignatureScheme for BbsScheme {
         count: NonZeroUsize,
         rng: impl RngCore + CryptoRng,
     ) -> CredxResult<(Self::PublicKey, Self::SecretKey)> {
-        if count.get() > 128 {
-            return Err(Error::General("Invalid key generation"));
-        }
         let sk = SecretKey::random(count, rng);
         let pk = PublicKey::from(&sk);
         Ok((pk, sk))
@@ -71,13 +68,12 @@ impl ShortGroupSignatureScheme for BbsScheme {
         let mut committing = ProofCommittedBuilder::<G1Projective, G1Affine, Scalar>::new(
             G1Projective::sum_of_products,
         );
+        let count = messages.iter().map(|(i, _)| *i + 1).max().unwrap_or(0);
+        let generators = public_key.generators(count);
         for (i, m) in messages {
-            if *i > public_key.y.len() {
-                return Err(Error::General("invalid blind signing"));
-            }
             secrets.push(*m);
-            points.push(public_key.y[*i]);
-            committing.commit_random(public_key.y[*i], &mut rng);
+            points.push(generators[*i]);
+            committing.commit_random(generators[*i], &mut rng);
         }
         let mut transcript = Transcript::new(b"new blind signature");
         transcript.append_message(b"public key", public_key.to_bytes().as_ref());
@@ -59,10 +59,6 @@ impl Signature {
         }
 
         let msgs = msgs.as_ref();
-        if msgs.len() > sk.max_messages {
-            return Err(Error::General("Too many messages"));
-        }
-
         let pub_key = PublicKey::from(sk);
         let domain = domain_calculation(&pub_key);
         let e = compute_e(sk, msgs, domain);
@@ -73,7 +69,8 @@ impl Signature {
             return Err(Error::General("Invalid signature"));
         }
 
-        let b = G1Projective::GENERATOR + G1Projective::sum_of_products(&pub_key.y, msgs);
+        let b = G1Projective::GENERATOR
+            + G1Projective::sum_of_products(&pub_key.generators(msgs.len()), msgs);
 
         let a = b * ske.expect("a valid scalar");
 
@@ -89,11 +86,12 @@ impl Signature {
             return Choice::from(0);
         }
         let msgs = msgs.as_ref();
-        if msgs.is_empty() || msgs.len() > pk.y.len() {
+        if msgs.is_empty() {
             return Choice::from(0);
         }
 
-        let b = G1Projective::GENERATOR + G1Projective::sum_of_products(&pk.y, msgs);
+        let b = G1Projective::GENERATOR
+            + G1Projective::sum_of_products(&pk.generators(msgs.len()), msgs);
         let lhs_pk = G2Projective::GENERATOR * self.e + pk.w;
 
         multi_miller_loop(&[
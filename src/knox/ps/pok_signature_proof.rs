@@ -6,6 +6,7 @@ use blsful::inner_types::*;
 use core::ops::BitOr;
 use elliptic_curve::group::prime::PrimeCurveAffine;
 use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -163,6 +164,51 @@ impl ProofOfSignatureKnowledge for PokSignatureProof {
 
         Ok(hidden)
     }
+
+    fn can_simulate(public_key: &PublicKey) -> bool {
+        !bool::from(public_key.is_invalid())
+    }
+
+    fn simulate(
+        public_key: &PublicKey,
+        revealed_messages: &[(usize, Scalar)],
+        _challenge: Scalar,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Self> {
+        if public_key.is_invalid().into() {
+            return Err(Error::General("Invalid public key"));
+        }
+        // Pick sigma_1 = a·P, sigma_2 = b·P and solve the pairing equation for the
+        // commitment J = (b/a)·P~ - X - Σ Y_i·m_i. The blind commitment is
+        // recomputed by the verifier from the random responses
+        let a = Scalar::random(&mut rng);
+        let b = Scalar::random(&mut rng);
+        let a_inv = Option::<Scalar>::from(a.invert())
+            .ok_or(Error::General("Invalid proof - random value was zero"))?;
+
+        let mut points = vec![G2Projective::GENERATOR, public_key.x];
+        let mut scalars = vec![b * a_inv, -Scalar::ONE];
+        let mut known = BTreeSet::new();
+        for (idx, msg) in revealed_messages {
+            if *idx >= public_key.y.len() {
+                return Err(Error::General("Invalid proof - revealed message index"));
+            }
+            known.insert(*idx);
+            points.push(public_key.y[*idx]);
+            scalars.push(-*msg);
+        }
+        let commitment = G2Projective::sum_of_products(points.as_ref(), scalars.as_ref());
+
+        let proof = (0..public_key.y.len() - known.len() + 2)
+            .map(|_| Scalar::random(&mut rng))
+            .collect();
+        Ok(Self {
+            sigma_1: G1Projective::GENERATOR * a,
+            sigma_2: G1Projective::GENERATOR * b,
+            commitment,
+            proof,
+        })
+    }
 }
 
 impl PokSignatureProof {
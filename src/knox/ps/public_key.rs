@@ -14,6 +14,10 @@ use subtle::Choice;
 ///
 /// `w` corresponds to m' in the paper to achieve
 /// EUF-CMA security level.
+///
+/// Each message needs its own secret so the key grows with the number of messages
+/// and can't be extended without the secret key. Schemas with many claims are better
+/// served by [`crate::knox::bbs::PublicKey`] which derives its message generators.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct PublicKey {
     /// The secret for m'
@@ -163,9 +163,6 @@ fn generate_secret_key(
     mut rng: impl RngCore + CryptoRng,
 ) -> Option<SecretKey> {
     let count = count.get();
-    if count > 128 {
-        return None;
-    }
     let w = Scalar::random(&mut rng);
     let x = Scalar::random(&mut rng);
     let mut y = Vec::new();
@@ -142,6 +142,20 @@ pub trait ProofOfSignatureKnowledge:
         public_key: &Self::PublicKey,
         revealed_messages: &[(usize, Scalar)],
     ) -> CredxResult<BTreeMap<usize, Scalar>>;
+
+    /// Can [`ProofOfSignatureKnowledge::simulate`] create proofs that verify with `public_key`
+    fn can_simulate(public_key: &Self::PublicKey) -> bool;
+
+    /// Simulate a proof that verifies with `challenge` without knowing a signature.
+    /// The hidden message proofs are random.
+    ///
+    /// Only sound when the challenge is fixed by the other branches of a disjunction.
+    fn simulate(
+        public_key: &Self::PublicKey,
+        revealed_messages: &[(usize, Scalar)],
+        challenge: Scalar,
+        rng: impl RngCore + CryptoRng,
+    ) -> CredxResult<Self>;
 }
 
 /// Trait for abstracting a short group signature scheme
@@ -19,6 +19,7 @@ fn create_credential<S: ShortGroupSignatureScheme>() -> CredxResult<CredentialBu
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -28,6 +29,7 @@ fn create_credential<S: ShortGroupSignatureScheme>() -> CredxResult<CredentialBu
                 min: Some(3),
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -37,6 +39,7 @@ fn create_credential<S: ShortGroupSignatureScheme>() -> CredxResult<CredentialBu
                 min: None,
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -46,6 +49,7 @@ fn create_credential<S: ShortGroupSignatureScheme>() -> CredxResult<CredentialBu
                 min: Some(0),
                 max: Some(u16::MAX as isize),
             }],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some(LABEL), Some(DESCRIPTION), &[], &schema_claims)?;
@@ -298,7 +302,7 @@ mod tests {
     #[test]
     fn test_decode_to_anoncreds() {
         let encoded_string: &str = "ukoGpc2lnbmF0dXJlg6ZtX3RpY2vZQDEwNTZlMjU3ODJlZDE0ZGJjNTY0YTE5ZjM2ZTMzOTc5OTMxYzBmYTQ0ZWU4OWQzNDk1YjVhNWE4OTI5MTE5Njmnc2lnbWFfMdlgYjUyZDcyNGM4ZTdhYjJiYzExYzRmMzYzYmRkMTJkMjUzNTNlNjNhODA0Nzc5MWFmZjE2MTViYWU5ZGVmZmY5NzM4MmM0OGU4MGE4YjBhOGM4YmIyNjk1NmVhZGM4NmVkp3NpZ21hXzLZYGEzMDYxN2M1ODY0ZWM3NjU1ZDM5ZjNkNzc1MTYzY2JjYTliNDFmNmFkOGZiYzJmNTE4NzUwNzA5YTUxZGRjMzQ0NDEyYjYwYTE2MWJjMjc1MjViZTg0ODRjNGZhNjI2M4KxcmV2b2NhdGlvbl9oYW5kbGXZYDhhY2Q5OWJmMTgxYTMzNTFmZWQ0ZjBhMGNmNmFmNDE2YjhiMDM1OGRkMDg4MGViOTkyODU1NDZlYTAzNmM0ZDZjMjViNjExNzJhMjA2NWI1ZDIyY2JiYjI3YTljMmRlObByZXZvY2F0aW9uX2luZGV4AA";
-        let decoded = decode_to_anoncreds_proof(&encoded_string);
+        let decoded = decode_to_anoncreds_proof(encoded_string);
         // println!("Decoded data: {:?}", decoded);
         assert!(decoded.is_ok(), "Decoding failed");
     }
@@ -312,7 +316,7 @@ mod tests {
         let cred_json: Value = serde_json::from_str(&cred_string).unwrap();
         let encoded_string = encode_to_w3c_proof(&cred_json).unwrap();
         // println!("Encoded data: {}", encoded_string);
-        assert!(encoded_string.len() > 0);
+        assert!(!encoded_string.is_empty());
     }
 
     #[test]
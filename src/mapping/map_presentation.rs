@@ -8,8 +8,8 @@ use crate::prelude::{
 };
 use crate::presentation::{Presentation, PresentationSchema};
 use crate::statement::{
-    CommitmentStatement, RangeStatement, RevocationStatement, SignatureStatement,
-    VerifiableEncryptionStatement,
+    CommitmentStatement, RangeStatement, RegistryEpochPolicy, RevocationStatement,
+    SignatureStatement, VerifiableEncryptionStatement,
 };
 use crate::{random_string, CredxResult};
 use base64::Engine;
@@ -73,6 +73,7 @@ fn create_presentation<S: ShortGroupSignatureScheme>(
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -82,6 +83,7 @@ fn create_presentation<S: ShortGroupSignatureScheme>(
                 min: Some(3),
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -91,6 +93,7 @@ fn create_presentation<S: ShortGroupSignatureScheme>(
                 min: None,
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -100,6 +103,7 @@ fn create_presentation<S: ShortGroupSignatureScheme>(
                 min: Some(0),
                 max: Some(u16::MAX as isize),
             }],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some(LABEL), Some(DESCRIPTION), &[], &schema_claims)?;
@@ -132,6 +136,8 @@ fn create_presentation<S: ShortGroupSignatureScheme>(
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let comm_st = CommitmentStatement {
         id: random_string(16, thread_rng()),
@@ -669,7 +675,7 @@ mod tests {
 
         let presentation_proof = tmp_list.0.clone();
         let presentation_request = tmp_list.1.clone();
-        let nonce: [u8; 16] = tmp_list.2.clone();
+        let nonce: [u8; 16] = tmp_list.2;
         presentation_proof
             .verify(&presentation_request, &nonce)
             .expect("Verification should not fail");
@@ -690,7 +696,7 @@ mod tests {
         let tmp_list = create_presentation::<PsScheme>().unwrap();
         let presentation_proof = tmp_list.0.clone();
         let presentation_request = tmp_list.1.clone();
-        let nonce: [u8; 16] = tmp_list.2.clone();
+        let nonce: [u8; 16] = tmp_list.2;
 
         let w3c_presentation =
             map_to_w3c_presentation(&presentation_proof, &presentation_request, &nonce);
@@ -710,7 +716,7 @@ mod tests {
         let tmp_list = create_presentation::<PsScheme>().unwrap();
         let presentation_proof = tmp_list.0.clone();
         let presentation_request = tmp_list.1.clone();
-        let nonce: [u8; 16] = tmp_list.2.clone();
+        let nonce: [u8; 16] = tmp_list.2;
 
         let mut file = File::create("./samples/presentations/anoncreds_presentation_list.json")
             .expect("Failed to create file");
@@ -1,39 +1,62 @@
+mod any_of;
+mod claim_range;
 mod commitment;
 mod create;
 mod credential;
 mod equality;
 mod membership;
+mod non_membership;
+mod not_equal;
+mod presence;
 mod proof;
 mod range;
+mod request;
 mod revocation;
 mod schema;
+mod selector;
 mod signature;
+mod suspension;
 mod verifiable_encryption;
 mod verifiable_encryption_decryption;
 mod verify;
 
+pub use any_of::*;
+pub use claim_range::*;
 pub use commitment::*;
 pub use credential::*;
 pub use equality::*;
 pub use membership::*;
+pub use non_membership::*;
+pub use not_equal::*;
+pub use presence::*;
 pub use proof::*;
 pub use range::*;
+pub use request::*;
 pub use revocation::*;
 pub use schema::*;
+pub use selector::*;
 pub use signature::*;
+pub use suspension::*;
 pub use verifiable_encryption::*;
 pub use verifiable_encryption_decryption::*;
 
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use crate::knox::short_group_sig_core::{HiddenMessage, ProofMessage};
 use crate::verifier::*;
-use crate::{claim::ClaimData, error::Error, statement::Statements, utils::*, CredxResult};
+use crate::{
+    claim::{ClaimData, NestedClaimData},
+    error::Error,
+    statement::Statements,
+    utils::*,
+    CredxResult,
+};
 use blsful::inner_types::{G1Affine, G2Affine, Scalar};
 use elliptic_curve::{ff::Field, group::prime::PrimeCurveAffine};
 use indexmap::{IndexMap, IndexSet};
 use merlin::Transcript;
 use rand_core::{CryptoRng, OsRng, RngCore};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uint_zigzag::Uint;
 
 /// Implementers can build proofs for presentations
@@ -52,6 +75,12 @@ pub(crate) enum PresentationBuilders<'a, S: ShortGroupSignatureScheme> {
     Range(Box<RangeBuilder<'a>>),
     Membership(Box<MembershipProofBuilder<'a>>),
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionBuilder<'a>>),
+    NonMembership(Box<NonMembershipProofBuilder<'a>>),
+    Presence(Box<PresenceBuilder<'a>>),
+    Suspension(Box<SuspensionProofBuilder<'a>>),
+    AnyOf(Box<AnyOfBuilder<'a, S>>),
+    NotEqual(Box<NotEqualBuilder<'a>>),
+    ClaimRange(Box<ClaimRangeBuilder<'a>>),
 }
 
 impl<S: ShortGroupSignatureScheme> PresentationBuilders<'_, S> {
@@ -66,6 +95,12 @@ impl<S: ShortGroupSignatureScheme> PresentationBuilders<'_, S> {
             Self::Range(r) => r.gen_proof(challenge),
             Self::Membership(m) => m.gen_proof(challenge),
             Self::VerifiableEncryptionDecryption(v) => v.gen_proof(challenge),
+            Self::NonMembership(n) => n.gen_proof(challenge),
+            Self::Presence(p) => p.gen_proof(challenge),
+            Self::Suspension(s) => s.gen_proof(challenge),
+            Self::AnyOf(a) => a.gen_proof(challenge),
+            Self::NotEqual(n) => n.gen_proof(challenge),
+            Self::ClaimRange(c) => c.gen_proof(challenge),
         }
     }
 }
@@ -128,6 +163,46 @@ impl<'a, S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionBuilde
     }
 }
 
+impl<'a, S: ShortGroupSignatureScheme> From<NonMembershipProofBuilder<'a>>
+    for PresentationBuilders<'a, S>
+{
+    fn from(value: NonMembershipProofBuilder<'a>) -> Self {
+        Self::NonMembership(Box::new(value))
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> From<PresenceBuilder<'a>> for PresentationBuilders<'a, S> {
+    fn from(value: PresenceBuilder<'a>) -> Self {
+        Self::Presence(Box::new(value))
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> From<SuspensionProofBuilder<'a>>
+    for PresentationBuilders<'a, S>
+{
+    fn from(value: SuspensionProofBuilder<'a>) -> Self {
+        Self::Suspension(Box::new(value))
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> From<AnyOfBuilder<'a, S>> for PresentationBuilders<'a, S> {
+    fn from(value: AnyOfBuilder<'a, S>) -> Self {
+        Self::AnyOf(Box::new(value))
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> From<NotEqualBuilder<'a>> for PresentationBuilders<'a, S> {
+    fn from(value: NotEqualBuilder<'a>) -> Self {
+        Self::NotEqual(Box::new(value))
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> From<ClaimRangeBuilder<'a>> for PresentationBuilders<'a, S> {
+    fn from(value: ClaimRangeBuilder<'a>) -> Self {
+        Self::ClaimRange(Box::new(value))
+    }
+}
+
 /// Defines the proofs for a verifier
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Presentation<S: ShortGroupSignatureScheme> {
@@ -147,9 +222,32 @@ pub struct Presentation<S: ShortGroupSignatureScheme> {
         deserialize_with = "deserialize_indexmap_nested"
     )]
     pub disclosed_messages: IndexMap<String, IndexMap<String, ClaimData>>,
+    /// The labels of revealed optional claims that are absent in each signature statement.
+    /// These are not included in `disclosed_messages`
+    #[serde(
+        default,
+        serialize_with = "serialize_indexmap",
+        deserialize_with = "deserialize_indexmap"
+    )]
+    pub absent_claims: IndexMap<String, Vec<String>>,
 }
 
 impl<S: ShortGroupSignatureScheme> Presentation<S> {
+    /// The disclosed messages for each signature statement with the
+    /// original object and array structure of the credential schema
+    pub fn nested_disclosed_messages(
+        &self,
+        schema: &PresentationSchema<S>,
+    ) -> IndexMap<String, BTreeMap<String, NestedClaimData>> {
+        let mut nested = IndexMap::new();
+        for (id, dm) in &self.disclosed_messages {
+            if let Some(Statements::Signature(ss)) = schema.statements.get(id) {
+                nested.insert(id.clone(), ss.issuer.schema.nest_claims(dm));
+            }
+        }
+        nested
+    }
+
     #[allow(clippy::type_complexity)]
     fn split_statements(
         schema: &PresentationSchema<S>,
@@ -217,6 +315,18 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
         }
     }
 
+    fn add_absent_claims_challenge_contribution(
+        id: &String,
+        absent: &[String],
+        transcript: &mut Transcript,
+    ) {
+        transcript.append_message(b"absent claims from statement ", id.as_bytes());
+        transcript.append_message(b"absent claims length", &Uint::from(absent.len()).to_vec());
+        for label in absent {
+            transcript.append_message(b"absent claim label", label.as_bytes());
+        }
+    }
+
     #[allow(clippy::type_complexity)]
     /// Map the claims to the respective types
     fn get_message_types<'a>(
@@ -242,7 +352,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
         // and the statement. Equality statements are shared across signatures
         for statement in predicate_statements.values() {
             let reference_ids = statement.reference_ids();
-            if reference_ids.len() > 1 {
+            if reference_ids.len() > 1 && matches!(statement, Statements::Equality(_)) {
                 same_proof_messages.push((*statement).clone());
             }
 
@@ -257,7 +367,19 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                         continue;
                     }
                     Some(indexer) => {
-                        let claim_index = statement.get_claim_index(ref_id);
+                        // Claim range statements name the claim by label
+                        let claim_index = match statement {
+                            Statements::ClaimRange(c) => match signature_statements.get(ref_id) {
+                                Some(Statements::Signature(ss)) => c.claim_index(ss)?,
+                                _ => {
+                                    return Err(Error::InvalidPresentationData(format!(
+                                        "claim range statement '{}' references '{}' which is not a signature statement",
+                                        c.id, ref_id
+                                    )))
+                                }
+                            },
+                            _ => statement.get_claim_index(ref_id),
+                        };
                         match indexer.get_mut(claim_index) {
                             None => {
                                 return Err(Error::InvalidPresentationData(format!(
@@ -288,7 +410,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                 // it must use a shared blinder, otherwise its proof specific
                 if let Statements::Signature(ss) = sig {
                     let claim_label = ss.issuer.schema.claim_indices.get_index(index).unwrap();
-                    if ss.disclosed.contains(claim_label) {
+                    if ss.is_disclosed(claim_label) {
                         proof_claims.push((claim.clone(), ProofMessage::Revealed(claim_value)));
                     } else if shared_proof_msg_indices[id][index] {
                         let blinder = Scalar::random(&mut rng);
@@ -0,0 +1,282 @@
+use super::*;
+use crate::knox::accumulator::vb20::MembershipProof as Vb20MembershipProof;
+use crate::knox::short_group_sig_core::short_group_traits::{
+    ProofOfSignatureKnowledge, ShortGroupSignatureScheme,
+};
+use crate::statement::{AnyOfBranch, AnyOfStatement};
+use std::collections::BTreeSet;
+
+/// A builder for disjunctive proofs.
+///
+/// The branch with a supplied credential is proven, every other branch is
+/// simulated with a challenge chosen in advance. The proven branch gets the
+/// presentation challenge minus the simulated ones.
+pub(crate) struct AnyOfBuilder<'a, S: ShortGroupSignatureScheme> {
+    id: &'a String,
+    signature: SignatureBuilder<'a, S>,
+    predicates: Vec<PresentationBuilders<'a, S>>,
+    /// The challenge and proof of each simulated branch, `None` for the proven branch
+    branches: Vec<Option<(Scalar, AnyOfBranchProof<S>)>>,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for AnyOfBuilder<'_, S> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let proven_challenge = self
+            .branches
+            .iter()
+            .flatten()
+            .fold(challenge, |acc, (c, _)| acc - c);
+        let mut proven = Some(AnyOfBranchProof {
+            signature: self.signature.gen_signature_proof(proven_challenge),
+            predicates: self
+                .predicates
+                .into_iter()
+                .map(|p| p.gen_proof(proven_challenge))
+                .collect(),
+        });
+        let mut challenges = Vec::with_capacity(self.branches.len());
+        let mut branches = Vec::with_capacity(self.branches.len());
+        for branch in self.branches {
+            let (c, proof) = branch.unwrap_or_else(|| {
+                (
+                    proven_challenge,
+                    proven.take().expect("only one branch is proven"),
+                )
+            });
+            challenges.push(c);
+            branches.push(proof);
+        }
+        AnyOfProof {
+            id: self.id.clone(),
+            challenges,
+            branches,
+        }
+        .into()
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> AnyOfBuilder<'a, S> {
+    /// Commit to the first branch with a supplied credential and simulate the others
+    pub fn commit(
+        statement: &'a AnyOfStatement<S>,
+        credentials: &IndexMap<String, PresentationCredential<S>>,
+        nonce: &[u8],
+        mut rng: impl RngCore + CryptoRng,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        statement.validate()?;
+        let (proven, credential) = statement
+            .branches
+            .iter()
+            .enumerate()
+            .find_map(|(i, b)| match credentials.get(&b.signature.id) {
+                Some(PresentationCredential::Signature(c)) => Some((i, c)),
+                _ => None,
+            })
+            .ok_or(Error::InvalidPresentationData(format!(
+                "no credential was supplied for any branch of any of statement '{}'",
+                statement.id
+            )))?;
+
+        let mut signature = None;
+        let mut predicates = Vec::new();
+        let mut branches = Vec::with_capacity(statement.branches.len());
+        for (i, branch) in statement.branches.iter().enumerate() {
+            transcript.append_message(b"any of branch", &Uint::from(i).to_vec());
+            if i != proven {
+                let challenge = Scalar::random(&mut rng);
+                let proof = Self::simulate(branch, challenge, nonce, &mut rng, transcript)?;
+                branches.push(Some((challenge, proof)));
+                continue;
+            }
+
+            // Claims used by predicates share blinders with the signature proof
+            let shared = branch
+                .predicates
+                .iter()
+                .map(|p| p.get_claim_index(&branch.signature.id))
+                .collect::<BTreeSet<_>>();
+            let messages = credential
+                .claims
+                .iter()
+                .enumerate()
+                .map(|(index, claim)| {
+                    let value = claim.to_scalar();
+                    if shared.contains(&index) {
+                        ProofMessage::Hidden(HiddenMessage::ExternalBlinding(
+                            value,
+                            Scalar::random(&mut rng),
+                        ))
+                    } else {
+                        ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(value))
+                    }
+                })
+                .collect::<Vec<_>>();
+            // The simulated branches can only use the statement epochs
+            // so any other epoch would reveal the proven branch
+            if credential.key_epoch != branch.key_epoch() {
+                return Err(Error::InvalidPresentationData(format!(
+                    "any of statement '{}' branch '{}' requires a credential signed in key epoch '{}' but found '{}'",
+                    statement.id,
+                    branch.signature.id,
+                    branch.key_epoch(),
+                    credential.key_epoch
+                )));
+            }
+            signature = Some(SignatureBuilder::commit(
+                &branch.signature,
+                &credential.signature,
+                credential.key_epoch,
+                &messages,
+                &mut rng,
+                transcript,
+            )?);
+            for predicate in &branch.predicates {
+                let claim = predicate.get_claim_index(&branch.signature.id);
+                let message =
+                    *messages
+                        .get(claim)
+                        .ok_or(Error::InvalidPresentationData(format!(
+                            "statement '{}' references claim '{}' which doesn't exist",
+                            predicate.id(),
+                            claim
+                        )))?;
+                match predicate {
+                    Statements::Revocation(r) => {
+                        if credential.revocation_epoch != branch.registry_epoch(r) {
+                            return Err(Error::InvalidPresentationData(format!(
+                                "any of statement '{}' revocation statement '{}' requires registry epoch '{}' but the credential has '{}'",
+                                statement.id,
+                                r.id,
+                                branch.registry_epoch(r),
+                                credential.revocation_epoch
+                            )));
+                        }
+                        let builder = RevocationProofBuilder::commit(
+                            r, credential, message, nonce, transcript,
+                        )?;
+                        predicates.push(builder.into());
+                    }
+                    Statements::Membership(m) => {
+                        let member = if let Some(PresentationCredential::Membership(member)) =
+                            credentials.get(&m.id)
+                        {
+                            member
+                        } else {
+                            return Err(Error::InvalidPresentationData(format!(
+                                "membership statement '{}' in any of statement '{}' has no membership credential",
+                                m.id, statement.id
+                            )));
+                        };
+                        let builder =
+                            MembershipProofBuilder::commit(m, member, message, nonce, transcript)?;
+                        predicates.push(builder.into());
+                    }
+                    // rejected by validate
+                    _ => {}
+                }
+            }
+            branches.push(None);
+        }
+
+        Ok(Self {
+            id: &statement.id,
+            signature: signature.expect("proven branch is committed"),
+            predicates,
+            branches,
+        })
+    }
+
+    /// Simulate the proofs for a branch and add the commitments the verifier
+    /// will compute from them to the transcript
+    fn simulate(
+        branch: &AnyOfBranch<S>,
+        challenge: Scalar,
+        nonce: &[u8],
+        mut rng: impl RngCore + CryptoRng,
+        transcript: &mut Transcript,
+    ) -> CredxResult<AnyOfBranchProof<S>> {
+        let verifying_key = branch
+            .signature
+            .issuer
+            .verifying_key_for_epoch(branch.key_epoch())?;
+        let pok = S::ProofOfSignatureKnowledge::simulate(verifying_key, &[], challenge, &mut rng)
+            .map_err(|_| Error::InvalidSignatureProofData)?;
+        let hidden_messages = pok.get_hidden_message_proofs(verifying_key, &[])?;
+
+        let mut predicates = Vec::with_capacity(branch.predicates.len());
+        for predicate in &branch.predicates {
+            let claim = predicate.get_claim_index(&branch.signature.id);
+            let message_proof =
+                *hidden_messages
+                    .get(&claim)
+                    .ok_or(Error::InvalidPresentationData(format!(
+                        "statement '{}' references claim '{}' which doesn't exist",
+                        predicate.id(),
+                        claim
+                    )))?;
+            match predicate {
+                Statements::Revocation(r) => predicates.push(
+                    RevocationProof {
+                        id: r.id.clone(),
+                        epoch: branch.registry_epoch(r),
+                        proof: Vb20MembershipProof::simulate(message_proof, &mut rng),
+                    }
+                    .into(),
+                ),
+                Statements::Membership(m) => predicates.push(
+                    MembershipProof {
+                        id: m.id.clone(),
+                        proof: Vb20MembershipProof::simulate(message_proof, &mut rng),
+                    }
+                    .into(),
+                ),
+                // rejected by validate
+                _ => {}
+            }
+        }
+        let proof = AnyOfBranchProof {
+            signature: SignatureProof {
+                id: branch.signature.id.clone(),
+                disclosed_messages: IndexMap::new(),
+                key_epoch: branch.key_epoch(),
+                pok,
+            },
+            predicates,
+        };
+        for verifier in branch_verifiers(branch, &proof, nonce)? {
+            verifier.add_challenge_contribution(challenge, transcript)?;
+        }
+        Ok(proof)
+    }
+}
+
+/// A disjunctive proof
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnyOfProof<S: ShortGroupSignatureScheme> {
+    /// The statement identifier
+    pub id: String,
+    /// The challenge for each branch, these sum to the presentation challenge
+    pub challenges: Vec<Scalar>,
+    /// The proofs for each branch
+    // No bounds since branches contain `PresentationProofs<S>` which contains this proof
+    #[serde(bound(serialize = "", deserialize = ""))]
+    pub branches: Vec<AnyOfBranchProof<S>>,
+}
+
+/// The proofs for a branch of an [`AnyOfProof`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnyOfBranchProof<S: ShortGroupSignatureScheme> {
+    /// The signature proof
+    #[serde(bound(
+        serialize = "SignatureProof<S>: Serialize",
+        deserialize = "SignatureProof<S>: Deserialize<'de>"
+    ))]
+    pub signature: SignatureProof<S>,
+    /// The predicate proofs in branch statement order
+    #[serde(bound(
+        serialize = "PresentationProofs<S>: Serialize",
+        deserialize = "PresentationProofs<S>: Deserialize<'de>"
+    ))]
+    pub predicates: Vec<PresentationProofs<S>>,
+}
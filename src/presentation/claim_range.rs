@@ -0,0 +1,113 @@
+use crate::claim::ClaimData;
+use crate::create_domain_proof_generator;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::{PresentationBuilder, PresentationProofs, RangeBuilder};
+use crate::statement::ClaimRangeStatement;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use bulletproofs::RangeProof as RangeProofBulletproof;
+use elliptic_curve::{group::Curve, Field};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// The generators for the claim commitment in claim range proofs
+pub(crate) fn claim_range_generators() -> (G1Projective, G1Projective) {
+    (
+        create_domain_proof_generator(b"credx claim range message generator"),
+        create_domain_proof_generator(b"credx claim range blinder generator"),
+    )
+}
+
+/// A claim range proof builder.
+///
+/// Commits to the claim like a commitment statement would using the
+/// claim range generators then proves the commitment is in range.
+pub(crate) struct ClaimRangeBuilder<'a> {
+    statement: &'a ClaimRangeStatement,
+    commitment: G1Projective,
+    blinder: Scalar,
+    r: Scalar,
+    range: RangeBuilder<'a>,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for ClaimRangeBuilder<'_> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let blinder_proof = self.r + challenge * self.blinder;
+        ClaimRangeProof {
+            id: self.statement.id.clone(),
+            commitment: self.commitment,
+            blinder_proof,
+            proof: self.range.gen_range_proof(challenge).proof,
+        }
+        .into()
+    }
+}
+
+impl<'a> ClaimRangeBuilder<'a> {
+    /// Create a new claim range proof builder
+    pub fn commit(
+        statement: &'a ClaimRangeStatement,
+        claim: &ClaimData,
+        b: Scalar,
+        mut rng: impl RngCore + CryptoRng,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        let value = match claim {
+            ClaimData::Number(n) => n.value,
+            ClaimData::Date(d) => d.value,
+            ClaimData::DateTime(d) => d.value,
+            ClaimData::Decimal(d) => d.value,
+            _ => return Err(Error::InvalidPresentationData(format!("claim range statement with id '{}' references claim '{}' which is not a number, date, datetime or decimal claim", statement.id, statement.claim))),
+        };
+        let (g, h) = claim_range_generators();
+        // b is the signature proof nonce for the claim and
+        // must not also blind the commitment
+        let blinder = Scalar::random(&mut rng);
+        let r = Scalar::random(&mut rng);
+        let commitment = g * claim.to_scalar() + h * blinder;
+        let blind_commitment = g * b + h * r;
+
+        transcript.append_message(b"", statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        let range = RangeBuilder::commit_bounds(
+            &statement.id,
+            statement.lower,
+            statement.upper,
+            g,
+            h,
+            commitment,
+            blinder,
+            value,
+            transcript,
+        )?;
+        Ok(Self {
+            statement,
+            commitment,
+            blinder,
+            r,
+            range,
+        })
+    }
+}
+
+/// A range proof on a signature claim
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClaimRangeProof {
+    /// The statement identifier
+    pub id: String,
+    /// The claim commitment
+    pub commitment: G1Projective,
+    /// The schnorr blinder proof
+    pub blinder_proof: Scalar,
+    /// The range proof
+    pub proof: RangeProofBulletproof,
+}
@@ -1,6 +1,7 @@
 use super::*;
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use crate::presentation::verifiable_encryption_decryption::VerifiableEncryptionDecryptionBuilder;
+use crate::statement::NotEqualTarget;
 use log::debug;
 
 impl<S: ShortGroupSignatureScheme> Presentation<S> {
@@ -37,10 +38,12 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
 
         let mut builders = Vec::<PresentationBuilders<S>>::with_capacity(schema.statements.len());
         let mut disclosed_messages = IndexMap::new();
+        let mut absent_claims = IndexMap::new();
 
         for (id, sig_statement) in &signature_statements {
             if let Statements::Signature(ss) = sig_statement {
                 let mut dm = IndexMap::new();
+                let mut absent = Vec::new();
                 let cred = if let PresentationCredential::Signature(cred) = &credentials[*id] {
                     cred
                 } else {
@@ -49,14 +52,23 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                 for (index, claim) in cred.claims.iter().enumerate() {
                     if matches!(messages[id][index].1, ProofMessage::Revealed(_)) {
                         let label = ss.issuer.schema.claim_indices.get_index(index).unwrap();
-                        dm.insert((*label).clone(), claim.clone());
+                        if claim.is_absent() {
+                            absent.push((*label).clone());
+                        } else {
+                            dm.insert((*label).clone(), claim.clone());
+                        }
                     }
                 }
                 Self::add_disclosed_messages_challenge_contribution(id, &dm, &mut transcript);
+                if !absent.is_empty() {
+                    Self::add_absent_claims_challenge_contribution(id, &absent, &mut transcript);
+                    absent_claims.insert((*id).clone(), absent);
+                }
                 let signature_messages = messages[*id].iter().map(|(_, m)| *m).collect::<Vec<_>>();
                 let builder = SignatureBuilder::commit(
                     ss,
                     &cred.signature,
+                    cred.key_epoch,
                     &signature_messages,
                     rng,
                     &mut transcript,
@@ -123,6 +135,33 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     id_to_builder.insert(*id, builders.len());
                     builders.push(builder.into());
                 }
+                Statements::NonMembership(n) => {
+                    let (_, proof_message) = messages[&n.reference_id][n.claim];
+                    if matches!(proof_message, ProofMessage::Revealed(_)) {
+                        return Err(Error::InvalidClaimData(
+                            "revealed claim cannot be used for set non-membership proofs",
+                        ));
+                    }
+                    let credential = if let PresentationCredential::NonMembership(credential) =
+                        &credentials[&n.id]
+                    {
+                        credential
+                    } else {
+                        continue;
+                    };
+                    let message = proof_message.get_message();
+                    let blinder = proof_message.get_blinder(rng).unwrap();
+                    let builder = NonMembershipProofBuilder::commit(
+                        n,
+                        credential,
+                        message,
+                        blinder,
+                        nonce,
+                        &mut transcript,
+                    )?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
                 Statements::Commitment(c) => {
                     let (_, proof_message) = messages[&c.reference_id][c.claim];
                     if matches!(proof_message, ProofMessage::Revealed(_)) {
@@ -176,6 +215,103 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     id_to_builder.insert(*id, builders.len());
                     builders.push(builder.into());
                 }
+                Statements::Presence(p) => {
+                    let (_, proof_message) = messages[&p.reference_id][p.claim];
+                    if matches!(proof_message, ProofMessage::Revealed(_)) {
+                        return Err(Error::InvalidClaimData(
+                            "revealed claim cannot be used for presence proofs",
+                        ));
+                    }
+                    let message = proof_message.get_message();
+                    let blinder = proof_message.get_blinder(rng).unwrap();
+                    let builder =
+                        PresenceBuilder::commit(p, message, blinder, rng, &mut transcript)?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
+                Statements::Suspension(s) => {
+                    let (_, proof_message) = messages[&s.reference_id][s.claim];
+                    if matches!(proof_message, ProofMessage::Revealed(_)) {
+                        return Err(Error::InvalidClaimData(
+                            "revealed claim cannot be used for set non-membership proofs",
+                        ));
+                    }
+                    let credential = if let PresentationCredential::Signature(credential) =
+                        &credentials[&s.reference_id]
+                    {
+                        credential
+                    } else {
+                        continue;
+                    };
+                    let message = proof_message.get_message();
+                    let blinder = proof_message.get_blinder(rng).unwrap();
+                    let builder = SuspensionProofBuilder::commit(
+                        s,
+                        credential,
+                        message,
+                        blinder,
+                        nonce,
+                        &mut transcript,
+                    )?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
+                Statements::NotEqual(n) => {
+                    let (_, proof_message) = messages[&n.reference_id][n.claim];
+                    if matches!(proof_message, ProofMessage::Revealed(_)) {
+                        return Err(Error::InvalidClaimData(
+                            "revealed claim cannot be used for not equal proofs",
+                        ));
+                    }
+                    let other = match &n.other {
+                        NotEqualTarget::Value(_) => None,
+                        NotEqualTarget::Claim {
+                            reference_id,
+                            claim,
+                        } => {
+                            let (_, other_message) = messages[reference_id][*claim];
+                            if matches!(other_message, ProofMessage::Revealed(_)) {
+                                return Err(Error::InvalidClaimData(
+                                    "revealed claim cannot be used for not equal proofs",
+                                ));
+                            }
+                            Some((
+                                other_message.get_message(),
+                                other_message.get_blinder(rng).unwrap(),
+                            ))
+                        }
+                    };
+                    let message = proof_message.get_message();
+                    let blinder = proof_message.get_blinder(rng).unwrap();
+                    let builder =
+                        NotEqualBuilder::commit(n, message, blinder, other, rng, &mut transcript)?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
+                Statements::ClaimRange(c) => {
+                    let signature = match signature_statements.get(&c.signature_id) {
+                        Some(Statements::Signature(ss)) => ss,
+                        _ => return Err(Error::InvalidPresentationData(format!("claim range statement with id '{}' references a signature statement with id '{}' but no signature statement has that id.", c.id, c.signature_id))),
+                    };
+                    let claim_index = c.claim_index(signature)?;
+                    let (claim, proof_message) = &messages[&c.signature_id][claim_index];
+                    if matches!(proof_message, ProofMessage::Revealed(_)) {
+                        return Err(Error::InvalidClaimData(
+                            "revealed claim cannot be used for range proofs",
+                        ));
+                    }
+                    let blinder = proof_message.get_blinder(rng).unwrap();
+                    let builder =
+                        ClaimRangeBuilder::commit(c, claim, blinder, rng, &mut transcript)?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
+                Statements::AnyOf(a) => {
+                    let builder =
+                        AnyOfBuilder::commit(a, credentials, nonce, rng, &mut transcript)?;
+                    id_to_builder.insert(*id, builders.len());
+                    builders.push(builder.into());
+                }
                 Statements::Range(_) => {
                     // handle after these since they depend on commitment builders
                     range_id.insert(*id);
@@ -203,17 +339,19 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                 };
                 let builder_index = id_to_builder[&r.reference_id];
                 if let PresentationBuilders::Commitment(commitment) = &builders[builder_index] {
-                    if let ClaimData::Number(n) = sig
+                    let value = match sig
                         .claims
                         .get(r.claim)
                         .ok_or(Error::InvalidPresentationData(format!("range proof statement with id '{}' references claim '{}' which doesn't exist", id, r.claim)))?
                     {
-                        let builder =
-                            RangeBuilder::commit(r, commitment, n.value, &mut transcript)?;
-                        range_builders.push(builder.into());
-                    } else {
-                        return Err(Error::InvalidPresentationData(format!("range proof statement with id '{}' references claim '{}' which is not a number claim", id, r.claim)));
-                    }
+                        ClaimData::Number(n) => n.value,
+                        ClaimData::Date(d) => d.value,
+                        ClaimData::DateTime(d) => d.value,
+                        ClaimData::Decimal(d) => d.value,
+                        _ => return Err(Error::InvalidPresentationData(format!("range proof statement with id '{}' references claim '{}' which is not a number, date, datetime or decimal claim", id, r.claim))),
+                    };
+                    let builder = RangeBuilder::commit(r, commitment, value, &mut transcript)?;
+                    range_builders.push(builder.into());
                 } else {
                     return Err(Error::InvalidPresentationData(format!("range proof statement with id '{}' references a commitment '{}' that doesn't exist", id, r.reference_id)));
                 }
@@ -237,6 +375,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
             proofs,
             challenge,
             disclosed_messages,
+            absent_claims,
         };
         debug!(
             "Presentation: {}",
@@ -0,0 +1,64 @@
+use crate::knox::accumulator::vb20::{
+    Element, NonMembershipProof as Vb20NonMembershipProof, NonMembershipProofCommitting,
+    ProofParams,
+};
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::prelude::NonMembershipCredential;
+use crate::presentation::{PresentationBuilder, PresentationProofs};
+use crate::statement::NonMembershipStatement;
+use crate::CredxResult;
+use blsful::inner_types::Scalar;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+
+pub(crate) struct NonMembershipProofBuilder<'a> {
+    id: &'a String,
+    committing: NonMembershipProofCommitting,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for NonMembershipProofBuilder<'_> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let proof = self.committing.gen_proof(Element(challenge));
+        NonMembershipProof {
+            id: self.id.clone(),
+            proof,
+        }
+        .into()
+    }
+}
+
+impl<'a> NonMembershipProofBuilder<'a> {
+    /// Create a new accumulator set non-membership proof builder
+    pub fn commit(
+        statement: &'a NonMembershipStatement,
+        credential: &NonMembershipCredential,
+        message: Scalar,
+        blinder: Scalar,
+        nonce: &[u8],
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        let params = ProofParams::new(statement.verification_key, Some(nonce));
+        let committing = NonMembershipProofCommitting::new(
+            Element(message),
+            *credential,
+            params,
+            statement.verification_key,
+            Some(Element(blinder)),
+        );
+        params.add_to_transcript(transcript);
+        committing.get_bytes_for_challenge(statement.accumulator, transcript);
+        Ok(Self {
+            id: &statement.id,
+            committing,
+        })
+    }
+}
+
+/// A non-membership proof based on accumulators
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NonMembershipProof {
+    /// The statement identifier
+    pub id: String,
+    /// The non-membership proof
+    pub proof: Vb20NonMembershipProof,
+}
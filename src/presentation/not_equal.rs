@@ -0,0 +1,184 @@
+use crate::create_domain_proof_generator;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::{PresentationBuilder, PresentationProofs};
+use crate::statement::{NotEqualStatement, NotEqualTarget};
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::{group::Curve, Field};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// The generators for the claim commitments in not equal proofs
+pub(crate) fn not_equal_generators() -> (G1Projective, G1Projective) {
+    (
+        create_domain_proof_generator(b"credx claim not equal message generator"),
+        create_domain_proof_generator(b"credx claim not equal blinder generator"),
+    )
+}
+
+/// A not equal proof builder.
+///
+/// The claim `m` is committed to as `C = g * m + h * ρ` and linked to the signature
+/// proof using the same message blinder. The other claim `n` is committed to the same
+/// way, a public value `v` is used as `g * v`.
+/// The difference `D = g * (m - n) + h * δ` is shown to be nonzero by proving
+/// knowledge of `y = (m - n)^-1` and `t = -δ * y` for `g = D * y + h * t`.
+pub(crate) struct NotEqualBuilder<'a> {
+    statement: &'a NotEqualStatement,
+    commitment: NotEqualCommitment,
+    other: Option<NotEqualCommitment>,
+    y: Scalar,
+    t: Scalar,
+    r_y: Scalar,
+    r_t: Scalar,
+}
+
+struct NotEqualCommitment {
+    commitment: G1Projective,
+    blinder: Scalar,
+    r: Scalar,
+}
+
+impl NotEqualCommitment {
+    fn new(message: Scalar, b: Scalar, mut rng: impl RngCore + CryptoRng) -> (Self, G1Projective) {
+        let (g, h) = not_equal_generators();
+        let blinder = Scalar::random(&mut rng);
+        let r = Scalar::random(&mut rng);
+        let commitment = g * message + h * blinder;
+        let blind_commitment = g * b + h * r;
+        (
+            Self {
+                commitment,
+                blinder,
+                r,
+            },
+            blind_commitment,
+        )
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for NotEqualBuilder<'_> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let (other_commitment, other_blinder_proof) = match self.other {
+            Some(o) => (Some(o.commitment), Some(o.r + challenge * o.blinder)),
+            None => (None, None),
+        };
+        NotEqualProof {
+            id: self.statement.id.clone(),
+            commitment: self.commitment.commitment,
+            blinder_proof: self.commitment.r + challenge * self.commitment.blinder,
+            other_commitment,
+            other_blinder_proof,
+            inverse_proof: self.r_y + challenge * self.y,
+            inverse_blinder_proof: self.r_t + challenge * self.t,
+        }
+        .into()
+    }
+}
+
+impl<'a> NotEqualBuilder<'a> {
+    /// Create a new not equal proof builder.
+    /// `other` is the message and blinder of the other claim when comparing two claims
+    pub fn commit(
+        statement: &'a NotEqualStatement,
+        message: Scalar,
+        b: Scalar,
+        other: Option<(Scalar, Scalar)>,
+        mut rng: impl RngCore + CryptoRng,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        statement.validate()?;
+        let (g, h) = not_equal_generators();
+        let (commitment, blind_commitment) = NotEqualCommitment::new(message, b, &mut rng);
+
+        let (other, other_blind_commitment, other_message) = match (&statement.other, other) {
+            (NotEqualTarget::Value(value), None) => (None, None, value.to_scalar()),
+            (NotEqualTarget::Claim { .. }, Some((other_message, other_b))) => {
+                let (other, other_blind_commitment) =
+                    NotEqualCommitment::new(other_message, other_b, &mut rng);
+                (Some(other), Some(other_blind_commitment), other_message)
+            }
+            (_, _) => {
+                return Err(Error::InvalidPresentationData(format!(
+                    "not equal statement '{}' doesn't match the supplied claims",
+                    statement.id
+                )))
+            }
+        };
+
+        let y = Option::<Scalar>::from((message - other_message).invert()).ok_or(
+            Error::InvalidClaimData("not equal statement - claims are equal"),
+        )?;
+        let (difference, difference_blinder) = match &other {
+            Some(o) => (
+                commitment.commitment - o.commitment,
+                commitment.blinder - o.blinder,
+            ),
+            None => (
+                commitment.commitment - g * other_message,
+                commitment.blinder,
+            ),
+        };
+        let t = -difference_blinder * y;
+        let r_y = Scalar::random(&mut rng);
+        let r_t = Scalar::random(&mut rng);
+        let inverse_commitment = difference * r_y + h * r_t;
+
+        transcript.append_message(b"", statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            commitment.commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        if let (Some(o), Some(other_blind_commitment)) = (&other, other_blind_commitment) {
+            transcript.append_message(
+                b"other commitment",
+                o.commitment.to_affine().to_compressed().as_slice(),
+            );
+            transcript.append_message(
+                b"other blind commitment",
+                other_blind_commitment
+                    .to_affine()
+                    .to_compressed()
+                    .as_slice(),
+            );
+        }
+        transcript.append_message(
+            b"inverse commitment",
+            inverse_commitment.to_affine().to_compressed().as_slice(),
+        );
+        Ok(Self {
+            statement,
+            commitment,
+            other,
+            y,
+            t,
+            r_y,
+            r_t,
+        })
+    }
+}
+
+/// A proof that a claim is not equal to a value or another claim
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct NotEqualProof {
+    /// The statement identifier
+    pub id: String,
+    /// The claim commitment
+    pub commitment: G1Projective,
+    /// The schnorr commitment blinder proof
+    pub blinder_proof: Scalar,
+    /// The other claim commitment when comparing two claims
+    pub other_commitment: Option<G1Projective>,
+    /// The schnorr other commitment blinder proof when comparing two claims
+    pub other_blinder_proof: Option<Scalar>,
+    /// The schnorr proof for the inverse of the claim difference
+    pub inverse_proof: Scalar,
+    /// The schnorr proof for the inverse blinder
+    pub inverse_blinder_proof: Scalar,
+}
@@ -0,0 +1,133 @@
+use crate::claim::ClaimData;
+use crate::create_domain_proof_generator;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::{PresentationBuilder, PresentationProofs};
+use crate::statement::PresenceStatement;
+use crate::CredxResult;
+use blsful::inner_types::{G1Projective, Scalar};
+use elliptic_curve::{group::Curve, Field};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// The generators for the claim commitment in presence proofs
+pub(crate) fn presence_generators() -> (G1Projective, G1Projective) {
+    (
+        create_domain_proof_generator(b"credx claim presence message generator"),
+        create_domain_proof_generator(b"credx claim presence blinder generator"),
+    )
+}
+
+/// A presence proof builder.
+///
+/// The claim `m` is committed to as `C = g * m + h * ρ` and linked to the signature
+/// proof using the same message blinder.
+/// An absent claim is shown by proving knowledge of `ρ` for `C - g * a = h * ρ`
+/// where `a` is the absent claim scalar.
+/// A present claim is shown by proving knowledge of `y = (m - a)^-1` and `t = -ρ * y`
+/// for `g = (C - g * a) * y + h * t` which is only possible when `m != a`.
+pub(crate) struct PresenceBuilder<'a> {
+    statement: &'a PresenceStatement,
+    commitment: G1Projective,
+    blinder: Scalar,
+    r: Scalar,
+    inverse: Option<PresenceInverse>,
+}
+
+struct PresenceInverse {
+    y: Scalar,
+    t: Scalar,
+    r_y: Scalar,
+    r_t: Scalar,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for PresenceBuilder<'_> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let blinder_proof = self.r + challenge * self.blinder;
+        let (inverse_proof, inverse_blinder_proof) = match self.inverse {
+            Some(i) => (Some(i.r_y + challenge * i.y), Some(i.r_t + challenge * i.t)),
+            None => (None, None),
+        };
+        PresenceProof {
+            id: self.statement.id.clone(),
+            commitment: self.commitment,
+            blinder_proof,
+            inverse_proof,
+            inverse_blinder_proof,
+        }
+        .into()
+    }
+}
+
+impl<'a> PresenceBuilder<'a> {
+    /// Create a new presence proof builder
+    pub fn commit(
+        statement: &'a PresenceStatement,
+        message: Scalar,
+        b: Scalar,
+        mut rng: impl RngCore + CryptoRng,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        let (g, h) = presence_generators();
+        let absent = ClaimData::absent_scalar();
+        let blinder = Scalar::random(&mut rng);
+        let r = Scalar::random(&mut rng);
+        let commitment = g * message + h * blinder;
+        let blind_commitment = g * b + h * r;
+
+        let (inverse, presence_commitment) = if statement.present {
+            let y = Option::<Scalar>::from((message - absent).invert())
+                .ok_or(Error::InvalidClaimData("claim is absent"))?;
+            let t = -blinder * y;
+            let r_y = Scalar::random(&mut rng);
+            let r_t = Scalar::random(&mut rng);
+            let presence_commitment = (commitment - g * absent) * r_y + h * r_t;
+            (
+                Some(PresenceInverse { y, t, r_y, r_t }),
+                presence_commitment,
+            )
+        } else {
+            if message != absent {
+                return Err(Error::InvalidClaimData("claim is present"));
+            }
+            (None, h * r)
+        };
+
+        transcript.append_message(b"", statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"presence commitment",
+            presence_commitment.to_affine().to_compressed().as_slice(),
+        );
+        Ok(Self {
+            statement,
+            commitment,
+            blinder,
+            r,
+            inverse,
+        })
+    }
+}
+
+/// A proof that an optional claim is present or absent
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresenceProof {
+    /// The statement identifier
+    pub id: String,
+    /// The claim commitment
+    pub commitment: G1Projective,
+    /// The schnorr commitment blinder proof
+    pub blinder_proof: Scalar,
+    /// The schnorr proof for the inverse of the claim difference when present
+    pub inverse_proof: Option<Scalar>,
+    /// The schnorr proof for the inverse blinder when present
+    pub inverse_blinder_proof: Option<Scalar>,
+}
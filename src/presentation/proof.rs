@@ -1,9 +1,9 @@
-use super::SignatureProof;
+use super::{AnyOfProof, ClaimRangeProof, SignatureProof};
 use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use crate::presentation::verifiable_encryption_decryption::VerifiableEncryptionDecryptionProof;
 use crate::presentation::{
-    CommitmentProof, EqualityProof, MembershipProof, RangeProof, RevocationProof,
-    VerifiableEncryptionProof,
+    CommitmentProof, EqualityProof, MembershipProof, NonMembershipProof, NotEqualProof,
+    PresenceProof, RangeProof, RevocationProof, SuspensionProof, VerifiableEncryptionProof,
 };
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +30,22 @@ pub enum PresentationProofs<S: ShortGroupSignatureScheme> {
     Membership(Box<MembershipProof>),
     /// Verifiable Encryption Decryption Proofs
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionProof>),
+    /// Non-membership Proofs
+    NonMembership(Box<NonMembershipProof>),
+    /// Claim presence proofs
+    Presence(Box<PresenceProof>),
+    /// Accumulator set non-membership proof for suspension
+    Suspension(Box<SuspensionProof>),
+    /// Disjunctive proofs
+    #[serde(bound(
+        serialize = "AnyOfProof<S>: Serialize",
+        deserialize = "AnyOfProof<S>: Deserialize<'de>"
+    ))]
+    AnyOf(Box<AnyOfProof<S>>),
+    /// Inequality proofs
+    NotEqual(Box<NotEqualProof>),
+    /// Range proofs on signature claims
+    ClaimRange(Box<ClaimRangeProof>),
 }
 
 impl<S: ShortGroupSignatureScheme> From<SignatureProof<S>> for PresentationProofs<S> {
@@ -82,6 +98,42 @@ impl<S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionProof>
     }
 }
 
+impl<S: ShortGroupSignatureScheme> From<NonMembershipProof> for PresentationProofs<S> {
+    fn from(value: NonMembershipProof) -> Self {
+        Self::NonMembership(Box::new(value))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<PresenceProof> for PresentationProofs<S> {
+    fn from(value: PresenceProof) -> Self {
+        Self::Presence(Box::new(value))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<SuspensionProof> for PresentationProofs<S> {
+    fn from(value: SuspensionProof) -> Self {
+        Self::Suspension(Box::new(value))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<AnyOfProof<S>> for PresentationProofs<S> {
+    fn from(value: AnyOfProof<S>) -> Self {
+        Self::AnyOf(Box::new(value))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<NotEqualProof> for PresentationProofs<S> {
+    fn from(value: NotEqualProof) -> Self {
+        Self::NotEqual(Box::new(value))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<ClaimRangeProof> for PresentationProofs<S> {
+    fn from(value: ClaimRangeProof) -> Self {
+        Self::ClaimRange(Box::new(value))
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> PresentationProofs<S> {
     /// Get the underlying statement identifier
     pub fn id(&self) -> &String {
@@ -94,6 +146,12 @@ impl<S: ShortGroupSignatureScheme> PresentationProofs<S> {
             Self::Range(r) => &r.id,
             Self::Membership(m) => &m.id,
             Self::VerifiableEncryptionDecryption(v) => &v.id,
+            Self::NonMembership(n) => &n.id,
+            Self::Presence(p) => &p.id,
+            Self::Suspension(s) => &s.id,
+            Self::AnyOf(a) => &a.id,
+            Self::NotEqual(n) => &n.id,
+            Self::ClaimRange(c) => &c.id,
         }
     }
 }
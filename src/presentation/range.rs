@@ -4,7 +4,7 @@ use crate::presentation::{CommitmentBuilder, PresentationBuilder, PresentationPr
 use crate::statement::RangeStatement;
 use crate::utils::*;
 use crate::CredxResult;
-use blsful::inner_types::Scalar;
+use blsful::inner_types::{G1Projective, Scalar};
 use bulletproofs::RangeProof as RangeProofBulletproof;
 use elliptic_curve::group::Curve;
 use merlin::Transcript;
@@ -12,23 +12,35 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
 pub(crate) struct RangeBuilder<'a> {
-    statement: &'a RangeStatement,
-    commitment_builder: &'a CommitmentBuilder<'a>,
+    id: &'a String,
+    lower: Option<isize>,
+    upper: Option<isize>,
+    message_generator: G1Projective,
+    blinder_generator: G1Projective,
+    commitment: G1Projective,
+    blinder: Scalar,
     adjusted_lower: Option<u64>,
     adjusted_upper: Option<u64>,
 }
 
 impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for RangeBuilder<'_> {
     fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        self.gen_range_proof(challenge).into()
+    }
+}
+
+impl<'a> RangeBuilder<'a> {
+    /// Create the range proof for the committed claim
+    pub fn gen_range_proof(self, challenge: Scalar) -> RangeProof {
         let pedersen_gen = bulletproofs::PedersenGens {
-            B: self.commitment_builder.statement.message_generator,
-            B_blinding: self.commitment_builder.statement.blinder_generator,
+            B: self.message_generator,
+            B_blinding: self.blinder_generator,
         };
 
         let mut transcript = Transcript::new(b"credx range proof");
         transcript.append_message(b"challenge", &challenge.to_be_bytes());
 
-        let blinder = self.commitment_builder.b;
+        let blinder = self.blinder;
 
         match (self.adjusted_upper, self.adjusted_lower) {
             (Some(upper), Some(lower)) => {
@@ -45,23 +57,20 @@ impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for RangeBuilder<'_> {
 
                 debug_assert_eq!(
                     commitments[0],
-                    self.commitment_builder.commitment
-                        + self.commitment_builder.statement.message_generator
-                            * Scalar::from(
-                                u64::MAX - zero_center(*self.statement.upper.as_ref().unwrap())
-                            )
+                    self.commitment
+                        + self.message_generator
+                            * Scalar::from(u64::MAX - zero_center(*self.upper.as_ref().unwrap()))
                 );
                 debug_assert_eq!(
                     commitments[1],
-                    self.commitment_builder.commitment
-                        - self.commitment_builder.statement.message_generator
-                            * Scalar::from(zero_center(*self.statement.lower.as_ref().unwrap()))
+                    self.commitment
+                        - self.message_generator
+                            * Scalar::from(zero_center(*self.lower.as_ref().unwrap()))
                 );
                 RangeProof {
-                    id: self.statement.id.clone(),
+                    id: self.id.clone(),
                     proof,
                 }
-                .into()
             }
             (Some(upper), None) => {
                 let bulletproof_gens = bulletproofs::BulletproofGens::new(64, 1);
@@ -76,17 +85,14 @@ impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for RangeBuilder<'_> {
                 .unwrap();
                 debug_assert_eq!(
                     commitment,
-                    self.commitment_builder.commitment
-                        + self.commitment_builder.statement.message_generator
-                            * Scalar::from(
-                                u64::MAX - zero_center(*self.statement.upper.as_ref().unwrap())
-                            )
+                    self.commitment
+                        + self.message_generator
+                            * Scalar::from(u64::MAX - zero_center(*self.upper.as_ref().unwrap()))
                 );
                 RangeProof {
-                    id: self.statement.id.clone(),
+                    id: self.id.clone(),
                     proof,
                 }
-                .into()
             }
             (None, Some(lower)) => {
                 let bulletproof_gens = bulletproofs::BulletproofGens::new(64, 1);
@@ -101,27 +107,24 @@ impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for RangeBuilder<'_> {
                 .unwrap();
                 debug_assert_eq!(
                     commitment,
-                    self.commitment_builder.commitment
-                        - self.commitment_builder.statement.message_generator
-                            * Scalar::from(zero_center(*self.statement.lower.as_ref().unwrap()))
+                    self.commitment
+                        - self.message_generator
+                            * Scalar::from(zero_center(*self.lower.as_ref().unwrap()))
                 );
                 RangeProof {
-                    id: self.statement.id.clone(),
+                    id: self.id.clone(),
                     proof,
                 }
-                .into()
             }
             (None, None) => {
                 panic!("How did this happen?")
             }
         }
     }
-}
 
-impl<'a> RangeBuilder<'a> {
     pub fn commit(
         statement: &'a RangeStatement,
-        commitment_builder: &'a CommitmentBuilder<'a>,
+        commitment_builder: &CommitmentBuilder<'_>,
         message: isize,
         transcript: &mut Transcript,
     ) -> CredxResult<Self> {
@@ -131,43 +134,65 @@ impl<'a> RangeBuilder<'a> {
             // Not testing the same message from the same signature
             return Err(Error::InvalidPresentationData(format!("range proof statement with id '{}' is not proving the same claim found in the specified commitment statement with id '{}': range proof statement reference signature statement id '{}', commitment statement reference signature statement id '{}'", statement.id, commitment_builder.statement.id, statement.signature_id, commitment_builder.statement.reference_id)));
         }
+        Self::commit_bounds(
+            &statement.id,
+            statement.lower,
+            statement.upper,
+            commitment_builder.statement.message_generator,
+            commitment_builder.statement.blinder_generator,
+            commitment_builder.commitment,
+            commitment_builder.b,
+            message,
+            transcript,
+        )
+    }
+
+    /// Commit to `lower <= message <= upper` where `commitment` is
+    /// `message_generator * message + blinder_generator * blinder`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn commit_bounds(
+        id: &'a String,
+        lower: Option<isize>,
+        upper: Option<isize>,
+        message_generator: G1Projective,
+        blinder_generator: G1Projective,
+        commitment: G1Projective,
+        blinder: Scalar,
+        message: isize,
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
         {
-            let lower = match statement.lower {
+            let lower = match lower {
                 Some(lower) => lower,
                 None => isize::MIN,
             };
-            let upper = match statement.upper {
+            let upper = match upper {
                 Some(upper) => upper,
                 None => isize::MAX,
             };
             if message < lower || message > upper {
-                return Err(Error::InvalidPresentationData(format!("the claim value '{}' is outside the range proof statement '{}' bounds: min '{}' and max '{}'", message, statement.id, lower, upper)));
+                return Err(Error::InvalidPresentationData(format!("the claim value '{}' is outside the range proof statement '{}' bounds: min '{}' and max '{}'", message, id, lower, upper)));
             }
         }
-        transcript.append_message(b"", statement.id.as_bytes());
-        transcript.append_message(
-            b"used commitment",
-            &commitment_builder.commitment.to_affine().to_compressed(),
-        );
+        transcript.append_message(b"", id.as_bytes());
+        transcript.append_message(b"used commitment", &commitment.to_affine().to_compressed());
         transcript.append_u64(b"range proof bits", 64);
 
-        let blind = commitment_builder.statement.blinder_generator * commitment_builder.b;
+        let blind = blinder_generator * blinder;
         let mut l = None;
         let mut u = None;
         // negation zero centers in the positive range
-        match (statement.lower, statement.upper) {
+        match (lower, upper) {
             (Some(lower), Some(upper)) => {
                 let adjusted_lower = zero_center(message) - zero_center(lower);
                 let max_upper = u64::MAX - zero_center(upper);
                 let adjusted_upper = zero_center(message) + max_upper;
                 l = Some(adjusted_lower);
                 u = Some(adjusted_upper);
-                let adjusted_upper_commitment = commitment_builder.statement.message_generator
-                    * Scalar::from(adjusted_upper)
-                    + blind;
-                let adjusted_lower_commitment = commitment_builder.statement.message_generator
-                    * Scalar::from(adjusted_lower)
-                    + blind;
+                let adjusted_upper_commitment =
+                    message_generator * Scalar::from(adjusted_upper) + blind;
+                let adjusted_lower_commitment =
+                    message_generator * Scalar::from(adjusted_lower) + blind;
                 transcript.append_message(b"range proof version", &[3]);
                 transcript.append_message(
                     b"adjusted upper commitment",
@@ -182,9 +207,8 @@ impl<'a> RangeBuilder<'a> {
                 let max_upper = u64::MAX - zero_center(upper);
                 let adjusted_upper = zero_center(message) + max_upper;
                 u = Some(adjusted_upper);
-                let adjusted_upper_commitment = commitment_builder.statement.message_generator
-                    * Scalar::from(adjusted_upper)
-                    + blind;
+                let adjusted_upper_commitment =
+                    message_generator * Scalar::from(adjusted_upper) + blind;
                 transcript.append_message(b"range proof version", &[2]);
                 transcript.append_message(
                     b"adjusted upper commitment",
@@ -194,9 +218,8 @@ impl<'a> RangeBuilder<'a> {
             (Some(lower), None) => {
                 let adjusted_lower = zero_center(message) - zero_center(lower);
                 l = Some(adjusted_lower);
-                let adjusted_lower_commitment = commitment_builder.statement.message_generator
-                    * Scalar::from(adjusted_lower)
-                    + blind;
+                let adjusted_lower_commitment =
+                    message_generator * Scalar::from(adjusted_lower) + blind;
                 transcript.append_message(b"range proof version", &[1]);
                 transcript.append_message(
                     b"adjusted lower commitment",
@@ -204,12 +227,17 @@ impl<'a> RangeBuilder<'a> {
                 );
             }
             (None, None) => {
-                return Err(Error::InvalidPresentationData(format!("range proof has no lower or upper bounds when committing: range_proof_builder: {:?}", statement)));
+                return Err(Error::InvalidPresentationData(format!("range proof has no lower or upper bounds when committing: range_proof_statement: {}", id)));
             }
         }
         Ok(Self {
-            statement,
-            commitment_builder,
+            id,
+            lower,
+            upper,
+            message_generator,
+            blinder_generator,
+            commitment,
+            blinder,
             adjusted_lower: l,
             adjusted_upper: u,
         })
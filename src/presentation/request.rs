@@ -0,0 +1,171 @@
+use super::*;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use chrono::Utc;
+use std::collections::HashMap;
+
+/// A verifier's request for a presentation.
+///
+/// The holder answers with a [`PresentationResponse`] whose presentation
+/// is bound to the hash of this request.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresentationRequest<S: ShortGroupSignatureScheme> {
+    /// The verifier's id
+    pub verifier_id: String,
+    /// The proofs the verifier wants
+    #[serde(bound(
+        serialize = "PresentationSchema<S>: Serialize",
+        deserialize = "PresentationSchema<S>: Deserialize<'de>"
+    ))]
+    pub schema: PresentationSchema<S>,
+    /// The verifier issued nonce
+    pub nonce: Vec<u8>,
+    /// When the request expires as seconds since the unix epoch
+    pub expires: u64,
+    /// The ids of the issuers the verifier accepts, empty accepts any issuer
+    pub accepted_issuers: Vec<String>,
+}
+
+/// A holder's response to a [`PresentationRequest`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresentationResponse<S: ShortGroupSignatureScheme> {
+    /// The hash of the request this responds to
+    pub request_hash: [u8; 32],
+    /// The presentation
+    #[serde(bound(
+        serialize = "Presentation<S>: Serialize",
+        deserialize = "Presentation<S>: Deserialize<'de>"
+    ))]
+    pub presentation: Presentation<S>,
+}
+
+/// Tracks the request nonces verifiers have accepted so responses can't be replayed
+pub trait NonceStore {
+    /// Record `nonce` as used until `expires`.
+    /// Returns `false` if the nonce was already used.
+    fn insert(&mut self, nonce: &[u8], expires: u64) -> CredxResult<bool>;
+}
+
+/// A nonce store that keeps used nonces in memory until they expire
+#[derive(Clone, Debug, Default)]
+pub struct MemoryNonceStore {
+    used: HashMap<Vec<u8>, u64>,
+}
+
+impl NonceStore for MemoryNonceStore {
+    fn insert(&mut self, nonce: &[u8], expires: u64) -> CredxResult<bool> {
+        // Requests are rejected once expired so their nonces no longer need tracking
+        let now = Utc::now().timestamp() as u64;
+        self.used.retain(|_, e| *e >= now);
+        if self.used.contains_key(nonce) {
+            return Ok(false);
+        }
+        self.used.insert(nonce.to_vec(), expires);
+        Ok(true)
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationRequest<S> {
+    const NONCE_LENGTH: usize = 32;
+
+    /// Create a new request with a random nonce that expires in `ttl` seconds
+    pub fn new(verifier_id: &str, schema: &PresentationSchema<S>, ttl: u64) -> Self {
+        let mut nonce = vec![0u8; Self::NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        Self {
+            verifier_id: verifier_id.to_string(),
+            schema: schema.clone(),
+            nonce,
+            expires: (Utc::now().timestamp() as u64).saturating_add(ttl),
+            accepted_issuers: Vec::new(),
+        }
+    }
+
+    /// The hash that binds a response to this request
+    pub fn hash(&self) -> [u8; 32] {
+        let mut transcript = Transcript::new(b"credx presentation request");
+        transcript.append_message(b"verifier id", self.verifier_id.as_bytes());
+        transcript.append_message(b"nonce", &self.nonce);
+        transcript.append_message(b"expires", &Uint::from(self.expires).to_vec());
+        transcript.append_message(
+            b"accepted issuers length",
+            &Uint::from(self.accepted_issuers.len()).to_vec(),
+        );
+        for id in &self.accepted_issuers {
+            transcript.append_message(b"accepted issuer id", id.as_bytes());
+        }
+        self.schema.add_challenge_contribution(&mut transcript);
+        let mut hash = [0u8; 32];
+        transcript.challenge_bytes(b"presentation request hash", &mut hash);
+        hash
+    }
+
+    /// Create the response to this request
+    pub fn respond(
+        &self,
+        credentials: &IndexMap<String, PresentationCredential<S>>,
+    ) -> CredxResult<PresentationResponse<S>> {
+        self.check_fresh()?;
+        self.check_issuers()?;
+        let request_hash = self.hash();
+        let presentation = Presentation::create(credentials, &self.schema, &request_hash)?;
+        Ok(PresentationResponse {
+            request_hash,
+            presentation,
+        })
+    }
+
+    /// Verify the response answers this request, the request hasn't expired,
+    /// and the request nonce hasn't been used before
+    pub fn verify<N: NonceStore>(
+        &self,
+        response: &PresentationResponse<S>,
+        nonce_store: &mut N,
+    ) -> CredxResult<()> {
+        self.check_fresh()?;
+        self.check_issuers()?;
+        let request_hash = self.hash();
+        if response.request_hash != request_hash {
+            return Err(Error::InvalidPresentationData(
+                "response is not bound to this presentation request".to_string(),
+            ));
+        }
+        response.presentation.verify(&self.schema, &request_hash)?;
+        if !nonce_store.insert(&self.nonce, self.expires)? {
+            return Err(Error::InvalidPresentationData(
+                "presentation request nonce has already been used".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_fresh(&self) -> CredxResult<()> {
+        if (Utc::now().timestamp() as u64) > self.expires {
+            return Err(Error::InvalidPresentationData(
+                "presentation request has expired".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_issuers(&self) -> CredxResult<()> {
+        if self.accepted_issuers.is_empty() {
+            return Ok(());
+        }
+        for statement in self.schema.statements.values() {
+            let signature_statements = match statement {
+                Statements::Signature(ss) => vec![ss.as_ref()],
+                Statements::AnyOf(a) => a.branches.iter().map(|b| &b.signature).collect(),
+                _ => continue,
+            };
+            for ss in signature_statements {
+                if !self.accepted_issuers.contains(&ss.issuer.id) {
+                    return Err(Error::InvalidPresentationData(format!(
+                        "signature statement '{}' uses issuer '{}' which is not accepted",
+                        ss.id, ss.issuer.id
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
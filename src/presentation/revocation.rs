@@ -10,9 +10,11 @@ use crate::CredxResult;
 use blsful::inner_types::Scalar;
 use merlin::Transcript;
 use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
 
 pub(crate) struct RevocationProofBuilder<'a> {
     id: &'a String,
+    epoch: usize,
     committing: MembershipProofCommitting,
 }
 
@@ -21,6 +23,7 @@ impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for RevocationProofBui
         let proof = self.committing.gen_proof(Element(challenge));
         RevocationProof {
             id: self.id.clone(),
+            epoch: self.epoch,
             proof,
         }
         .into()
@@ -36,6 +39,9 @@ impl<'a> RevocationProofBuilder<'a> {
         nonce: &[u8],
         transcript: &mut Transcript,
     ) -> CredxResult<Self> {
+        // Fail early instead of making a proof the verifier won't accept
+        let epoch = credential.revocation_epoch;
+        statement.accumulator_for_epoch(epoch)?;
         let params = ProofParams::new(statement.verification_key, Some(nonce));
         let committing = MembershipProofCommitting::new(
             message,
@@ -43,10 +49,12 @@ impl<'a> RevocationProofBuilder<'a> {
             params,
             statement.verification_key,
         );
+        transcript.append_message(b"registry epoch", &Uint::from(epoch).to_vec());
         params.add_to_transcript(transcript);
         committing.get_bytes_for_challenge(transcript);
         Ok(Self {
             id: &statement.id,
+            epoch,
             committing,
         })
     }
@@ -57,6 +65,9 @@ impl<'a> RevocationProofBuilder<'a> {
 pub struct RevocationProof {
     /// The statement identifier
     pub id: String,
+    /// The registry epoch the proof was made against
+    #[serde(default)]
+    pub epoch: usize,
     /// The membership proof
     pub proof: MembershipProof,
 }
@@ -0,0 +1,255 @@
+use super::*;
+use crate::credential::CredentialBundle;
+use crate::statement::{discloses, SignatureStatement};
+use core::fmt::{self, Display, Formatter};
+
+/// Finds the wallet credentials that can satisfy the signature statements
+/// in a presentation schema.
+///
+/// Signature statements and the branches of any of statements are matched,
+/// credentials for membership statements are keyed by statement id
+/// and must be added by the caller.
+#[derive(Clone, Debug)]
+pub struct CredentialSelector<'a, S: ShortGroupSignatureScheme> {
+    wallet: &'a [CredentialBundle<S>],
+}
+
+/// A choice of wallet credential for each signature statement
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CredentialAssignment {
+    /// The index of the wallet credential for each signature statement id.
+    /// Any of statements have one entry for the branch signature statement
+    /// the credential satisfies.
+    #[serde(
+        serialize_with = "serialize_indexmap",
+        deserialize_with = "deserialize_indexmap"
+    )]
+    pub selections: IndexMap<String, usize>,
+}
+
+/// Why a wallet credential can't be used for a signature statement
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum SelectionReason {
+    /// The credential is from another issuer
+    WrongIssuer {
+        /// The issuer id in the statement
+        expected: String,
+        /// The issuer id of the credential
+        found: String,
+    },
+    /// The credential uses another credential schema
+    WrongSchema {
+        /// The schema id in the statement
+        expected: String,
+        /// The schema id of the credential
+        found: String,
+    },
+    /// The credential doesn't have a claim the statement reveals
+    MissingClaim {
+        /// The claim label
+        label: String,
+    },
+    /// The credential was signed with a key the issuer no longer accepts
+    RetiredKey {
+        /// The key epoch that signed the credential
+        epoch: usize,
+    },
+    /// The credential wasn't signed with the key epoch an any of branch proves with
+    WrongKeyEpoch {
+        /// The key epoch of the branch issuer
+        expected: usize,
+        /// The key epoch that signed the credential
+        found: usize,
+    },
+}
+
+impl Display for SelectionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongIssuer { expected, found } => {
+                write!(f, "expected issuer '{}' but found '{}'", expected, found)
+            }
+            Self::WrongSchema { expected, found } => {
+                write!(f, "expected schema '{}' but found '{}'", expected, found)
+            }
+            Self::MissingClaim { label } => write!(f, "claim '{}' is not in the schema", label),
+            Self::RetiredKey { epoch } => write!(f, "signed with retired key epoch {}", epoch),
+            Self::WrongKeyEpoch { expected, found } => {
+                write!(f, "expected key epoch {} but found {}", expected, found)
+            }
+        }
+    }
+}
+
+/// A signature statement that no wallet credential can satisfy
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SelectionFailure {
+    /// The signature statement id
+    pub statement_id: String,
+    /// Why each wallet credential was rejected, by wallet index,
+    /// once for every branch of an any of statement
+    pub rejected: Vec<(usize, SelectionReason)>,
+}
+
+impl Display for SelectionFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.rejected.is_empty() {
+            return write!(f, "statement '{}': the wallet is empty", self.statement_id);
+        }
+        write!(f, "statement '{}':", self.statement_id)?;
+        for (index, reason) in &self.rejected {
+            write!(f, " credential {} {};", index, reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// The signature statements that can't be satisfied
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SelectionReport {
+    /// Every failure in schema statement order
+    pub failures: Vec<SelectionFailure>,
+}
+
+impl Display for SelectionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut sep = "";
+        for failure in &self.failures {
+            write!(f, "{}{}", sep, failure)?;
+            sep = "\n";
+        }
+        Ok(())
+    }
+}
+
+impl<'a, S: ShortGroupSignatureScheme> CredentialSelector<'a, S> {
+    /// Create a new selector over the wallet credentials
+    pub fn new(wallet: &'a [CredentialBundle<S>]) -> Self {
+        Self { wallet }
+    }
+
+    /// The wallet credentials that can satisfy the signature statement
+    pub fn candidates(&self, statement: &SignatureStatement<S>) -> Vec<usize> {
+        (0..self.wallet.len())
+            .filter(|i| self.check(statement, &self.wallet[*i]).is_none())
+            .collect()
+    }
+
+    /// Every assignment of wallet credentials to the signature statements in `schema`.
+    ///
+    /// Fails with [`Error::CredentialSelection`] listing each statement
+    /// no wallet credential can satisfy.
+    pub fn select(&self, schema: &PresentationSchema<S>) -> CredxResult<Vec<CredentialAssignment>> {
+        let mut failures = Vec::new();
+        let mut candidates = Vec::new();
+        for statement in schema.statements.values() {
+            // Any of branches can only prove with the branch issuer's key epoch
+            let (id, signatures) = match statement {
+                Statements::Signature(ss) => (&ss.id, vec![(ss.as_ref(), None)]),
+                Statements::AnyOf(a) => (
+                    &a.id,
+                    a.branches
+                        .iter()
+                        .map(|b| (&b.signature, Some(b.key_epoch())))
+                        .collect(),
+                ),
+                _ => continue,
+            };
+            let mut found = Vec::new();
+            let mut rejected = Vec::new();
+            for (ss, key_epoch) in signatures {
+                for (i, bundle) in self.wallet.iter().enumerate() {
+                    let reason = self.check(ss, bundle).or_else(|| {
+                        key_epoch
+                            .filter(|e| *e != bundle.credential.key_epoch)
+                            .map(|expected| SelectionReason::WrongKeyEpoch {
+                                expected,
+                                found: bundle.credential.key_epoch,
+                            })
+                    });
+                    match reason {
+                        None => found.push((ss.id.clone(), i)),
+                        Some(reason) => rejected.push((i, reason)),
+                    }
+                }
+            }
+            if found.is_empty() {
+                failures.push(SelectionFailure {
+                    statement_id: id.clone(),
+                    rejected,
+                });
+            }
+            candidates.push(found);
+        }
+        if !failures.is_empty() {
+            return Err(Error::CredentialSelection(SelectionReport { failures }));
+        }
+
+        let mut assignments = vec![CredentialAssignment::default()];
+        for found in &candidates {
+            let mut next = Vec::with_capacity(assignments.len() * found.len());
+            for assignment in &assignments {
+                for (id, i) in found {
+                    let mut assignment = assignment.clone();
+                    assignment.selections.insert(id.clone(), *i);
+                    next.push(assignment);
+                }
+            }
+            assignments = next;
+        }
+        Ok(assignments)
+    }
+
+    /// The presentation credentials for `assignment`
+    pub fn credentials(
+        &self,
+        assignment: &CredentialAssignment,
+    ) -> IndexMap<String, PresentationCredential<S>> {
+        assignment
+            .selections
+            .iter()
+            .map(|(id, i)| (id.clone(), self.wallet[*i].credential.clone().into()))
+            .collect()
+    }
+
+    fn check(
+        &self,
+        statement: &SignatureStatement<S>,
+        bundle: &CredentialBundle<S>,
+    ) -> Option<SelectionReason> {
+        if bundle.issuer.id != statement.issuer.id {
+            return Some(SelectionReason::WrongIssuer {
+                expected: statement.issuer.id.clone(),
+                found: bundle.issuer.id.clone(),
+            });
+        }
+        if bundle.issuer.schema.id != statement.issuer.schema.id {
+            return Some(SelectionReason::WrongSchema {
+                expected: statement.issuer.schema.id.clone(),
+                found: bundle.issuer.schema.id.clone(),
+            });
+        }
+        if let Some(label) = statement.disclosed.iter().find(|d| {
+            !bundle
+                .issuer
+                .schema
+                .claims
+                .iter()
+                .any(|c| discloses(d, &c.label))
+        }) {
+            return Some(SelectionReason::MissingClaim {
+                label: label.clone(),
+            });
+        }
+        if statement
+            .issuer
+            .verifying_key_for_epoch(bundle.credential.key_epoch)
+            .is_err()
+        {
+            return Some(SelectionReason::RetiredKey {
+                epoch: bundle.credential.key_epoch,
+            });
+        }
+        None
+    }
+}
@@ -9,6 +9,7 @@ use blsful::inner_types::Scalar;
 use indexmap::IndexMap;
 use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
 
 /// A builder for creating signature presentations
 pub(crate) struct SignatureBuilder<'a, S: ShortGroupSignatureScheme> {
@@ -16,6 +17,8 @@ pub(crate) struct SignatureBuilder<'a, S: ShortGroupSignatureScheme> {
     id: &'a String,
     /// The messages that belong to this signature
     disclosed_messages: IndexMap<usize, Scalar>,
+    /// The issuer key epoch that signed the credential
+    key_epoch: usize,
     /// The signature proof of knowledge builder
     pok_sig: S::ProofOfSignatureKnowledgeContribution,
 }
@@ -23,13 +26,7 @@ pub(crate) struct SignatureBuilder<'a, S: ShortGroupSignatureScheme> {
 impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for SignatureBuilder<'_, S> {
     /// Finalize proofs
     fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
-        // PS signature generate_proof can't fail, okay to unwrap
-        SignatureProof {
-            id: self.id.clone(),
-            disclosed_messages: self.disclosed_messages,
-            pok: self.pok_sig.generate_proof(challenge).unwrap(),
-        }
-        .into()
+        self.gen_signature_proof(challenge).into()
     }
 }
 
@@ -38,13 +35,15 @@ impl<'a, S: ShortGroupSignatureScheme> SignatureBuilder<'a, S> {
     pub fn commit(
         statement: &'a SignatureStatement<S>,
         signature: &S::Signature,
+        key_epoch: usize,
         messages: &[ProofMessage<Scalar>],
         rng: impl RngCore + CryptoRng,
         transcript: &mut Transcript,
     ) -> CredxResult<Self> {
+        let verifying_key = statement.issuer.verifying_key_for_epoch(key_epoch)?;
         match S::ProofOfSignatureKnowledgeContribution::commit(
             signature,
-            &statement.issuer.verifying_key,
+            verifying_key,
             messages,
             rng,
         ) {
@@ -57,16 +56,29 @@ impl<'a, S: ShortGroupSignatureScheme> SignatureBuilder<'a, S> {
                     .map(|(i, m)| (i, m.get_message()))
                     .collect::<IndexMap<usize, Scalar>>();
 
+                transcript.append_message(b"issuer key epoch", &Uint::from(key_epoch).to_vec());
                 poksig.add_proof_contribution(transcript);
 
                 Ok(Self {
                     id: &statement.id,
                     disclosed_messages,
+                    key_epoch,
                     pok_sig: poksig,
                 })
             }
         }
     }
+
+    /// Finalize the signature proof
+    pub fn gen_signature_proof(self, challenge: Scalar) -> SignatureProof<S> {
+        // PS signature generate_proof can't fail, okay to unwrap
+        SignatureProof {
+            id: self.id.clone(),
+            disclosed_messages: self.disclosed_messages,
+            key_epoch: self.key_epoch,
+            pok: self.pok_sig.generate_proof(challenge).unwrap(),
+        }
+    }
 }
 
 /// A signature proof that can be presented
@@ -80,6 +92,9 @@ pub struct SignatureProof<S: ShortGroupSignatureScheme> {
         deserialize_with = "deserialize_indexmap"
     )]
     pub disclosed_messages: IndexMap<usize, Scalar>,
+    /// The issuer key epoch that signed the credential
+    #[serde(default)]
+    pub key_epoch: usize,
     /// The proof
     pub pok: S::ProofOfSignatureKnowledge,
 }
@@ -0,0 +1,71 @@
+use crate::credential::Credential;
+use crate::error::Error;
+use crate::knox::accumulator::vb20::{
+    Element, NonMembershipProof, NonMembershipProofCommitting, ProofParams,
+};
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::presentation::{PresentationBuilder, PresentationProofs};
+use crate::statement::SuspensionStatement;
+use crate::CredxResult;
+use blsful::inner_types::Scalar;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+
+pub(crate) struct SuspensionProofBuilder<'a> {
+    statement: &'a SuspensionStatement,
+    committing: NonMembershipProofCommitting,
+}
+
+impl<S: ShortGroupSignatureScheme> PresentationBuilder<S> for SuspensionProofBuilder<'_> {
+    fn gen_proof(self, challenge: Scalar) -> PresentationProofs<S> {
+        let proof = self.committing.gen_proof(Element(challenge));
+        SuspensionProof {
+            id: self.statement.id.clone(),
+            proof,
+        }
+        .into()
+    }
+}
+
+impl<'a> SuspensionProofBuilder<'a> {
+    /// Create a new accumulator set non-membership proof builder
+    pub fn commit<S: ShortGroupSignatureScheme>(
+        statement: &'a SuspensionStatement,
+        credential: &Credential<S>,
+        message: Scalar,
+        blinder: Scalar,
+        nonce: &[u8],
+        transcript: &mut Transcript,
+    ) -> CredxResult<Self> {
+        let handle = credential
+            .suspension_handle
+            .ok_or(Error::InvalidPresentationData(format!(
+                "suspension statement '{}' requires a credential with a suspension handle",
+                statement.id
+            )))?;
+        let params = ProofParams::new(statement.verification_key, Some(nonce));
+        // The signature proof's blinder binds this proof to the same claim
+        let committing = NonMembershipProofCommitting::new(
+            Element(message),
+            handle,
+            params,
+            statement.verification_key,
+            Some(Element(blinder)),
+        );
+        params.add_to_transcript(transcript);
+        committing.get_bytes_for_challenge(statement.accumulator, transcript);
+        Ok(Self {
+            statement,
+            committing,
+        })
+    }
+}
+
+/// A non-membership proof based on accumulators
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SuspensionProof {
+    /// The statement identifier
+    pub id: String,
+    /// The non-membership proof
+    pub proof: NonMembershipProof,
+}
@@ -1,5 +1,6 @@
 use super::*;
 use crate::knox::short_group_sig_core::short_group_traits::ProofOfSignatureKnowledge;
+use crate::statement::NotEqualTarget;
 use std::collections::BTreeMap;
 
 impl<S: ShortGroupSignatureScheme> Presentation<S> {
@@ -21,6 +22,25 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                         &self.disclosed_messages[&ss.id],
                         &mut transcript,
                     );
+                    if let Some(absent) = self.absent_claims.get(&ss.id) {
+                        let absent_scalar = ClaimData::absent_scalar();
+                        for label in absent {
+                            let index = ss.issuer.schema.claim_indices.get_index_of(label);
+                            if index.and_then(|i| proof.disclosed_messages.get(&i))
+                                != Some(&absent_scalar)
+                            {
+                                return Err(Error::InvalidPresentationData(format!(
+                                    "absent claim '{}' is not disclosed as absent in signature proof '{}'",
+                                    label, ss.id
+                                )));
+                            }
+                        }
+                        Self::add_absent_claims_challenge_contribution(
+                            &ss.id,
+                            absent,
+                            &mut transcript,
+                        );
+                    }
                     let verifier = SignatureVerifier::new(ss, proof);
                     verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
                     verifiers.push(verifier.into());
@@ -39,6 +59,10 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
         for (id, pred_statement) in &predicate_statements {
             match (pred_statement, self.proofs.get(*id)) {
                 (Statements::Revocation(aa), Some(PresentationProofs::Revocation(proof))) => {
+                    if let Some(Statements::Signature(ss)) = schema.statements.get(&aa.reference_id)
+                    {
+                        aa.check_signed_state_issuer(&ss.issuer)?;
+                    }
                     let hidden_messages =
                         self.get_sig_hidden_message_proofs(schema, &aa.reference_id)?;
                     let message_proof = hidden_messages
@@ -48,6 +72,16 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
                     verifiers.push(verifier.into());
                 }
+                (Statements::Suspension(ss), Some(PresentationProofs::Suspension(proof))) => {
+                    let hidden_messages =
+                        self.get_sig_hidden_message_proofs(schema, &ss.reference_id)?;
+                    let message_proof = hidden_messages
+                        .get(&ss.claim)
+                        .ok_or(Error::InvalidPresentationData(format!("suspension statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", ss.id, ss.claim)))?;
+                    let verifier = SuspensionVerifier::new(ss, proof, nonce, *message_proof);
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
                 (Statements::Membership(mm), Some(PresentationProofs::Membership(proof))) => {
                     let hidden_messages =
                         self.get_sig_hidden_message_proofs(schema, &mm.reference_id)?;
@@ -58,6 +92,16 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
                     verifiers.push(verifier.into());
                 }
+                (Statements::NonMembership(nn), Some(PresentationProofs::NonMembership(proof))) => {
+                    let hidden_messages =
+                        self.get_sig_hidden_message_proofs(schema, &nn.reference_id)?;
+                    let message_proof = hidden_messages
+                        .get(&nn.claim)
+                        .ok_or(Error::InvalidPresentationData(format!("non-membership statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", nn.id, nn.claim)))?;
+                    let verifier = NonMembershipVerifier::new(nn, proof, nonce, *message_proof);
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
                 (Statements::Equality(statement), Some(PresentationProofs::Equality(_))) => {
                     let verifier = EqualityVerifier {
                         statement,
@@ -101,6 +145,20 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
                     verifiers.push(verifier.into());
                 }
+                (Statements::Presence(statement), Some(PresentationProofs::Presence(proof))) => {
+                    let hidden_messages =
+                        self.get_sig_hidden_message_proofs(schema, &statement.reference_id)?;
+                    let message_proof = hidden_messages
+                        .get(&statement.claim)
+                        .ok_or(Error::InvalidPresentationData(format!("presence statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", statement.id, statement.claim)))?;
+                    let verifier = PresenceVerifier {
+                        statement,
+                        proof,
+                        message_proof: *message_proof,
+                    };
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
                 (Statements::Range(statement), Some(PresentationProofs::Range(proof))) => {
                     let cstmt = predicate_statements
                         .get(&statement.reference_id)
@@ -143,6 +201,66 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                     verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
                     verifiers.push(verifier.into());
                 }
+                (Statements::NotEqual(statement), Some(PresentationProofs::NotEqual(proof))) => {
+                    let hidden_messages =
+                        self.get_sig_hidden_message_proofs(schema, &statement.reference_id)?;
+                    let message_proof = hidden_messages
+                        .get(&statement.claim)
+                        .ok_or(Error::InvalidPresentationData(format!("not equal statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", statement.id, statement.claim)))?;
+                    let other_message_proof = match &statement.other {
+                        NotEqualTarget::Value(_) => None,
+                        NotEqualTarget::Claim {
+                            reference_id,
+                            claim,
+                        } => {
+                            let hidden_messages =
+                                self.get_sig_hidden_message_proofs(schema, reference_id)?;
+                            let other_message_proof = hidden_messages
+                                .get(claim)
+                                .ok_or(Error::InvalidPresentationData(format!("not equal statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", statement.id, claim)))?;
+                            Some(*other_message_proof)
+                        }
+                    };
+                    let verifier = NotEqualVerifier {
+                        statement,
+                        proof,
+                        message_proof: *message_proof,
+                        other_message_proof,
+                    };
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
+                (
+                    Statements::ClaimRange(statement),
+                    Some(PresentationProofs::ClaimRange(proof)),
+                ) => {
+                    let signature = match signature_statements.get(&statement.signature_id) {
+                        Some(Statements::Signature(ss)) => ss,
+                        _ => return Err(Error::InvalidPresentationData(format!("claim range statement with id '{}' references a signature statement with id '{}' but no signature statement has that id.", statement.id, statement.signature_id))),
+                    };
+                    let claim_index = statement.claim_index(signature)?;
+                    let hidden_messages =
+                        self.get_sig_hidden_message_proofs(schema, &statement.signature_id)?;
+                    let message_proof = hidden_messages
+                        .get(&claim_index)
+                        .ok_or(Error::InvalidPresentationData(format!("claim range statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", statement.id, statement.claim)))?;
+                    let verifier = ClaimRangeVerifier {
+                        statement,
+                        proof,
+                        message_proof: *message_proof,
+                    };
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
+                (Statements::AnyOf(statement), Some(PresentationProofs::AnyOf(proof))) => {
+                    let verifier = AnyOfVerifier {
+                        statement,
+                        proof,
+                        nonce,
+                    };
+                    verifier.add_challenge_contribution(self.challenge, &mut transcript)?;
+                    verifiers.push(verifier.into());
+                }
                 (_, _) => {
                     return Err(Error::InvalidPresentationData(format!(
                         "an unknown predicate statement was found in the presentation: {:?}",
@@ -198,7 +316,7 @@ impl<S: ShortGroupSignatureScheme> Presentation<S> {
                         let hidden_messages = s
                             .pok
                             .get_hidden_message_proofs(
-                                &sig_st.issuer.verifying_key,
+                                sig_st.issuer.verifying_key_for_epoch(s.key_epoch)?,
                                 disclosed_messages.as_slice(),
                             )?;
                         Ok(hidden_messages)
@@ -1,62 +1,215 @@
+mod refresh;
+mod signed_state;
+mod store;
+mod suspension;
+
+pub use refresh::*;
+pub use signed_state::*;
+pub use store::*;
+pub use suspension::*;
+
 use crate::error::Error;
-use crate::knox::accumulator::vb20::{Accumulator, Element, SecretKey};
-use crate::{utils::*, CredxResult};
-use indexmap::IndexSet;
+use crate::knox::accumulator::vb20::{
+    combine_deltas, Accumulator, Coefficient, Element, SecretKey,
+};
+use crate::CredxResult;
+use chrono::Utc;
 use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 
 /// A revocation registry for credentials
+///
+/// The elements and history are kept in a [`RegistryStore`],
+/// in memory by default.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct RevocationRegistry {
+pub struct RevocationRegistry<R = MemoryRegistryStore> {
+    /// The registry value
+    pub value: Accumulator,
+    /// The number of updates applied to the registry value
+    #[serde(default)]
+    pub epoch: usize,
+    /// The elements and history of the registry
+    #[serde(flatten)]
+    pub store: R,
+}
+
+/// The revocation registry value at an epoch
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEpoch {
+    /// The registry epoch
+    pub epoch: usize,
+    /// When the epoch started as seconds since the unix epoch,
+    /// zero for the initial epoch so a seeded registry is reproducible
+    pub timestamp: u64,
     /// The registry value
     pub value: Accumulator,
-    /// All the elements ever included in the revocation registry
-    /// Includes the index to help with ordering
-    #[serde(
-        serialize_with = "serialize_indexset",
-        deserialize_with = "deserialize_indexset"
-    )]
-    pub elements: IndexSet<String>,
-    /// The current active set
-    #[serde(
-        serialize_with = "serialize_indexset",
-        deserialize_with = "deserialize_indexset"
-    )]
-    pub active: IndexSet<String>,
+}
+
+impl From<&RegistryUpdate> for RegistryEpoch {
+    fn from(u: &RegistryUpdate) -> Self {
+        Self {
+            epoch: u.epoch,
+            timestamp: u.timestamp,
+            value: u.value,
+        }
+    }
+}
+
+/// A change to a revocation registry value.
+///
+/// Holders apply updates in epoch order to bring their
+/// revocation handles up to date without contacting the issuer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryUpdate {
+    /// The registry epoch after this update
+    pub epoch: usize,
+    /// When the update was made as seconds since the unix epoch
+    #[serde(default)]
+    pub timestamp: u64,
+    /// The registry value after this update
+    pub value: Accumulator,
+    /// The elements added to the registry value
+    pub additions: Vec<Element>,
+    /// The elements removed from the registry value
+    pub deletions: Vec<Element>,
+    /// The coefficients for updating revocation handles
+    pub coefficients: Vec<Coefficient>,
+}
+
+impl RegistryUpdate {
+    /// Combine consecutive updates into one update that takes a revocation handle
+    /// from the epoch before the first update to the epoch of the last
+    pub fn combine(updates: &[RegistryUpdate]) -> CredxResult<Self> {
+        let latest = updates.last().ok_or(Error::InvalidRevocationHandleUpdate)?;
+        if updates.windows(2).any(|w| w[1].epoch != w[0].epoch + 1) {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        let deltas = updates
+            .iter()
+            .map(|u| (&u.additions, &u.deletions, &u.coefficients))
+            .collect::<Vec<_>>();
+        let (additions, deletions, coefficients) = combine_deltas(&deltas);
+        Ok(Self {
+            epoch: latest.epoch,
+            timestamp: latest.timestamp,
+            value: latest.value,
+            additions,
+            deletions,
+            coefficients,
+        })
+    }
 }
 
 impl RevocationRegistry {
-    /// Create a new revocation registry with an initial set of elements to `count`
+    /// Create a new revocation registry with no elements kept in memory
     pub fn new(rng: impl RngCore + CryptoRng) -> Self {
+        Self::with_store(MemoryRegistryStore::default(), rng)
+            .expect("in memory registry store doesn't fail")
+    }
+}
+
+impl<R: RegistryStore> RevocationRegistry<R> {
+    /// Create a new revocation registry kept in `store`.
+    /// If the store already has a history the latest registry value is used
+    /// and `rng` is ignored.
+    pub fn with_store(mut store: R, rng: impl RngCore + CryptoRng) -> CredxResult<Self> {
+        if let Some(latest) = store.history().last() {
+            return Ok(Self {
+                value: latest.value,
+                epoch: latest.epoch,
+                store,
+            });
+        }
         let value = Accumulator::random(rng);
-        Self {
-            active: IndexSet::new(),
-            elements: IndexSet::new(),
+        store.push_epoch(RegistryEpoch {
+            epoch: 0,
+            timestamp: 0,
             value,
-        }
+        })?;
+        Ok(Self {
+            value,
+            epoch: 0,
+            store,
+        })
     }
 
-    /// Remove the specified elements from the registry
-    pub fn revoke(&mut self, sk: &SecretKey, elements: &[String]) -> CredxResult<()> {
-        let mut removals = Vec::new();
-        for e in elements {
-            if !self.active.shift_remove(e) {
-                return Err(Error::InvalidRevocationRegistryRevokeOperation);
-            }
-            removals.push(Element::hash(e.as_bytes()));
-        }
+    /// Is the element known to the registry
+    pub fn contains(&self, element: &str) -> bool {
+        self.store.contains(element)
+    }
 
-        self.value.remove_elements_assign(sk, removals.as_slice());
+    /// Is the element in the active set
+    pub fn is_active(&self, element: &str) -> bool {
+        self.store.is_active(element)
+    }
 
-        Ok(())
+    /// Every registry value in epoch order
+    pub fn history(&self) -> &[RegistryEpoch] {
+        self.store.history()
     }
 
-    /// Add the elements to the registry
-    pub fn add(&mut self, elements: &[String]) {
-        for e in elements {
-            if self.elements.insert(e.clone()) {
-                self.active.insert(e.clone());
-            }
+    /// The registry value at `epoch`
+    pub fn value_at(&self, epoch: usize) -> Option<&RegistryEpoch> {
+        self.history().iter().find(|e| e.epoch == epoch)
+    }
+
+    /// The registry values that were still current no more than `max_age` seconds ago
+    pub fn recent(&self, max_age: u64) -> &[RegistryEpoch] {
+        self.recent_as_of(max_age, Utc::now().timestamp() as u64)
+    }
+
+    /// The registry values that were still current no more than `max_age` seconds
+    /// before `as_of`, e.g. when auditing past presentations
+    pub fn recent_as_of(&self, max_age: u64, as_of: u64) -> &[RegistryEpoch] {
+        let history = self.history();
+        let start = history
+            .windows(2)
+            .take_while(|w| w[1].timestamp.saturating_add(max_age) < as_of)
+            .count();
+        &history[start..]
+    }
+
+    /// Remove the specified elements from the registry
+    /// and return the update for holders
+    pub fn revoke(&mut self, sk: &SecretKey, elements: &[String]) -> CredxResult<RegistryUpdate> {
+        if elements
+            .iter()
+            .enumerate()
+            .any(|(i, e)| !self.store.is_active(e) || elements[..i].contains(e))
+        {
+            return Err(Error::InvalidRevocationRegistryRevokeOperation);
         }
+        let additions = Vec::new();
+        let deletions = elements
+            .iter()
+            .map(|e| Element::hash(e.as_bytes()))
+            .collect::<Vec<_>>();
+        let mut value = self.value;
+        let coefficients = value.update_assign(sk, &additions, &deletions);
+        let epoch = self.epoch + 1;
+        let timestamp = Utc::now().timestamp() as u64;
+        self.store.revoke(
+            elements,
+            RegistryEpoch {
+                epoch,
+                timestamp,
+                value,
+            },
+        )?;
+        self.value = value;
+        self.epoch = epoch;
+        Ok(RegistryUpdate {
+            epoch,
+            timestamp,
+            value,
+            additions,
+            deletions,
+            coefficients,
+        })
+    }
+
+    /// Add the elements to the registry
+    pub fn add(&mut self, elements: &[String]) -> CredxResult<()> {
+        self.store.add(elements)
     }
 }
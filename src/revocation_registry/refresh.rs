@@ -0,0 +1,168 @@
+use super::{RegistryEpoch, RegistryUpdate, SignedAccumulatorState};
+use crate::error::Error;
+use crate::CredxResult;
+use blsful::{Bls12381G2Impl, PublicKey};
+use serde::{Deserialize, Serialize};
+use std::collections::{btree_map::Entry, BTreeMap};
+
+/// A request for the revocation registry updates after `epoch`.
+///
+/// The request doesn't identify the holder or the credential,
+/// every holder with a revocation handle at `epoch` sends the same request
+/// and applies the same updates to their own handle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WitnessRefreshRequest {
+    /// The registry epoch of the holder's revocation handle
+    pub epoch: usize,
+}
+
+/// The revocation registry update needed to refresh a revocation handle
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WitnessRefreshResponse {
+    /// The requested registry epoch
+    pub epoch: usize,
+    /// The latest registry value signed by the issuer
+    pub latest: SignedAccumulatorState,
+    /// The updates after `epoch` combined into one batch update,
+    /// [`None`] when `epoch` is the latest
+    pub update: Option<RegistryUpdate>,
+}
+
+impl WitnessRefreshResponse {
+    /// Check the latest registry value is signed by the issuer of
+    /// `registry_id` and the update ends at that value.
+    ///
+    /// The update itself isn't signed, a handle refreshed with it
+    /// must still be checked against the latest registry value.
+    pub fn verify(
+        &self,
+        registry_id: &str,
+        verifying_key: &PublicKey<Bls12381G2Impl>,
+    ) -> CredxResult<()> {
+        if self.latest.registry_id != registry_id {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        self.latest.verify(verifying_key)?;
+        let consistent = match &self.update {
+            Some(update) => {
+                self.epoch < update.epoch
+                    && update.epoch == self.latest.epoch
+                    && update.value == self.latest.value
+            }
+            None => self.epoch == self.latest.epoch,
+        };
+        if consistent {
+            Ok(())
+        } else {
+            Err(Error::InvalidRevocationHandleUpdate)
+        }
+    }
+}
+
+/// A service that hands out revocation registry updates so holders
+/// can refresh their revocation handles without the issuer secret key
+pub trait WitnessRefreshService {
+    /// The update after the requested epoch
+    fn refresh(&self, request: &WitnessRefreshRequest) -> CredxResult<WitnessRefreshResponse>;
+
+    /// The updates for many requests at once
+    fn refresh_batch(
+        &self,
+        requests: &[WitnessRefreshRequest],
+    ) -> CredxResult<Vec<WitnessRefreshResponse>> {
+        requests.iter().map(|r| self.refresh(r)).collect()
+    }
+}
+
+/// An in-memory witness refresh service that stands in for a remote server.
+///
+/// The issuer publishes every registry update with the signed registry value.
+/// The server doesn't need any issuer secret, it combines the published
+/// update polynomials from the requested epoch into one batch update
+/// that every holder at that epoch applies to their own handle.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LocalWitnessRefreshServer {
+    /// The registry value before any updates
+    pub genesis: RegistryEpoch,
+    /// Every published update in epoch order
+    pub updates: Vec<RegistryUpdate>,
+    /// The latest registry value signed by the issuer
+    pub signed_state: SignedAccumulatorState,
+}
+
+impl LocalWitnessRefreshServer {
+    /// Create a new server starting at the registry value `genesis`
+    /// with the issuer's signature on it
+    pub fn new(genesis: RegistryEpoch, signed_state: SignedAccumulatorState) -> CredxResult<Self> {
+        if signed_state.epoch != genesis.epoch || signed_state.value != genesis.value {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        Ok(Self {
+            genesis,
+            updates: Vec::new(),
+            signed_state,
+        })
+    }
+
+    /// Publish the next registry update with the issuer's signature on the new value
+    pub fn publish(
+        &mut self,
+        update: RegistryUpdate,
+        signed_state: SignedAccumulatorState,
+    ) -> CredxResult<()> {
+        if update.epoch != self.latest().epoch + 1
+            || signed_state.epoch != update.epoch
+            || signed_state.value != update.value
+        {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        self.updates.push(update);
+        self.signed_state = signed_state;
+        Ok(())
+    }
+
+    /// The latest registry value
+    pub fn latest(&self) -> RegistryEpoch {
+        self.updates
+            .last()
+            .map(RegistryEpoch::from)
+            .unwrap_or(self.genesis)
+    }
+}
+
+impl WitnessRefreshService for LocalWitnessRefreshServer {
+    fn refresh(&self, request: &WitnessRefreshRequest) -> CredxResult<WitnessRefreshResponse> {
+        let latest = self.latest();
+        if request.epoch < self.genesis.epoch || request.epoch > latest.epoch {
+            return Err(Error::InvalidRevocationHandleUpdate);
+        }
+        let pending = &self.updates[request.epoch - self.genesis.epoch..];
+        let update = if pending.is_empty() {
+            None
+        } else {
+            Some(RegistryUpdate::combine(pending)?)
+        };
+        Ok(WitnessRefreshResponse {
+            epoch: request.epoch,
+            latest: self.signed_state.clone(),
+            update,
+        })
+    }
+
+    /// Holders at the same epoch share one combined update
+    fn refresh_batch(
+        &self,
+        requests: &[WitnessRefreshRequest],
+    ) -> CredxResult<Vec<WitnessRefreshResponse>> {
+        let mut responses = BTreeMap::new();
+        requests
+            .iter()
+            .map(|r| {
+                if let Entry::Vacant(e) = responses.entry(r.epoch) {
+                    e.insert(self.refresh(r)?);
+                }
+                Ok(responses[&r.epoch].clone())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::error::Error;
+use crate::knox::accumulator::vb20::Accumulator;
+use crate::CredxResult;
+use blsful::*;
+use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
+
+/// A registry value at an epoch signed by the issuer.
+///
+/// Verifiers check the signature before accepting the value
+/// so holders and third parties can't substitute their own.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedAccumulatorState {
+    /// The id of the registry, the issuer id for revocation registries
+    pub registry_id: String,
+    /// The registry epoch
+    pub epoch: usize,
+    /// The registry value
+    pub value: Accumulator,
+    /// The issuer's signature over the id, epoch and value
+    pub signature: Signature<Bls12381G2Impl>,
+}
+
+impl SignedAccumulatorState {
+    const DST: &'static [u8] = b"CREDX-SIGNED-ACCUMULATOR-STATE-";
+
+    /// Sign the registry `value` at `epoch`
+    pub fn new(
+        registry_id: &str,
+        epoch: usize,
+        value: Accumulator,
+        signing_key: &SecretKey<Bls12381G2Impl>,
+    ) -> CredxResult<Self> {
+        let signature = signing_key
+            .sign(
+                SignatureSchemes::ProofOfPossession,
+                &Self::message(registry_id, epoch, value),
+            )
+            .map_err(|_| Error::InvalidSigningOperation)?;
+        Ok(Self {
+            registry_id: registry_id.to_string(),
+            epoch,
+            value,
+            signature,
+        })
+    }
+
+    /// Check the signature was made by `verifying_key`
+    pub fn verify(&self, verifying_key: &PublicKey<Bls12381G2Impl>) -> CredxResult<()> {
+        self.signature
+            .verify(
+                verifying_key,
+                Self::message(&self.registry_id, self.epoch, self.value),
+            )
+            .map_err(|_| Error::InvalidAccumulatorStateSignature)
+    }
+
+    /// The byte representation of this signed state
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Self::message(&self.registry_id, self.epoch, self.value);
+        bytes.extend_from_slice(&Vec::<u8>::from(&self.signature));
+        bytes
+    }
+
+    fn message(registry_id: &str, epoch: usize, value: Accumulator) -> Vec<u8> {
+        let mut message = Self::DST.to_vec();
+        message.extend_from_slice(&Uint::from(registry_id.len()).to_vec());
+        message.extend_from_slice(registry_id.as_bytes());
+        message.extend_from_slice(&Uint::from(epoch).to_vec());
+        message.extend_from_slice(value.to_bytes().as_ref());
+        message
+    }
+}
@@ -0,0 +1,266 @@
+use super::RegistryEpoch;
+use crate::error::Error;
+use crate::{utils::*, CredxResult};
+use indexmap::IndexSet;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Storage for the elements and history of a revocation registry.
+///
+/// The registry value and epoch are kept by [`super::RevocationRegistry`],
+/// the store keeps everything that grows with the number of credentials.
+pub trait RegistryStore {
+    /// Is the element known to the registry
+    fn contains(&self, element: &str) -> bool;
+    /// Is the element in the active set
+    fn is_active(&self, element: &str) -> bool;
+    /// The number of elements ever added
+    fn len(&self) -> usize;
+    /// The number of elements in the active set
+    fn active_len(&self) -> usize;
+    /// Does the store have no elements
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Add new elements to the active set, elements that are already known are skipped
+    fn add(&mut self, elements: &[String]) -> CredxResult<()>;
+    /// Remove elements from the active set and append the registry value
+    /// at the new epoch as one change, so a crash can't leave
+    /// the active set and the history out of step
+    fn revoke(&mut self, elements: &[String], epoch: RegistryEpoch) -> CredxResult<()>;
+    /// Every registry value in epoch order
+    fn history(&self) -> &[RegistryEpoch];
+    /// Append the registry value at a new epoch
+    fn push_epoch(&mut self, epoch: RegistryEpoch) -> CredxResult<()>;
+}
+
+/// A registry store that keeps everything in memory
+/// and is serialized with the registry
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MemoryRegistryStore {
+    /// All the elements ever included in the revocation registry
+    /// Includes the index to help with ordering
+    #[serde(
+        serialize_with = "serialize_indexset",
+        deserialize_with = "deserialize_indexset"
+    )]
+    pub elements: IndexSet<String>,
+    /// The current active set
+    #[serde(
+        serialize_with = "serialize_indexset",
+        deserialize_with = "deserialize_indexset"
+    )]
+    pub active: IndexSet<String>,
+    /// Every registry value in epoch order
+    #[serde(default)]
+    pub history: Vec<RegistryEpoch>,
+}
+
+impl RegistryStore for MemoryRegistryStore {
+    fn contains(&self, element: &str) -> bool {
+        self.elements.contains(element)
+    }
+
+    fn is_active(&self, element: &str) -> bool {
+        self.active.contains(element)
+    }
+
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    fn active_len(&self) -> usize {
+        self.active.len()
+    }
+
+    fn add(&mut self, elements: &[String]) -> CredxResult<()> {
+        for e in elements {
+            if self.elements.insert(e.clone()) {
+                self.active.insert(e.clone());
+            }
+        }
+        Ok(())
+    }
+
+    fn revoke(&mut self, elements: &[String], epoch: RegistryEpoch) -> CredxResult<()> {
+        for e in elements {
+            self.active.shift_remove(e);
+        }
+        self.history.push(epoch);
+        Ok(())
+    }
+
+    fn history(&self) -> &[RegistryEpoch] {
+        &self.history
+    }
+
+    fn push_epoch(&mut self, epoch: RegistryEpoch) -> CredxResult<()> {
+        self.history.push(epoch);
+        Ok(())
+    }
+}
+
+/// A registry store backed by an append-only log file.
+///
+/// Every change is appended to the log as one line and synced to disk
+/// before it's applied so a change never rewrites what is already stored.
+/// Opening the store replays the log. A trailing line without a newline
+/// is a change that was torn by a crash and is truncated away.
+/// Only the file path is serialized, deserializing reopens the log.
+#[derive(Debug)]
+pub struct FileRegistryStore {
+    path: PathBuf,
+    log: File,
+    memory: MemoryRegistryStore,
+}
+
+/// An entry in the registry log file
+#[derive(Debug, Serialize, Deserialize)]
+enum RegistryLogEntry {
+    Add(Vec<String>),
+    Revoke {
+        elements: Vec<String>,
+        epoch: RegistryEpoch,
+    },
+    Epoch(RegistryEpoch),
+}
+
+impl FileRegistryStore {
+    /// Open the log file at `path`, creating it if it doesn't exist
+    pub fn open<P: AsRef<Path>>(path: P) -> CredxResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut memory = MemoryRegistryStore::default();
+        let mut log = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)
+            .map_err(storage_error)?;
+        let mut reader = BufReader::new(&log);
+        let mut line = Vec::new();
+        let mut committed = 0;
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line).map_err(storage_error)?;
+            // Only lines ending in a newline were completely written
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            committed += read as u64;
+            if line.len() == 1 {
+                continue;
+            }
+            match serde_json::from_slice(&line).map_err(|_| Error::DeserializationError)? {
+                RegistryLogEntry::Add(elements) => memory.add(&elements)?,
+                RegistryLogEntry::Revoke { elements, epoch } => memory.revoke(&elements, epoch)?,
+                RegistryLogEntry::Epoch(epoch) => memory.push_epoch(epoch)?,
+            }
+        }
+        if log.metadata().map_err(storage_error)?.len() > committed {
+            log.set_len(committed).map_err(storage_error)?;
+            log.sync_data().map_err(storage_error)?;
+        }
+        log.seek(SeekFrom::End(0)).map_err(storage_error)?;
+        Ok(Self { path, log, memory })
+    }
+
+    /// The log file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn append(&mut self, entry: &RegistryLogEntry) -> CredxResult<()> {
+        let mut line = serde_json::to_vec(entry).map_err(|_| Error::General("registry log"))?;
+        line.push(b'\n');
+        self.log.write_all(&line).map_err(storage_error)?;
+        self.log.sync_data().map_err(storage_error)
+    }
+}
+
+impl RegistryStore for FileRegistryStore {
+    fn contains(&self, element: &str) -> bool {
+        self.memory.contains(element)
+    }
+
+    fn is_active(&self, element: &str) -> bool {
+        self.memory.is_active(element)
+    }
+
+    fn len(&self) -> usize {
+        self.memory.len()
+    }
+
+    fn active_len(&self) -> usize {
+        self.memory.active_len()
+    }
+
+    fn add(&mut self, elements: &[String]) -> CredxResult<()> {
+        let new = elements
+            .iter()
+            .enumerate()
+            .filter(|(i, e)| !self.memory.contains(e) && !elements[..*i].contains(e))
+            .map(|(_, e)| e.clone())
+            .collect::<Vec<_>>();
+        if new.is_empty() {
+            return Ok(());
+        }
+        self.append(&RegistryLogEntry::Add(new.clone()))?;
+        self.memory.add(&new)
+    }
+
+    fn revoke(&mut self, elements: &[String], epoch: RegistryEpoch) -> CredxResult<()> {
+        self.append(&RegistryLogEntry::Revoke {
+            elements: elements.to_vec(),
+            epoch,
+        })?;
+        self.memory.revoke(elements, epoch)
+    }
+
+    fn history(&self) -> &[RegistryEpoch] {
+        self.memory.history()
+    }
+
+    fn push_epoch(&mut self, epoch: RegistryEpoch) -> CredxResult<()> {
+        self.append(&RegistryLogEntry::Epoch(epoch))?;
+        self.memory.push_epoch(epoch)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileRegistryStorePath {
+    path: PathBuf,
+}
+
+impl Serialize for FileRegistryStore {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        FileRegistryStorePath {
+            path: self.path.clone(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for FileRegistryStore {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let stored = FileRegistryStorePath::deserialize(d)?;
+        Self::open(&stored.path).map_err(|e| {
+            serde::de::Error::custom(format!(
+                "unable to open registry log '{}': {:?}",
+                stored.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+fn storage_error(e: std::io::Error) -> Error {
+    Error::RegistryStorage(e.to_string())
+}
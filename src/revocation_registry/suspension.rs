@@ -0,0 +1,111 @@
+use super::RegistryUpdate;
+use crate::error::Error;
+use crate::knox::accumulator::vb20::{Accumulator, Element, NonMembershipWitness, SecretKey};
+use crate::{utils::*, CredxResult};
+use chrono::Utc;
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+
+/// A registry of suspended credentials.
+///
+/// Unlike the revocation registry, the accumulator holds the elements
+/// that are *not* allowed so holders prove non-membership.
+/// Suspensions can be lifted and holders keep using the same credential.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SuspensionRegistry {
+    /// The registry value
+    pub value: Accumulator,
+    /// The currently suspended elements
+    #[serde(
+        serialize_with = "serialize_indexset",
+        deserialize_with = "deserialize_indexset"
+    )]
+    pub suspended: IndexSet<String>,
+    /// The number of updates applied to the registry value
+    pub epoch: usize,
+}
+
+impl Default for SuspensionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SuspensionRegistry {
+    /// Create a new suspension registry with no suspended elements
+    pub fn new() -> Self {
+        Self {
+            value: Accumulator::default(),
+            suspended: IndexSet::new(),
+            epoch: 0,
+        }
+    }
+
+    /// Create a suspension handle for `element`.
+    /// Fails if the element is suspended.
+    pub fn witness(&self, sk: &SecretKey, element: &str) -> CredxResult<NonMembershipWitness> {
+        let suspended = self
+            .suspended
+            .iter()
+            .map(|e| Element::hash(e.as_bytes()))
+            .collect::<Vec<_>>();
+        NonMembershipWitness::new(Element::hash(element.as_bytes()), &suspended, sk)
+            .ok_or(Error::InvalidRevocationRegistryRevokeOperation)
+    }
+
+    /// Add the elements to the suspended set
+    /// and return the update for holders
+    pub fn suspend(&mut self, sk: &SecretKey, elements: &[String]) -> CredxResult<RegistryUpdate> {
+        if elements
+            .iter()
+            .enumerate()
+            .any(|(i, e)| self.suspended.contains(e) || elements[..i].contains(e))
+        {
+            return Err(Error::InvalidRevocationRegistryRevokeOperation);
+        }
+        let mut additions = Vec::with_capacity(elements.len());
+        for e in elements {
+            self.suspended.insert(e.clone());
+            additions.push(Element::hash(e.as_bytes()));
+        }
+
+        Ok(self.update(sk, additions, Vec::new()))
+    }
+
+    /// Remove the elements from the suspended set
+    /// and return the update for holders
+    pub fn lift(&mut self, sk: &SecretKey, elements: &[String]) -> CredxResult<RegistryUpdate> {
+        if elements
+            .iter()
+            .enumerate()
+            .any(|(i, e)| !self.suspended.contains(e) || elements[..i].contains(e))
+        {
+            return Err(Error::InvalidRevocationRegistryRevokeOperation);
+        }
+        let mut deletions = Vec::with_capacity(elements.len());
+        for e in elements {
+            self.suspended.shift_remove(e);
+            deletions.push(Element::hash(e.as_bytes()));
+        }
+
+        Ok(self.update(sk, Vec::new(), deletions))
+    }
+
+    fn update(
+        &mut self,
+        sk: &SecretKey,
+        additions: Vec<Element>,
+        deletions: Vec<Element>,
+    ) -> RegistryUpdate {
+        let coefficients = self.value.update_assign(sk, &additions, &deletions);
+        self.epoch += 1;
+        RegistryUpdate {
+            epoch: self.epoch,
+            timestamp: Utc::now().timestamp() as u64,
+            value: self.value,
+            additions,
+            deletions,
+            coefficients,
+        }
+    }
+}
@@ -1,18 +1,30 @@
+mod any_of;
+mod claim_range;
 mod commitment;
 mod equality;
 mod membership;
+mod non_membership;
+mod not_equal;
+mod presence;
 mod range;
 mod revocation;
 mod signature;
+mod suspension;
 mod verifiable_encryption;
 mod verifiable_encryption_decryption;
 
+pub use any_of::*;
+pub use claim_range::*;
 pub use commitment::*;
 pub use equality::*;
 pub use membership::*;
+pub use non_membership::*;
+pub use not_equal::*;
+pub use presence::*;
 pub use range::*;
 pub use revocation::*;
 pub use signature::*;
+pub use suspension::*;
 pub use verifiable_encryption::*;
 pub use verifiable_encryption_decryption::*;
 
@@ -56,6 +68,22 @@ pub enum Statements<S: ShortGroupSignatureScheme> {
     /// Membership statements
     Membership(Box<MembershipStatement>),
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionStatement<G1Projective>>),
+    /// Non-membership statements
+    NonMembership(Box<NonMembershipStatement>),
+    /// Claim presence statements
+    Presence(Box<PresenceStatement>),
+    /// Suspension statements
+    Suspension(Box<SuspensionStatement>),
+    /// Disjunctive statements
+    #[serde(bound(
+        serialize = "AnyOfStatement<S>: Serialize",
+        deserialize = "AnyOfStatement<S>: Deserialize<'de>"
+    ))]
+    AnyOf(Box<AnyOfStatement<S>>),
+    /// Inequality statements
+    NotEqual(Box<NotEqualStatement>),
+    /// Range statements on signature claims
+    ClaimRange(Box<ClaimRangeStatement>),
 }
 
 impl<S: ShortGroupSignatureScheme> From<SignatureStatement<S>> for Statements<S> {
@@ -110,6 +138,42 @@ impl<S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionStatement<
     }
 }
 
+impl<S: ShortGroupSignatureScheme> From<NonMembershipStatement> for Statements<S> {
+    fn from(n: NonMembershipStatement) -> Self {
+        Self::NonMembership(Box::new(n))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<PresenceStatement> for Statements<S> {
+    fn from(p: PresenceStatement) -> Self {
+        Self::Presence(Box::new(p))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<SuspensionStatement> for Statements<S> {
+    fn from(s: SuspensionStatement) -> Self {
+        Self::Suspension(Box::new(s))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<AnyOfStatement<S>> for Statements<S> {
+    fn from(a: AnyOfStatement<S>) -> Self {
+        Self::AnyOf(Box::new(a))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<NotEqualStatement> for Statements<S> {
+    fn from(n: NotEqualStatement) -> Self {
+        Self::NotEqual(Box::new(n))
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> From<ClaimRangeStatement> for Statements<S> {
+    fn from(c: ClaimRangeStatement) -> Self {
+        Self::ClaimRange(Box::new(c))
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> Statements<S> {
     /// Return the statement id
     pub fn id(&self) -> String {
@@ -122,6 +186,12 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.id(),
             Self::Membership(m) => m.id(),
             Self::VerifiableEncryptionDecryption(v) => v.id(),
+            Self::NonMembership(n) => n.id(),
+            Self::Presence(p) => p.id(),
+            Self::Suspension(s) => s.id(),
+            Self::AnyOf(a) => a.id(),
+            Self::NotEqual(n) => n.id(),
+            Self::ClaimRange(c) => c.id(),
         }
     }
 
@@ -136,6 +206,12 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.reference_ids(),
             Self::Membership(m) => m.reference_ids(),
             Self::VerifiableEncryptionDecryption(v) => v.reference_ids(),
+            Self::NonMembership(n) => n.reference_ids(),
+            Self::Presence(p) => p.reference_ids(),
+            Self::Suspension(s) => s.reference_ids(),
+            Self::AnyOf(a) => a.reference_ids(),
+            Self::NotEqual(n) => n.reference_ids(),
+            Self::ClaimRange(c) => c.reference_ids(),
         }
     }
 
@@ -150,6 +226,12 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.add_challenge_contribution(transcript),
             Self::Membership(m) => m.add_challenge_contribution(transcript),
             Self::VerifiableEncryptionDecryption(v) => v.add_challenge_contribution(transcript),
+            Self::NonMembership(n) => n.add_challenge_contribution(transcript),
+            Self::Presence(p) => p.add_challenge_contribution(transcript),
+            Self::Suspension(s) => s.add_challenge_contribution(transcript),
+            Self::AnyOf(a) => a.add_challenge_contribution(transcript),
+            Self::NotEqual(n) => n.add_challenge_contribution(transcript),
+            Self::ClaimRange(c) => c.add_challenge_contribution(transcript),
         }
     }
 
@@ -164,6 +246,12 @@ impl<S: ShortGroupSignatureScheme> Statements<S> {
             Self::Range(r) => r.get_claim_index(reference_id),
             Self::Membership(m) => m.get_claim_index(reference_id),
             Self::VerifiableEncryptionDecryption(v) => v.get_claim_index(reference_id),
+            Self::NonMembership(n) => n.get_claim_index(reference_id),
+            Self::Presence(p) => p.get_claim_index(reference_id),
+            Self::Suspension(s) => s.get_claim_index(reference_id),
+            Self::AnyOf(a) => a.get_claim_index(reference_id),
+            Self::NotEqual(n) => n.get_claim_index(reference_id),
+            Self::ClaimRange(c) => c.get_claim_index(reference_id),
         }
     }
 }
@@ -0,0 +1,138 @@
+use super::{RevocationStatement, SignatureStatement, Statement, Statements};
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::{
+    ProofOfSignatureKnowledge, ShortGroupSignatureScheme,
+};
+use crate::CredxResult;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
+
+/// A disjunction of signature statements.
+/// The prover shows one branch holds without revealing which.
+///
+/// Branches can't disclose claims since the disclosed values would identify
+/// the branch. Branch predicates must be revocation or membership statements
+/// that reference the branch signature statement. For the same reason every branch
+/// is proven with the issuer's current key and the registry epoch the statement
+/// names, credentials signed with an older key or holding an older witness must
+/// be refreshed first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnyOfStatement<S: ShortGroupSignatureScheme> {
+    /// The statement id
+    pub id: String,
+    /// The alternatives, one must hold
+    // No bounds since branches contain `Statements<S>` which contains this statement
+    #[serde(bound(serialize = "", deserialize = ""))]
+    pub branches: Vec<AnyOfBranch<S>>,
+}
+
+/// An alternative in an [`AnyOfStatement`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AnyOfBranch<S: ShortGroupSignatureScheme> {
+    /// The signature statement
+    #[serde(bound(
+        serialize = "SignatureStatement<S>: Serialize",
+        deserialize = "SignatureStatement<S>: Deserialize<'de>"
+    ))]
+    pub signature: SignatureStatement<S>,
+    /// The predicates on the signature statement claims
+    #[serde(bound(
+        serialize = "Statements<S>: Serialize",
+        deserialize = "Statements<S>: Deserialize<'de>"
+    ))]
+    pub predicates: Vec<Statements<S>>,
+}
+
+impl<S: ShortGroupSignatureScheme> AnyOfBranch<S> {
+    /// The issuer key epoch every proof for this branch uses
+    pub(crate) fn key_epoch(&self) -> usize {
+        self.signature.issuer.key_epoch
+    }
+
+    /// The registry epoch every proof for the branch revocation predicate uses
+    pub(crate) fn registry_epoch(&self, statement: &RevocationStatement) -> usize {
+        statement.newest_epoch(self.signature.issuer.revocation_epoch)
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> Statement for AnyOfStatement<S> {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn reference_ids(&self) -> Vec<String> {
+        Vec::with_capacity(0)
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"statement type", b"any of");
+        transcript.append_message(b"statement id", self.id.as_bytes());
+        transcript.append_message(
+            b"any of branches length",
+            &Uint::from(self.branches.len()).to_vec(),
+        );
+        for branch in &self.branches {
+            branch.signature.add_challenge_contribution(transcript);
+            transcript.append_message(
+                b"any of branch predicates length",
+                &Uint::from(branch.predicates.len()).to_vec(),
+            );
+            for predicate in &branch.predicates {
+                predicate.add_challenge_contribution(transcript);
+            }
+        }
+    }
+
+    fn get_claim_index(&self, _reference_id: &str) -> usize {
+        unimplemented!()
+    }
+}
+
+impl<S: ShortGroupSignatureScheme> AnyOfStatement<S> {
+    /// Check the branches can be proven without revealing which one holds
+    pub fn validate(&self) -> CredxResult<()> {
+        if self.branches.is_empty() {
+            return Err(Error::InvalidPresentationData(format!(
+                "any of statement '{}' has no branches",
+                self.id
+            )));
+        }
+        for branch in &self.branches {
+            if !S::ProofOfSignatureKnowledge::can_simulate(&branch.signature.issuer.verifying_key) {
+                return Err(Error::InvalidPresentationData(format!(
+                    "any of statement '{}' branch '{}' issuer key can't be used to simulate proofs",
+                    self.id, branch.signature.id
+                )));
+            }
+            if !branch.signature.disclosed.is_empty() {
+                return Err(Error::InvalidPresentationData(format!(
+                    "any of statement '{}' branch '{}' can't disclose claims",
+                    self.id, branch.signature.id
+                )));
+            }
+            for predicate in &branch.predicates {
+                let reference_id = match predicate {
+                    Statements::Revocation(r) => &r.reference_id,
+                    Statements::Membership(m) => &m.reference_id,
+                    _ => {
+                        return Err(Error::InvalidPresentationData(format!(
+                            "any of statement '{}' only supports revocation and membership predicates, found '{}'",
+                            self.id,
+                            predicate.id()
+                        )))
+                    }
+                };
+                if *reference_id != branch.signature.id {
+                    return Err(Error::InvalidPresentationData(format!(
+                        "any of statement '{}' predicate '{}' must reference its branch signature statement '{}'",
+                        self.id,
+                        predicate.id(),
+                        branch.signature.id
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::claim::DecimalClaim;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::statement::{
+    date_bounds, datetime_bounds, decimal_bounds, older_than_bounds, younger_than_bounds, Bounds,
+    SignatureStatement, Statement,
+};
+use crate::CredxResult;
+use chrono::{DateTime, NaiveDate, Utc};
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
+
+/// A range proof statement on a signature statement claim.
+///
+/// Unlike [`super::RangeStatement`] no commitment statement is needed,
+/// the claim commitment is created with fixed generators when the presentation is created.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClaimRangeStatement {
+    /// The statement id
+    pub id: String,
+    /// The reference id to the signature statement
+    pub signature_id: String,
+    /// The claim label in the signature statement
+    pub claim: String,
+    /// The lower bound to test against if set
+    pub lower: Option<isize>,
+    /// The upper bound to test against if set
+    pub upper: Option<isize>,
+}
+
+impl ClaimRangeStatement {
+    /// Create a range statement for a number claim where the claim is
+    /// between `lower` and `upper` inclusive
+    pub fn new(
+        id: &str,
+        signature_id: &str,
+        claim: &str,
+        lower: Option<isize>,
+        upper: Option<isize>,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            signature_id: signature_id.to_string(),
+            claim: claim.to_string(),
+            lower,
+            upper,
+        }
+    }
+
+    fn with_bounds(id: &str, signature_id: &str, claim: &str, (lower, upper): Bounds) -> Self {
+        Self::new(id, signature_id, claim, lower, upper)
+    }
+
+    /// Create a range statement for a date claim where the claim is
+    /// between `lower` and `upper` inclusive
+    pub fn date_range(
+        id: &str,
+        signature_id: &str,
+        claim: &str,
+        lower: Option<NaiveDate>,
+        upper: Option<NaiveDate>,
+    ) -> Self {
+        Self::with_bounds(id, signature_id, claim, date_bounds(lower, upper))
+    }
+
+    /// Create a range statement for a datetime claim where the claim is
+    /// between `lower` and `upper` inclusive
+    pub fn datetime_range(
+        id: &str,
+        signature_id: &str,
+        claim: &str,
+        lower: Option<DateTime<Utc>>,
+        upper: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self::with_bounds(id, signature_id, claim, datetime_bounds(lower, upper))
+    }
+
+    /// Create a range statement for a decimal claim where the claim is
+    /// between `lower` and `upper` inclusive.
+    /// The scale comes from the claim schema in the signature statement's issuer
+    /// and the bounds must be exactly representable at that scale
+    pub fn decimal_range<S: ShortGroupSignatureScheme>(
+        id: &str,
+        signature: &SignatureStatement<S>,
+        claim: &str,
+        lower: Option<DecimalClaim>,
+        upper: Option<DecimalClaim>,
+    ) -> CredxResult<Self> {
+        let statement = Self::new(id, &signature.id, claim, None, None);
+        let claim_schema = &signature.issuer.schema.claims[statement.claim_index(signature)?];
+        let bounds = decimal_bounds(claim_schema, lower, upper)?;
+        Ok(Self::with_bounds(id, &signature.id, claim, bounds))
+    }
+
+    /// Create a range statement that the date claim is at least `years` ago
+    /// according to the verifier's clock, i.e. a date of birth for someone older than `years`
+    pub fn older_than(id: &str, signature_id: &str, claim: &str, years: u32) -> CredxResult<Self> {
+        let today = Utc::now().date_naive();
+        Self::older_than_as_of(id, signature_id, claim, years, today)
+    }
+
+    /// Create a range statement that the date claim is at least `years` before `today`
+    pub fn older_than_as_of(
+        id: &str,
+        signature_id: &str,
+        claim: &str,
+        years: u32,
+        today: NaiveDate,
+    ) -> CredxResult<Self> {
+        let bounds = older_than_bounds(years, today)?;
+        Ok(Self::with_bounds(id, signature_id, claim, bounds))
+    }
+
+    /// Create a range statement that the date claim is less than `years` ago
+    /// according to the verifier's clock, i.e. a date of birth for someone younger than `years`
+    pub fn younger_than(
+        id: &str,
+        signature_id: &str,
+        claim: &str,
+        years: u32,
+    ) -> CredxResult<Self> {
+        let today = Utc::now().date_naive();
+        Self::younger_than_as_of(id, signature_id, claim, years, today)
+    }
+
+    /// Create a range statement that the date claim is less than `years` before `today`
+    pub fn younger_than_as_of(
+        id: &str,
+        signature_id: &str,
+        claim: &str,
+        years: u32,
+        today: NaiveDate,
+    ) -> CredxResult<Self> {
+        let bounds = younger_than_bounds(years, today)?;
+        Ok(Self::with_bounds(id, signature_id, claim, bounds))
+    }
+
+    /// The index of the claim label in the signature statement schema
+    pub fn claim_index<S: ShortGroupSignatureScheme>(
+        &self,
+        signature: &SignatureStatement<S>,
+    ) -> CredxResult<usize> {
+        signature
+            .issuer
+            .schema
+            .claim_indices
+            .get_index_of(&self.claim)
+            .ok_or(Error::InvalidPresentationData(format!(
+                "claim range statement '{}' references claim '{}' which doesn't exist in signature statement '{}'",
+                self.id, self.claim, signature.id
+            )))
+    }
+}
+
+impl Statement for ClaimRangeStatement {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn reference_ids(&self) -> Vec<String> {
+        vec![self.signature_id.clone()]
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"statement type", b"claim range proof");
+        transcript.append_message(b"statement id", self.id.as_bytes());
+        transcript.append_message(
+            b"reference signature statement id",
+            self.signature_id.as_bytes(),
+        );
+        transcript.append_message(b"claim label", self.claim.as_bytes());
+        transcript.append_message(b"lower version", &[self.lower.map_or(0u8, |_| 1u8)]);
+        if let Some(lower) = self.lower.as_ref() {
+            transcript.append_message(b"lower", &Uint::from(*lower).to_vec());
+        }
+        transcript.append_message(b"upper version", &[self.upper.map_or(0u8, |_| 1u8)]);
+        if let Some(upper) = self.upper.as_ref() {
+            transcript.append_message(b"upper", &Uint::from(*upper).to_vec());
+        }
+    }
+
+    /// The claim is a label, use [`ClaimRangeStatement::claim_index`] with the signature statement
+    fn get_claim_index(&self, _reference_id: &str) -> usize {
+        unimplemented!()
+    }
+}
@@ -0,0 +1,97 @@
+use crate::claim::ClaimData;
+use crate::error::Error;
+use crate::statement::Statement;
+use crate::CredxResult;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
+
+/// A statement that a hidden claim is not equal to a public value
+/// or to a hidden claim in another signature statement
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NotEqualStatement {
+    /// The statement id
+    pub id: String,
+    /// The other statement id
+    pub reference_id: String,
+    /// The claim index in the other statement
+    pub claim: usize,
+    /// What the claim must differ from
+    pub other: NotEqualTarget,
+}
+
+/// The value a [`NotEqualStatement`] claim is compared to
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum NotEqualTarget {
+    /// A public claim value
+    Value(ClaimData),
+    /// A hidden claim in a different signature statement
+    Claim {
+        /// The other statement id
+        reference_id: String,
+        /// The claim index in the other statement
+        claim: usize,
+    },
+}
+
+impl Statement for NotEqualStatement {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn reference_ids(&self) -> Vec<String> {
+        match &self.other {
+            NotEqualTarget::Value(_) => vec![self.reference_id.clone()],
+            NotEqualTarget::Claim { reference_id, .. } => {
+                vec![self.reference_id.clone(), reference_id.clone()]
+            }
+        }
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"statement type", b"not equal");
+        transcript.append_message(b"statement id", self.id.as_bytes());
+        transcript.append_message(b"reference statement id", self.reference_id.as_bytes());
+        transcript.append_message(b"claim index", &Uint::from(self.claim).to_vec());
+        match &self.other {
+            NotEqualTarget::Value(value) => {
+                transcript.append_message(b"other value", &value.to_bytes());
+                transcript.append_message(b"other value scalar", &value.to_scalar().to_be_bytes());
+            }
+            NotEqualTarget::Claim {
+                reference_id,
+                claim,
+            } => {
+                transcript.append_message(b"other reference statement id", reference_id.as_bytes());
+                transcript.append_message(b"other claim index", &Uint::from(*claim).to_vec());
+            }
+        }
+    }
+
+    fn get_claim_index(&self, reference_id: &str) -> usize {
+        match &self.other {
+            NotEqualTarget::Claim {
+                reference_id: other_id,
+                claim,
+            } if other_id == reference_id => *claim,
+            _ => self.claim,
+        }
+    }
+}
+
+impl NotEqualStatement {
+    /// Check both claims can be linked to their signature proofs.
+    /// Each reference supplies one claim so the two claims
+    /// must come from different signature statements
+    pub fn validate(&self) -> CredxResult<()> {
+        if let NotEqualTarget::Claim { reference_id, .. } = &self.other {
+            if *reference_id == self.reference_id {
+                return Err(Error::InvalidPresentationData(format!(
+                    "not equal statement '{}' must compare claims from different signature statements",
+                    self.id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::statement::Statement;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use uint_zigzag::Uint;
+
+/// A statement that an optional claim is present or absent without revealing it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresenceStatement {
+    /// The statement id
+    pub id: String,
+    /// The other statement id
+    pub reference_id: String,
+    /// The claim index in the other statement
+    pub claim: usize,
+    /// [`true`] to prove the claim is present, [`false`] to prove it is absent
+    pub present: bool,
+}
+
+impl Statement for PresenceStatement {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn reference_ids(&self) -> Vec<String> {
+        vec![self.reference_id.clone()]
+    }
+
+    fn add_challenge_contribution(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"statement type", b"claim presence");
+        transcript.append_message(b"statement id", self.id.as_bytes());
+        transcript.append_message(b"reference statement id", self.reference_id.as_bytes());
+        transcript.append_message(b"claim index", &Uint::from(self.claim).to_vec());
+        transcript.append_message(b"claim present", &[u8::from(self.present)]);
+    }
+
+    fn get_claim_index(&self, _reference_id: &str) -> usize {
+        self.claim
+    }
+}
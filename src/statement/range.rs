@@ -1,4 +1,10 @@
+use crate::claim::{ClaimType, DateClaim, DateTimeClaim, DecimalClaim};
+use crate::credential::ClaimSchema;
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
 use crate::statement::*;
+use crate::CredxResult;
+use chrono::{DateTime, NaiveDate, Utc};
 use merlin::Transcript;
 use serde::{Deserialize, Serialize};
 use uint_zigzag::Uint;
@@ -20,6 +26,205 @@ pub struct RangeStatement {
     pub upper: Option<isize>,
 }
 
+impl RangeStatement {
+    fn with_bounds(
+        id: &str,
+        reference_id: &str,
+        signature_id: &str,
+        claim: usize,
+        (lower, upper): Bounds,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            reference_id: reference_id.to_string(),
+            signature_id: signature_id.to_string(),
+            claim,
+            lower,
+            upper,
+        }
+    }
+
+    /// Create a range statement for a date claim where the claim is
+    /// between `lower` and `upper` inclusive
+    pub fn date_range(
+        id: &str,
+        reference_id: &str,
+        signature_id: &str,
+        claim: usize,
+        lower: Option<NaiveDate>,
+        upper: Option<NaiveDate>,
+    ) -> Self {
+        let bounds = date_bounds(lower, upper);
+        Self::with_bounds(id, reference_id, signature_id, claim, bounds)
+    }
+
+    /// Create a range statement for a datetime claim where the claim is
+    /// between `lower` and `upper` inclusive
+    pub fn datetime_range(
+        id: &str,
+        reference_id: &str,
+        signature_id: &str,
+        claim: usize,
+        lower: Option<DateTime<Utc>>,
+        upper: Option<DateTime<Utc>>,
+    ) -> Self {
+        let bounds = datetime_bounds(lower, upper);
+        Self::with_bounds(id, reference_id, signature_id, claim, bounds)
+    }
+
+    /// Create a range statement for a decimal claim where the claim is
+    /// between `lower` and `upper` inclusive.
+    /// The scale comes from the claim schema in the signature statement's issuer
+    /// and the bounds must be exactly representable at that scale
+    pub fn decimal_range<S: ShortGroupSignatureScheme>(
+        id: &str,
+        reference_id: &str,
+        signature: &SignatureStatement<S>,
+        claim: usize,
+        lower: Option<DecimalClaim>,
+        upper: Option<DecimalClaim>,
+    ) -> CredxResult<Self> {
+        let claim_schema = signature
+            .issuer
+            .schema
+            .claims
+            .get(claim)
+            .ok_or(Error::General("claim index is out of range"))?;
+        let bounds = decimal_bounds(claim_schema, lower, upper)?;
+        Ok(Self::with_bounds(
+            id,
+            reference_id,
+            &signature.id,
+            claim,
+            bounds,
+        ))
+    }
+
+    /// Create a range statement that the date claim is at least `years` ago
+    /// according to the verifier's clock, i.e. a date of birth for someone older than `years`
+    pub fn older_than(
+        id: &str,
+        reference_id: &str,
+        signature_id: &str,
+        claim: usize,
+        years: u32,
+    ) -> CredxResult<Self> {
+        let today = Utc::now().date_naive();
+        Self::older_than_as_of(id, reference_id, signature_id, claim, years, today)
+    }
+
+    /// Create a range statement that the date claim is at least `years` before `today`
+    pub fn older_than_as_of(
+        id: &str,
+        reference_id: &str,
+        signature_id: &str,
+        claim: usize,
+        years: u32,
+        today: NaiveDate,
+    ) -> CredxResult<Self> {
+        let bounds = older_than_bounds(years, today)?;
+        Ok(Self::with_bounds(
+            id,
+            reference_id,
+            signature_id,
+            claim,
+            bounds,
+        ))
+    }
+
+    /// Create a range statement that the date claim is less than `years` ago
+    /// according to the verifier's clock, i.e. a date of birth for someone younger than `years`
+    pub fn younger_than(
+        id: &str,
+        reference_id: &str,
+        signature_id: &str,
+        claim: usize,
+        years: u32,
+    ) -> CredxResult<Self> {
+        let today = Utc::now().date_naive();
+        Self::younger_than_as_of(id, reference_id, signature_id, claim, years, today)
+    }
+
+    /// Create a range statement that the date claim is less than `years` before `today`
+    pub fn younger_than_as_of(
+        id: &str,
+        reference_id: &str,
+        signature_id: &str,
+        claim: usize,
+        years: u32,
+        today: NaiveDate,
+    ) -> CredxResult<Self> {
+        let bounds = younger_than_bounds(years, today)?;
+        Ok(Self::with_bounds(
+            id,
+            reference_id,
+            signature_id,
+            claim,
+            bounds,
+        ))
+    }
+}
+
+/// The inclusive lower and upper bounds of a range statement
+pub(crate) type Bounds = (Option<isize>, Option<isize>);
+
+/// Convert date bounds to date claim values
+pub(crate) fn date_bounds(lower: Option<NaiveDate>, upper: Option<NaiveDate>) -> Bounds {
+    (
+        lower.map(|d| DateClaim::from(d).value),
+        upper.map(|d| DateClaim::from(d).value),
+    )
+}
+
+/// Convert datetime bounds to datetime claim values
+pub(crate) fn datetime_bounds(
+    lower: Option<DateTime<Utc>>,
+    upper: Option<DateTime<Utc>>,
+) -> Bounds {
+    (
+        lower.map(|d| DateTimeClaim::from(d).value),
+        upper.map(|d| DateTimeClaim::from(d).value),
+    )
+}
+
+/// The bounds for a date at least `years` before `today`
+pub(crate) fn older_than_bounds(years: u32, today: NaiveDate) -> CredxResult<Bounds> {
+    let upper =
+        DateClaim::years_before(today, years).ok_or(Error::General("date is out of range"))?;
+    Ok(date_bounds(None, Some(upper)))
+}
+
+/// The bounds for a date less than `years` before `today`
+pub(crate) fn younger_than_bounds(years: u32, today: NaiveDate) -> CredxResult<Bounds> {
+    let lower = DateClaim::years_before(today, years)
+        .and_then(|d| d.succ_opt())
+        .ok_or(Error::General("date is out of range"))?;
+    Ok(date_bounds(Some(lower), None))
+}
+
+/// Convert decimal bounds to the scale of the decimal claim schema
+pub(crate) fn decimal_bounds(
+    claim_schema: &ClaimSchema,
+    lower: Option<DecimalClaim>,
+    upper: Option<DecimalClaim>,
+) -> CredxResult<Bounds> {
+    if claim_schema.claim_type != ClaimType::Decimal {
+        return Err(Error::General("claim is not a decimal claim"));
+    }
+    let rescale = |bound: Option<DecimalClaim>| {
+        bound
+            .map(|d| {
+                d.rescale(claim_schema.scale)
+                    .map(|d| d.value)
+                    .ok_or(Error::General(
+                        "decimal bound cannot be represented at the claim scale",
+                    ))
+            })
+            .transpose()
+    };
+    Ok((rescale(lower)?, rescale(upper)?))
+}
+
 impl Statement for RangeStatement {
     fn id(&self) -> String {
         self.id.clone()
@@ -1,5 +1,12 @@
+use crate::error::Error;
+use crate::issuer::IssuerPublic;
 use crate::knox::accumulator::vb20;
+use crate::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use crate::revocation_registry::{RegistryEpoch, SignedAccumulatorState};
 use crate::statement::Statement;
+use crate::CredxResult;
+use blsful::{inner_types::GroupEncoding, Bls12381G2Impl, PublicKey};
+use chrono::Utc;
 use merlin::Transcript;
 use serde::{Deserialize, Serialize};
 use uint_zigzag::Uint;
@@ -17,6 +24,76 @@ pub struct RevocationStatement {
     pub verification_key: vb20::PublicKey,
     /// The claim index in the other statement
     pub claim: usize,
+    /// The registry epochs that are accepted
+    #[serde(default)]
+    pub epochs: RegistryEpochPolicy,
+    /// The issuer signed registry states that are accepted.
+    /// When set only registry values with a signed state can be used.
+    #[serde(default)]
+    pub signed_states: Option<SignedStatePolicy>,
+}
+
+/// The revocation registry epochs a verifier accepts
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub enum RegistryEpochPolicy {
+    /// Only the statement `accumulator` value
+    #[default]
+    Current,
+    /// Only the registry value at this epoch
+    Pinned(RegistryEpoch),
+    /// Any of the registry values in `history` that were still current no more
+    /// than `max_age` seconds before `as_of`. A value stops being current when
+    /// a later epoch in `history` starts, the newest value is always current.
+    Recent {
+        /// The registry values that can be used
+        history: Vec<RegistryEpoch>,
+        /// The maximum age in seconds
+        max_age: u64,
+        /// The time to measure the age from as seconds since the unix epoch,
+        /// the verifier's clock when not set
+        #[serde(default)]
+        as_of: Option<u64>,
+    },
+}
+
+/// The registry values a verifier accepts must be signed by the issuer.
+/// The registry id and verifying key must be those of the issuer
+/// in the referenced signature statement.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedStatePolicy {
+    /// The registry the states belong to
+    pub registry_id: String,
+    /// The issuer's registry verifying key
+    pub verifying_key: PublicKey<Bls12381G2Impl>,
+    /// The signed registry states
+    pub states: Vec<SignedAccumulatorState>,
+}
+
+impl SignedStatePolicy {
+    /// Create a policy for the states signed by `issuer`
+    pub fn new<S: ShortGroupSignatureScheme>(
+        issuer: &IssuerPublic<S>,
+        states: Vec<SignedAccumulatorState>,
+    ) -> Self {
+        Self {
+            registry_id: issuer.id.clone(),
+            verifying_key: issuer.registry_verifying_key,
+            states,
+        }
+    }
+
+    /// Check `value` at `epoch` has a valid signed state
+    pub fn check(&self, epoch: usize, value: vb20::Accumulator) -> CredxResult<()> {
+        let state = self
+            .states
+            .iter()
+            .find(|s| s.registry_id == self.registry_id && s.epoch == epoch && s.value == value)
+            .ok_or(Error::InvalidPresentationData(format!(
+                "registry '{}' has no signed state for epoch '{}'",
+                self.registry_id, epoch
+            )))?;
+        state.verify(&self.verifying_key)
+    }
 }
 
 impl Statement for RevocationStatement {
@@ -38,9 +115,137 @@ impl Statement for RevocationStatement {
             self.verification_key.to_bytes().as_ref(),
         );
         transcript.append_message(b"accumulator", self.accumulator.to_bytes().as_ref());
+        match &self.epochs {
+            RegistryEpochPolicy::Current => {}
+            RegistryEpochPolicy::Pinned(e) => {
+                transcript.append_message(b"registry epoch policy", b"pinned");
+                add_registry_epoch_challenge_contribution(e, transcript);
+            }
+            RegistryEpochPolicy::Recent {
+                history,
+                max_age,
+                as_of,
+            } => {
+                transcript.append_message(b"registry epoch policy", b"recent");
+                transcript.append_message(b"registry max age", &Uint::from(*max_age).to_vec());
+                if let Some(as_of) = as_of {
+                    transcript.append_message(b"registry as of", &Uint::from(*as_of).to_vec());
+                }
+                transcript.append_message(
+                    b"registry history length",
+                    &Uint::from(history.len()).to_vec(),
+                );
+                for e in history {
+                    add_registry_epoch_challenge_contribution(e, transcript);
+                }
+            }
+        }
+        if let Some(policy) = &self.signed_states {
+            transcript.append_message(b"registry id", policy.registry_id.as_bytes());
+            transcript.append_message(
+                b"registry verifying key",
+                policy.verifying_key.0.to_bytes().as_ref(),
+            );
+            transcript.append_message(
+                b"registry signed states length",
+                &Uint::from(policy.states.len()).to_vec(),
+            );
+            for state in &policy.states {
+                transcript.append_message(b"registry signed state", &state.to_bytes());
+            }
+        }
     }
 
     fn get_claim_index(&self, _reference_id: &str) -> usize {
         self.claim
     }
 }
+
+impl RevocationStatement {
+    /// The registry value to verify a proof made at `epoch`
+    /// and that the issuer signed it if signed states are required
+    pub fn accumulator_for_epoch(&self, epoch: usize) -> CredxResult<vb20::Accumulator> {
+        let accumulator = self.accumulator_for_epoch_policy(epoch)?;
+        if let Some(policy) = &self.signed_states {
+            policy.check(epoch, accumulator)?;
+        }
+        Ok(accumulator)
+    }
+
+    /// Check the signed states are from `issuer`, the issuer
+    /// of the referenced signature statement
+    pub(crate) fn check_signed_state_issuer<S: ShortGroupSignatureScheme>(
+        &self,
+        issuer: &IssuerPublic<S>,
+    ) -> CredxResult<()> {
+        match &self.signed_states {
+            Some(policy)
+                if policy.registry_id != issuer.id
+                    || policy.verifying_key != issuer.registry_verifying_key =>
+            {
+                Err(Error::InvalidPresentationData(format!(
+                    "revocation statement '{}' signed states are not from the issuer of signature statement '{}'",
+                    self.id, self.reference_id
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The newest registry epoch this statement accepts,
+    /// `current` when the policy doesn't name one
+    pub(crate) fn newest_epoch(&self, current: usize) -> usize {
+        match &self.epochs {
+            RegistryEpochPolicy::Current => current,
+            RegistryEpochPolicy::Pinned(e) => e.epoch,
+            RegistryEpochPolicy::Recent { history, .. } => history
+                .iter()
+                .max_by_key(|e| e.timestamp)
+                .map(|e| e.epoch)
+                .unwrap_or(current),
+        }
+    }
+
+    fn accumulator_for_epoch_policy(&self, epoch: usize) -> CredxResult<vb20::Accumulator> {
+        match &self.epochs {
+            RegistryEpochPolicy::Current => Ok(self.accumulator),
+            RegistryEpochPolicy::Pinned(e) if e.epoch == epoch => Ok(e.value),
+            RegistryEpochPolicy::Pinned(e) => Err(Error::InvalidPresentationData(format!(
+                "revocation statement '{}' requires registry epoch '{}' but found '{}'",
+                self.id, e.epoch, epoch
+            ))),
+            RegistryEpochPolicy::Recent {
+                history,
+                max_age,
+                as_of,
+            } => {
+                let as_of = as_of.unwrap_or_else(|| Utc::now().timestamp() as u64);
+                history
+                    .iter()
+                    .find(|e| {
+                        let superseded = history
+                            .iter()
+                            .filter(|n| n.epoch > e.epoch)
+                            .map(|n| n.timestamp)
+                            .min();
+                        e.epoch == epoch
+                            && superseded.is_none_or(|t| t.saturating_add(*max_age) >= as_of)
+                    })
+                    .map(|e| e.value)
+                    .ok_or(Error::InvalidPresentationData(format!(
+                        "revocation statement '{}' does not accept registry epoch '{}'",
+                        self.id, epoch
+                    )))
+            }
+        }
+    }
+}
+
+fn add_registry_epoch_challenge_contribution(e: &RegistryEpoch, transcript: &mut Transcript) {
+    transcript.append_message(b"registry epoch", &Uint::from(e.epoch).to_vec());
+    transcript.append_message(
+        b"registry epoch timestamp",
+        &Uint::from(e.timestamp).to_vec(),
+    );
+    transcript.append_message(b"registry epoch value", e.value.to_bytes().as_ref());
+}
@@ -21,6 +21,20 @@ pub struct SignatureStatement<S: ShortGroupSignatureScheme> {
     pub issuer: IssuerPublic<S>,
 }
 
+impl<S: ShortGroupSignatureScheme> SignatureStatement<S> {
+    /// [`true`] if the claim with `label` is disclosed by this statement
+    /// either directly or because a parent object or array path is disclosed
+    pub fn is_disclosed(&self, label: &str) -> bool {
+        self.disclosed.iter().any(|d| discloses(d, label))
+    }
+}
+
+/// [`true`] if the disclosed label `disclosed` reveals the claim with `label`
+pub(crate) fn discloses(disclosed: &str, label: &str) -> bool {
+    disclosed == label
+        || disclosed.starts_with('/') && label.starts_with(&format!("{}/", disclosed))
+}
+
 impl<S: ShortGroupSignatureScheme> Statement for SignatureStatement<S> {
     /// Return this statement unique identifier
     fn id(&self) -> String {
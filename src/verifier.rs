@@ -1,18 +1,30 @@
+mod any_of;
+mod claim_range;
 mod commitment;
 mod equality;
 mod membership;
+mod non_membership;
+mod not_equal;
+mod presence;
 mod range;
 mod revocation;
 mod signature;
+mod suspension;
 mod verifiable_encryption;
 mod verifiable_encryption_decryption;
 
+pub use any_of::*;
+pub use claim_range::*;
 pub use commitment::*;
 pub use equality::*;
 pub use membership::*;
+pub use non_membership::*;
+pub use not_equal::*;
+pub use presence::*;
 pub use range::*;
 pub use revocation::*;
 pub use signature::*;
+pub use suspension::*;
 pub use verifiable_encryption::*;
 pub use verifiable_encryption_decryption::*;
 
@@ -42,6 +54,12 @@ pub(crate) enum ProofVerifiers<'a, 'b, 'c, S: ShortGroupSignatureScheme> {
     Range(Box<RangeProofVerifier<'a, 'b, 'c>>),
     Membership(Box<MembershipVerifier<'a, 'b>>),
     VerifiableEncryptionDecryption(Box<VerifiableEncryptionDecryptionVerifier<'a, 'b>>),
+    NonMembership(Box<NonMembershipVerifier<'a, 'b>>),
+    Presence(Box<PresenceVerifier<'a, 'b>>),
+    Suspension(Box<SuspensionVerifier<'a, 'b>>),
+    AnyOf(Box<AnyOfVerifier<'a, 'b, 'c, S>>),
+    NotEqual(Box<NotEqualVerifier<'a, 'b>>),
+    ClaimRange(Box<ClaimRangeVerifier<'a, 'b>>),
 }
 
 impl<'a, 'b, S: ShortGroupSignatureScheme> From<SignatureVerifier<'a, 'b, S>>
@@ -108,7 +126,81 @@ impl<'a, 'b, S: ShortGroupSignatureScheme> From<VerifiableEncryptionDecryptionVe
     }
 }
 
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<NonMembershipVerifier<'a, 'b>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: NonMembershipVerifier<'a, 'b>) -> Self {
+        Self::NonMembership(Box::new(a))
+    }
+}
+
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<PresenceVerifier<'a, 'b>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: PresenceVerifier<'a, 'b>) -> Self {
+        Self::Presence(Box::new(a))
+    }
+}
+
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<SuspensionVerifier<'a, 'b>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: SuspensionVerifier<'a, 'b>) -> Self {
+        Self::Suspension(Box::new(a))
+    }
+}
+
+impl<'a, 'b, 'c, S: ShortGroupSignatureScheme> From<AnyOfVerifier<'a, 'b, 'c, S>>
+    for ProofVerifiers<'a, 'b, 'c, S>
+{
+    fn from(a: AnyOfVerifier<'a, 'b, 'c, S>) -> Self {
+        Self::AnyOf(Box::new(a))
+    }
+}
+
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<NotEqualVerifier<'a, 'b>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: NotEqualVerifier<'a, 'b>) -> Self {
+        Self::NotEqual(Box::new(a))
+    }
+}
+
+impl<'a, 'b, S: ShortGroupSignatureScheme> From<ClaimRangeVerifier<'a, 'b>>
+    for ProofVerifiers<'a, 'b, '_, S>
+{
+    fn from(a: ClaimRangeVerifier<'a, 'b>) -> Self {
+        Self::ClaimRange(Box::new(a))
+    }
+}
+
 impl<S: ShortGroupSignatureScheme> ProofVerifiers<'_, '_, '_, S> {
+    /// Recompute the challenge contribution
+    pub fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        match self {
+            Self::Signature(s) => s.add_challenge_contribution(challenge, transcript),
+            Self::Revocation(a) => a.add_challenge_contribution(challenge, transcript),
+            Self::Equality(e) => e.add_challenge_contribution(challenge, transcript),
+            Self::Commitment(c) => c.add_challenge_contribution(challenge, transcript),
+            Self::VerifiableEncryption(v) => v.add_challenge_contribution(challenge, transcript),
+            Self::Range(r) => r.add_challenge_contribution(challenge, transcript),
+            Self::Membership(m) => m.add_challenge_contribution(challenge, transcript),
+            Self::VerifiableEncryptionDecryption(v) => {
+                v.add_challenge_contribution(challenge, transcript)
+            }
+            Self::NonMembership(n) => n.add_challenge_contribution(challenge, transcript),
+            Self::Presence(p) => p.add_challenge_contribution(challenge, transcript),
+            Self::Suspension(s) => s.add_challenge_contribution(challenge, transcript),
+            Self::AnyOf(a) => a.add_challenge_contribution(challenge, transcript),
+            Self::NotEqual(n) => n.add_challenge_contribution(challenge, transcript),
+            Self::ClaimRange(c) => c.add_challenge_contribution(challenge, transcript),
+        }
+    }
+
     /// Verify any additional proof material
     pub fn verify(&self, challenge: Scalar) -> CredxResult<()> {
         match self {
@@ -120,6 +212,12 @@ impl<S: ShortGroupSignatureScheme> ProofVerifiers<'_, '_, '_, S> {
             Self::Range(r) => r.verify(challenge),
             Self::Membership(m) => m.verify(challenge),
             Self::VerifiableEncryptionDecryption(v) => v.verify(challenge),
+            Self::NonMembership(n) => n.verify(challenge),
+            Self::Presence(p) => p.verify(challenge),
+            Self::Suspension(s) => s.verify(challenge),
+            Self::AnyOf(a) => a.verify(challenge),
+            Self::NotEqual(n) => n.verify(challenge),
+            Self::ClaimRange(c) => c.verify(challenge),
         }
     }
 }
@@ -0,0 +1,148 @@
+use crate::error::Error;
+use crate::knox::short_group_sig_core::short_group_traits::{
+    ProofOfSignatureKnowledge, ShortGroupSignatureScheme,
+};
+use crate::presentation::{AnyOfBranchProof, AnyOfProof, PresentationProofs};
+use crate::statement::{AnyOfBranch, AnyOfStatement, Statements};
+use crate::verifier::{
+    MembershipVerifier, ProofVerifier, ProofVerifiers, RevocationVerifier, SignatureVerifier,
+};
+use crate::CredxResult;
+use blsful::inner_types::Scalar;
+use merlin::Transcript;
+use uint_zigzag::Uint;
+
+pub struct AnyOfVerifier<'a, 'b, 'c, S: ShortGroupSignatureScheme> {
+    pub statement: &'a AnyOfStatement<S>,
+    pub proof: &'b AnyOfProof<S>,
+    pub nonce: &'c [u8],
+}
+
+impl<S: ShortGroupSignatureScheme> ProofVerifier for AnyOfVerifier<'_, '_, '_, S> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        self.statement.validate()?;
+        let count = self.statement.branches.len();
+        if self.proof.branches.len() != count || self.proof.challenges.len() != count {
+            return Err(Error::InvalidPresentationData(format!(
+                "any of proof '{}' expected '{}' branches",
+                self.statement.id, count
+            )));
+        }
+        // The prover can pick all but one branch challenge,
+        // the sum makes sure one branch was answered honestly
+        let sum = self
+            .proof
+            .challenges
+            .iter()
+            .fold(Scalar::ZERO, |acc, c| acc + c);
+        if sum != challenge {
+            return Err(Error::InvalidPresentationData(format!(
+                "any of proof '{}' branch challenges don't sum to the presentation challenge",
+                self.statement.id
+            )));
+        }
+        for (i, branch_challenge) in self.proof.challenges.iter().enumerate() {
+            transcript.append_message(b"any of branch", &Uint::from(i).to_vec());
+            let verifiers = branch_verifiers(
+                &self.statement.branches[i],
+                &self.proof.branches[i],
+                self.nonce,
+            )?;
+            for verifier in &verifiers {
+                verifier.add_challenge_contribution(*branch_challenge, transcript)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn verify(&self, _challenge: Scalar) -> CredxResult<()> {
+        for (i, branch_challenge) in self.proof.challenges.iter().enumerate() {
+            let verifiers = branch_verifiers(
+                &self.statement.branches[i],
+                &self.proof.branches[i],
+                self.nonce,
+            )?;
+            for verifier in &verifiers {
+                verifier.verify(*branch_challenge)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The verifiers for the signature and predicate proofs of a branch
+pub(crate) fn branch_verifiers<'a, 'b, 'c, S: ShortGroupSignatureScheme>(
+    branch: &'a AnyOfBranch<S>,
+    proof: &'b AnyOfBranchProof<S>,
+    nonce: &'c [u8],
+) -> CredxResult<Vec<ProofVerifiers<'a, 'b, 'c, S>>> {
+    if proof.signature.id != branch.signature.id || !proof.signature.disclosed_messages.is_empty() {
+        return Err(Error::InvalidPresentationData(format!(
+            "any of branch proof '{}' doesn't match signature statement '{}'",
+            proof.signature.id, branch.signature.id
+        )));
+    }
+    if proof.signature.key_epoch != branch.key_epoch() {
+        return Err(Error::InvalidPresentationData(format!(
+            "any of branch '{}' must use key epoch '{}'",
+            branch.signature.id,
+            branch.key_epoch()
+        )));
+    }
+    if proof.predicates.len() != branch.predicates.len() {
+        return Err(Error::InvalidPresentationData(format!(
+            "any of branch '{}' expected '{}' predicate proofs but found '{}'",
+            branch.signature.id,
+            branch.predicates.len(),
+            proof.predicates.len()
+        )));
+    }
+    let verifying_key = branch
+        .signature
+        .issuer
+        .verifying_key_for_epoch(proof.signature.key_epoch)?;
+    let hidden_messages = proof
+        .signature
+        .pok
+        .get_hidden_message_proofs(verifying_key, &[])?;
+
+    let mut verifiers = Vec::with_capacity(branch.predicates.len() + 1);
+    verifiers.push(SignatureVerifier::new(&branch.signature, &proof.signature).into());
+    for (statement, predicate_proof) in branch.predicates.iter().zip(&proof.predicates) {
+        match (statement, predicate_proof) {
+            (Statements::Revocation(r), PresentationProofs::Revocation(p)) => {
+                if p.epoch != branch.registry_epoch(r) {
+                    return Err(Error::InvalidPresentationData(format!(
+                        "any of branch '{}' revocation proof '{}' must use registry epoch '{}'",
+                        branch.signature.id,
+                        r.id,
+                        branch.registry_epoch(r)
+                    )));
+                }
+                r.check_signed_state_issuer(&branch.signature.issuer)?;
+                let message_proof = hidden_messages
+                    .get(&r.claim)
+                    .ok_or(Error::InvalidPresentationData(format!("revocation statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", r.id, r.claim)))?;
+                verifiers.push(RevocationVerifier::new(r, p, nonce, *message_proof).into());
+            }
+            (Statements::Membership(m), PresentationProofs::Membership(p)) => {
+                let message_proof = hidden_messages
+                    .get(&m.claim)
+                    .ok_or(Error::InvalidPresentationData(format!("membership statement with id '{}' references a claim proof '{}' that doesn't exist or was not included", m.id, m.claim)))?;
+                verifiers.push(MembershipVerifier::new(m, p, nonce, *message_proof).into());
+            }
+            (_, _) => {
+                return Err(Error::InvalidPresentationData(format!(
+                    "any of branch '{}' predicate '{}' doesn't match its proof",
+                    branch.signature.id,
+                    statement.id()
+                )))
+            }
+        }
+    }
+    Ok(verifiers)
+}
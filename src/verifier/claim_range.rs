@@ -0,0 +1,57 @@
+use crate::presentation::{claim_range_generators, ClaimRangeProof};
+use crate::statement::ClaimRangeStatement;
+use crate::verifier::{ProofVerifier, RangeBoundsVerifier};
+use crate::CredxResult;
+use blsful::inner_types::Scalar;
+use elliptic_curve::group::Curve;
+use merlin::Transcript;
+
+pub struct ClaimRangeVerifier<'a, 'b> {
+    pub statement: &'a ClaimRangeStatement,
+    pub proof: &'b ClaimRangeProof,
+    pub message_proof: Scalar,
+}
+
+impl ClaimRangeVerifier<'_, '_> {
+    fn bounds_verifier(&self) -> RangeBoundsVerifier<'_> {
+        let (g, h) = claim_range_generators();
+        RangeBoundsVerifier {
+            id: &self.statement.id,
+            lower: self.statement.lower,
+            upper: self.statement.upper,
+            message_generator: g,
+            blinder_generator: h,
+            commitment: self.proof.commitment,
+            proof: &self.proof.proof,
+        }
+    }
+}
+
+impl ProofVerifier for ClaimRangeVerifier<'_, '_> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        let (g, h) = claim_range_generators();
+        let blind_commitment = self.proof.commitment * -challenge
+            + g * self.message_proof
+            + h * self.proof.blinder_proof;
+
+        transcript.append_message(b"", self.statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            self.proof.commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        self.bounds_verifier()
+            .add_challenge_contribution(challenge, transcript)
+    }
+
+    fn verify(&self, challenge: Scalar) -> CredxResult<()> {
+        self.bounds_verifier().verify(challenge)
+    }
+}
@@ -53,7 +53,7 @@ impl<S: ShortGroupSignatureScheme> ProofVerifier for EqualityVerifier<'_, '_, '_
                             let hidden_messages = s
                                 .pok
                                 .get_hidden_message_proofs(
-                                    &sig_st.issuer.verifying_key,
+                                    sig_st.issuer.verifying_key_for_epoch(s.key_epoch)?,
                                     disclosed_messages.as_slice(),
                                 )?;
                             let hidden_message = hidden_messages
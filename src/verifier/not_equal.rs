@@ -0,0 +1,95 @@
+use crate::error::Error;
+use crate::presentation::{not_equal_generators, NotEqualProof};
+use crate::statement::{NotEqualStatement, NotEqualTarget};
+use crate::verifier::ProofVerifier;
+use crate::CredxResult;
+use blsful::inner_types::Scalar;
+use elliptic_curve::group::Curve;
+use merlin::Transcript;
+
+pub struct NotEqualVerifier<'a, 'b> {
+    pub statement: &'a NotEqualStatement,
+    pub proof: &'b NotEqualProof,
+    pub message_proof: Scalar,
+    /// The other claim message proof when comparing two claims
+    pub other_message_proof: Option<Scalar>,
+}
+
+impl ProofVerifier for NotEqualVerifier<'_, '_> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        self.statement.validate()?;
+        let (g, h) = not_equal_generators();
+        let blind_commitment = self.proof.commitment * -challenge
+            + g * self.message_proof
+            + h * self.proof.blinder_proof;
+        let (difference, other) = match (
+            &self.statement.other,
+            self.other_message_proof,
+            self.proof.other_commitment,
+            self.proof.other_blinder_proof,
+        ) {
+            (NotEqualTarget::Value(value), None, None, None) => {
+                (self.proof.commitment - g * value.to_scalar(), None)
+            }
+            (
+                NotEqualTarget::Claim { .. },
+                Some(other_message_proof),
+                Some(other_commitment),
+                Some(other_blinder_proof),
+            ) => {
+                let other_blind_commitment = other_commitment * -challenge
+                    + g * other_message_proof
+                    + h * other_blinder_proof;
+                (
+                    self.proof.commitment - other_commitment,
+                    Some((other_commitment, other_blind_commitment)),
+                )
+            }
+            (_, _, _, _) => {
+                return Err(Error::InvalidPresentationData(format!(
+                    "not equal proof '{}' does not match the statement",
+                    self.statement.id
+                )))
+            }
+        };
+        let inverse_commitment = g * -challenge
+            + difference * self.proof.inverse_proof
+            + h * self.proof.inverse_blinder_proof;
+
+        transcript.append_message(b"", self.statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            self.proof.commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        if let Some((other_commitment, other_blind_commitment)) = other {
+            transcript.append_message(
+                b"other commitment",
+                other_commitment.to_affine().to_compressed().as_slice(),
+            );
+            transcript.append_message(
+                b"other blind commitment",
+                other_blind_commitment
+                    .to_affine()
+                    .to_compressed()
+                    .as_slice(),
+            );
+        }
+        transcript.append_message(
+            b"inverse commitment",
+            inverse_commitment.to_affine().to_compressed().as_slice(),
+        );
+        Ok(())
+    }
+
+    fn verify(&self, _challenge: Scalar) -> CredxResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::claim::ClaimData;
+use crate::error::Error;
+use crate::presentation::{presence_generators, PresenceProof};
+use crate::statement::PresenceStatement;
+use crate::verifier::ProofVerifier;
+use crate::CredxResult;
+use blsful::inner_types::Scalar;
+use elliptic_curve::group::Curve;
+use merlin::Transcript;
+
+pub struct PresenceVerifier<'a, 'b> {
+    pub statement: &'a PresenceStatement,
+    pub proof: &'b PresenceProof,
+    pub message_proof: Scalar,
+}
+
+impl ProofVerifier for PresenceVerifier<'_, '_> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        let (g, h) = presence_generators();
+        let absent = ClaimData::absent_scalar();
+        let blind_commitment = self.proof.commitment * -challenge
+            + g * self.message_proof
+            + h * self.proof.blinder_proof;
+        let difference = self.proof.commitment - g * absent;
+        let presence_commitment = match (
+            self.statement.present,
+            self.proof.inverse_proof,
+            self.proof.inverse_blinder_proof,
+        ) {
+            (true, Some(inverse_proof), Some(inverse_blinder_proof)) => {
+                g * -challenge + difference * inverse_proof + h * inverse_blinder_proof
+            }
+            (false, None, None) => difference * -challenge + h * self.proof.blinder_proof,
+            (_, _, _) => {
+                return Err(Error::InvalidPresentationData(format!(
+                    "presence proof '{}' does not match the statement",
+                    self.statement.id
+                )))
+            }
+        };
+
+        transcript.append_message(b"", self.statement.id.as_bytes());
+        transcript.append_message(
+            b"commitment",
+            self.proof.commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"blind commitment",
+            blind_commitment.to_affine().to_compressed().as_slice(),
+        );
+        transcript.append_message(
+            b"presence commitment",
+            presence_commitment.to_affine().to_compressed().as_slice(),
+        );
+        Ok(())
+    }
+
+    fn verify(&self, _challenge: Scalar) -> CredxResult<()> {
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use crate::utils::{get_num_scalar, zero_center};
 use crate::verifier::ProofVerifier;
 use crate::CredxResult;
 use blsful::inner_types::{G1Projective, Scalar};
+use bulletproofs::RangeProof as RangeProofBulletproof;
 use elliptic_curve::group::Curve;
 use merlin::Transcript;
 
@@ -16,27 +17,68 @@ pub struct RangeProofVerifier<'a, 'b, 'c> {
     pub commitment: G1Projective,
 }
 
+impl RangeProofVerifier<'_, '_, '_> {
+    fn bounds_verifier(&self) -> RangeBoundsVerifier<'_> {
+        RangeBoundsVerifier {
+            id: &self.statement.id,
+            lower: self.statement.lower,
+            upper: self.statement.upper,
+            message_generator: self.commitment_statement.message_generator,
+            blinder_generator: self.commitment_statement.blinder_generator,
+            commitment: self.commitment,
+            proof: &self.proof.proof,
+        }
+    }
+}
+
 impl ProofVerifier for RangeProofVerifier<'_, '_, '_> {
+    fn add_challenge_contribution(
+        &self,
+        challenge: Scalar,
+        transcript: &mut Transcript,
+    ) -> CredxResult<()> {
+        self.bounds_verifier()
+            .add_challenge_contribution(challenge, transcript)
+    }
+
+    fn verify(&self, challenge: Scalar) -> CredxResult<()> {
+        self.bounds_verifier().verify(challenge)
+    }
+}
+
+/// Checks a range proof that the value in `commitment` is between `lower` and `upper`
+#[derive(Debug)]
+pub(crate) struct RangeBoundsVerifier<'a> {
+    pub id: &'a String,
+    pub lower: Option<isize>,
+    pub upper: Option<isize>,
+    pub message_generator: G1Projective,
+    pub blinder_generator: G1Projective,
+    pub commitment: G1Projective,
+    pub proof: &'a RangeProofBulletproof,
+}
+
+impl ProofVerifier for RangeBoundsVerifier<'_> {
     fn add_challenge_contribution(
         &self,
         _challenge: Scalar,
         transcript: &mut Transcript,
     ) -> CredxResult<()> {
-        transcript.append_message(b"", self.statement.id.as_bytes());
+        transcript.append_message(b"", self.id.as_bytes());
         transcript.append_message(
             b"used commitment",
             &self.commitment.to_affine().to_compressed(),
         );
         transcript.append_u64(b"range proof bits", 64);
 
-        match (self.statement.lower, self.statement.upper) {
+        match (self.lower, self.upper) {
             (Some(lower), Some(upper)) => {
                 let sc_lower = get_num_scalar(lower);
                 let adjusted_lower_commitment =
-                    self.commitment - self.commitment_statement.message_generator * sc_lower;
+                    self.commitment - self.message_generator * sc_lower;
                 let sc_upper = Scalar::from(u64::MAX - zero_center(upper));
                 let adjusted_upper_commitment =
-                    self.commitment + self.commitment_statement.message_generator * sc_upper;
+                    self.commitment + self.message_generator * sc_upper;
                 transcript.append_message(b"range proof version", &[3]);
                 transcript.append_message(
                     b"adjusted upper commitment",
@@ -51,7 +93,7 @@ impl ProofVerifier for RangeProofVerifier<'_, '_, '_> {
             (None, Some(upper)) => {
                 let sc_upper = Scalar::from(u64::MAX - zero_center(upper));
                 let adjusted_upper_commitment =
-                    self.commitment + self.commitment_statement.message_generator * sc_upper;
+                    self.commitment + self.message_generator * sc_upper;
                 transcript.append_message(b"range proof version", &[2]);
                 transcript.append_message(
                     b"adjusted upper commitment",
@@ -62,7 +104,7 @@ impl ProofVerifier for RangeProofVerifier<'_, '_, '_> {
             (Some(lower), None) => {
                 let sc_lower = get_num_scalar(lower);
                 let adjusted_lower_commitment =
-                    self.commitment - self.commitment_statement.message_generator * sc_lower;
+                    self.commitment - self.message_generator * sc_lower;
                 transcript.append_message(b"range proof version", &[1]);
                 transcript.append_message(
                     b"adjusted lower commitment",
@@ -70,30 +112,29 @@ impl ProofVerifier for RangeProofVerifier<'_, '_, '_> {
                 );
                 Ok(())
             }
-            (None, None) => Err(Error::InvalidPresentationData(format!("range proof has no lower or upper bounds when adding to the transcript: range_proof_verifier: {:?}", self))),
+            (None, None) => Err(Error::InvalidPresentationData(format!("range proof has no lower or upper bounds when adding to the transcript: range_bounds_verifier: {:?}", self))),
         }
     }
 
     fn verify(&self, challenge: Scalar) -> CredxResult<()> {
         let pedersen_gen = bulletproofs::PedersenGens {
-            B: self.commitment_statement.message_generator,
-            B_blinding: self.commitment_statement.blinder_generator,
+            B: self.message_generator,
+            B_blinding: self.blinder_generator,
         };
 
         let mut transcript = Transcript::new(b"credx range proof");
         transcript.append_message(b"challenge", &challenge.to_be_bytes());
 
-        match (self.statement.lower, self.statement.upper) {
+        match (self.lower, self.upper) {
             (Some(lower), Some(upper)) => {
                 let bulletproof_gens = bulletproofs::BulletproofGens::new(64, 2);
                 let sc_lower = get_num_scalar(lower);
                 let adjusted_lower_commitment =
-                    self.commitment - self.commitment_statement.message_generator * sc_lower;
+                    self.commitment - self.message_generator * sc_lower;
                 let sc_upper = Scalar::from(u64::MAX - zero_center(upper));
                 let adjusted_upper_commitment =
-                    self.commitment + self.commitment_statement.message_generator * sc_upper;
+                    self.commitment + self.message_generator * sc_upper;
                 self.proof
-                    .proof
                     .verify_multiple(
                         &bulletproof_gens,
                         &pedersen_gen,
@@ -107,9 +148,8 @@ impl ProofVerifier for RangeProofVerifier<'_, '_, '_> {
                 let bulletproof_gens = bulletproofs::BulletproofGens::new(64, 1);
                 let sc_upper = Scalar::from(u64::MAX - zero_center(upper));
                 let adjusted_upper_commitment =
-                    self.commitment + self.commitment_statement.message_generator * sc_upper;
+                    self.commitment + self.message_generator * sc_upper;
                 self.proof
-                    .proof
                     .verify_single(
                         &bulletproof_gens,
                         &pedersen_gen,
@@ -123,9 +163,8 @@ impl ProofVerifier for RangeProofVerifier<'_, '_, '_> {
                 let bulletproof_gens = bulletproofs::BulletproofGens::new(64, 1);
                 let sc_lower = get_num_scalar(lower);
                 let adjusted_lower_commitment =
-                    self.commitment - self.commitment_statement.message_generator * sc_lower;
+                    self.commitment - self.message_generator * sc_lower;
                 self.proof
-                    .proof
                     .verify_single(
                         &bulletproof_gens,
                         &pedersen_gen,
@@ -135,7 +174,7 @@ impl ProofVerifier for RangeProofVerifier<'_, '_, '_> {
                     )
                     .map_err(|_| Error::InvalidBulletproofRange)
             }
-            (None, None) => Err(Error::InvalidPresentationData(format!("range proof has no lower or upper bounds when verifying the range proof: range_proof_verifier: {:?}", self))),
+            (None, None) => Err(Error::InvalidPresentationData(format!("range proof has no lower or upper bounds when verifying the range proof: range_bounds_verifier: {:?}", self))),
         }
     }
 }
@@ -6,6 +6,7 @@ use crate::verifier::ProofVerifier;
 use crate::CredxResult;
 use blsful::inner_types::Scalar;
 use merlin::Transcript;
+use uint_zigzag::Uint;
 
 pub struct RevocationVerifier<'a, 'b> {
     statement: &'a RevocationStatement,
@@ -37,9 +38,12 @@ impl ProofVerifier for RevocationVerifier<'_, '_> {
         challenge: Scalar,
         transcript: &mut Transcript,
     ) -> CredxResult<()> {
+        let epoch = self.accumulator_proof.epoch;
+        let accumulator = self.statement.accumulator_for_epoch(epoch)?;
+        transcript.append_message(b"registry epoch", &Uint::from(epoch).to_vec());
         self.params.add_to_transcript(transcript);
         let finalized = self.accumulator_proof.proof.finalize(
-            self.statement.accumulator,
+            accumulator,
             self.params,
             self.statement.verification_key,
             Element(challenge),
@@ -7,6 +7,7 @@ use crate::verifier::ProofVerifier;
 use crate::CredxResult;
 use blsful::inner_types::Scalar;
 use merlin::Transcript;
+use uint_zigzag::Uint;
 
 pub struct SignatureVerifier<'a, 'b, S: ShortGroupSignatureScheme> {
     statement: &'a SignatureStatement<S>,
@@ -38,8 +39,16 @@ impl<S: ShortGroupSignatureScheme> ProofVerifier for SignatureVerifier<'_, '_, S
         challenge: Scalar,
         transcript: &mut Transcript,
     ) -> CredxResult<()> {
+        let verifying_key = self
+            .statement
+            .issuer
+            .verifying_key_for_epoch(self.signature_proof.key_epoch)?;
+        transcript.append_message(
+            b"issuer key epoch",
+            &Uint::from(self.signature_proof.key_epoch).to_vec(),
+        );
         self.signature_proof.pok.add_proof_contribution(
-            &self.statement.issuer.verifying_key,
+            verifying_key,
             &self.disclosed_messages,
             challenge,
             transcript,
@@ -48,10 +57,12 @@ impl<S: ShortGroupSignatureScheme> ProofVerifier for SignatureVerifier<'_, '_, S
     }
 
     fn verify(&self, challenge: Scalar) -> CredxResult<()> {
-        self.signature_proof.pok.verify(
-            &self.statement.issuer.verifying_key,
-            &self.disclosed_messages,
-            challenge,
-        )
+        let verifying_key = self
+            .statement
+            .issuer
+            .verifying_key_for_epoch(self.signature_proof.key_epoch)?;
+        self.signature_proof
+            .pok
+            .verify(verifying_key, &self.disclosed_messages, challenge)
     }
 }
@@ -0,0 +1,315 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::presentation::{
+    Presentation, PresentationCredential, PresentationProofs, PresentationSchema,
+};
+use credx::statement::{
+    AnyOfBranch, AnyOfStatement, RegistryEpochPolicy, RevocationStatement, SignatureStatement,
+};
+use credx::CredxResult;
+use indexmap::{indexmap, IndexMap};
+use maplit::btreeset;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn credential_schema(label: &str) -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    CredentialSchema::new(Some(label), None, &[], &schema_claims)
+}
+
+fn branch<S: ShortGroupSignatureScheme>(id: &str, issuer: &IssuerPublic<S>) -> AnyOfBranch<S> {
+    AnyOfBranch {
+        signature: SignatureStatement {
+            disclosed: btreeset! {},
+            id: id.to_string(),
+            issuer: issuer.clone(),
+        },
+        predicates: vec![RevocationStatement {
+            id: format!("{} revocation", id),
+            reference_id: id.to_string(),
+            accumulator: issuer.revocation_registry,
+            verification_key: issuer.revocation_verifying_key,
+            claim: 0,
+            epochs: RegistryEpochPolicy::Current,
+            signed_states: None,
+        }
+        .into()],
+    }
+}
+
+fn any_of_schema<S: ShortGroupSignatureScheme>(
+    passport: &IssuerPublic<S>,
+    license: &IssuerPublic<S>,
+) -> PresentationSchema<S> {
+    let any_of = AnyOfStatement {
+        id: "identity document".to_string(),
+        branches: vec![branch("passport", passport), branch("license", license)],
+    };
+    PresentationSchema::new(&[any_of.into()])
+}
+
+fn create_and_verify<S: ShortGroupSignatureScheme>(
+    schema: &PresentationSchema<S>,
+    credentials: &IndexMap<String, PresentationCredential<S>>,
+) -> CredxResult<Presentation<S>> {
+    let nonce = b"any of nonce";
+    let presentation = Presentation::create(credentials, schema, nonce)?;
+    presentation.verify(schema, nonce)?;
+    let json = serde_json::to_string(&presentation).unwrap();
+    let presentation: Presentation<S> = serde_json::from_str(&json).unwrap();
+    presentation.verify(schema, nonce)?;
+    Ok(presentation)
+}
+
+fn proof_shape<S: ShortGroupSignatureScheme>(
+    presentation: &Presentation<S>,
+) -> Vec<(usize, Vec<usize>)> {
+    match &presentation.proofs["identity document"] {
+        PresentationProofs::AnyOf(proof) => proof
+            .branches
+            .iter()
+            .map(|b| {
+                let epochs = b
+                    .predicates
+                    .iter()
+                    .map(|p| match p {
+                        PresentationProofs::Revocation(r) => r.epoch,
+                        p => panic!("expected a revocation proof, found {:?}", p),
+                    })
+                    .collect();
+                (b.signature.key_epoch, epochs)
+            })
+            .collect(),
+        p => panic!("expected an any of proof, found {:?}", p),
+    }
+}
+
+#[test]
+fn any_of_bbs() {
+    setup();
+    let res = test_any_of::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn any_of_ps() {
+    setup();
+    let res = test_any_of::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn any_of_epochs_bbs() {
+    setup();
+    let res = test_any_of_epochs::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn any_of_epochs_ps() {
+    setup();
+    let res = test_any_of_epochs::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn any_of_rejects_keys_without_simulation_element() {
+    setup();
+    let res = test_keys_without_simulation_element();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_any_of_epochs<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (_, mut passport_issuer) = Issuer::<S>::new(&credential_schema("Passport")?);
+    let (_, mut license_issuer) = Issuer::<S>::new(&credential_schema("License")?);
+    let old_passport = passport_issuer.sign_credential(&[
+        RevocationClaim::from("passport-1").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    let mut license = license_issuer.sign_credential(&[
+        RevocationClaim::from("license-1").into(),
+        HashedClaim::from("John Doe").into(),
+    ])?;
+    license_issuer.sign_credential(&[
+        RevocationClaim::from("license-2").into(),
+        HashedClaim::from("Richard Roe").into(),
+    ])?;
+
+    // The passport issuer rotates its key and the license registry moves on
+    passport_issuer.rotate_signing_key()?;
+    let passport = passport_issuer.sign_credential(&[
+        RevocationClaim::from("passport-2").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    let update = license_issuer.revoke_credentials(&[RevocationClaim::from("license-2")])?;
+    let schema = any_of_schema(
+        &IssuerPublic::from(&passport_issuer),
+        &IssuerPublic::from(&license_issuer),
+    );
+
+    // Simulated branches use the statement epochs so a credential
+    // from an older key or registry epoch would stand out
+    assert!(create_and_verify(
+        &schema,
+        &indexmap! { "passport".to_string() => old_passport.credential.into() },
+    )
+    .is_err());
+    assert!(create_and_verify(
+        &schema,
+        &indexmap! { "license".to_string() => license.credential.clone().into() },
+    )
+    .is_err());
+
+    license.apply_registry_updates(&[update])?;
+    let with_passport = create_and_verify(
+        &schema,
+        &indexmap! { "passport".to_string() => passport.credential.into() },
+    )?;
+    let with_license = create_and_verify(
+        &schema,
+        &indexmap! { "license".to_string() => license.credential.into() },
+    )?;
+    let shape = proof_shape(&with_passport);
+    assert_eq!(shape[0].0, 1);
+    assert_eq!(shape, proof_shape(&with_license));
+    Ok(())
+}
+
+fn test_keys_without_simulation_element() -> CredxResult<()> {
+    let (passport_public, mut passport_issuer) =
+        Issuer::<BbsScheme>::new(&credential_schema("Passport")?);
+    let (license_public, _) = Issuer::<BbsScheme>::new(&credential_schema("License")?);
+    let passport = passport_issuer.sign_credential(&[
+        RevocationClaim::from("passport-1").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+
+    // Keys serialized before the simulation element was added still load
+    let mut json = serde_json::to_value(&passport_public).unwrap();
+    json["verifying_key"]
+        .as_object_mut()
+        .unwrap()
+        .remove("h_x")
+        .unwrap();
+    let old_public: IssuerPublic<BbsScheme> =
+        serde_json::from_str(&serde_json::to_string(&json).unwrap()).unwrap();
+    let credentials = indexmap! { "passport".to_string() => passport.credential.into() };
+    let schema = PresentationSchema::new(&[SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "passport".to_string(),
+        issuer: old_public.clone(),
+    }
+    .into()]);
+    create_and_verify(&schema, &credentials)?;
+
+    // but can't be used in any of statements
+    let schema = any_of_schema(&old_public, &license_public);
+    assert!(Presentation::create(&credentials, &schema, b"any of nonce").is_err());
+    Ok(())
+}
+
+fn test_any_of<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (passport_public, mut passport_issuer) = Issuer::<S>::new(&credential_schema("Passport")?);
+    let (license_public, mut license_issuer) = Issuer::<S>::new(&credential_schema("License")?);
+    let passport = passport_issuer.sign_credential(&[
+        RevocationClaim::from("passport-1").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    let license = license_issuer.sign_credential(&[
+        RevocationClaim::from("license-1").into(),
+        HashedClaim::from("John Doe").into(),
+    ])?;
+
+    let schema = any_of_schema(&passport_public, &license_public);
+    let with_passport = create_and_verify(
+        &schema,
+        &indexmap! { "passport".to_string() => passport.credential.clone().into() },
+    )?;
+    let with_license = create_and_verify(
+        &schema,
+        &indexmap! { "license".to_string() => license.credential.clone().into() },
+    )?;
+    // Both presentations have the same shape so the verifier can't tell the branch
+    for presentation in [&with_passport, &with_license] {
+        assert!(presentation.disclosed_messages.is_empty());
+        match &presentation.proofs["identity document"] {
+            PresentationProofs::AnyOf(proof) => {
+                assert_eq!(proof.challenges.len(), 2);
+                assert_eq!(proof.branches[0].signature.key_epoch, 0);
+                assert_eq!(proof.branches[1].signature.key_epoch, 0);
+            }
+            p => panic!("expected an any of proof, found {:?}", p),
+        }
+    }
+
+    // Moving challenge between branches breaks the proof
+    let mut tampered = with_license.clone();
+    if let Some(PresentationProofs::AnyOf(proof)) = tampered.proofs.get_mut("identity document") {
+        proof.challenges.swap(0, 1);
+    }
+    assert!(tampered.verify(&schema, b"any of nonce").is_err());
+    assert!(with_license.verify(&schema, b"another nonce").is_err());
+
+    // A holder with neither credential can't prove the statement
+    let (_, mut other_issuer) = Issuer::<S>::new(&credential_schema("Passport")?);
+    let other = other_issuer.sign_credential(&[
+        RevocationClaim::from("passport-2").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    assert!(Presentation::create(&IndexMap::new(), &schema, b"any of nonce").is_err());
+    assert!(create_and_verify(
+        &schema,
+        &indexmap! { "passport".to_string() => other.credential.into() },
+    )
+    .is_err());
+
+    // A revoked license no longer satisfies its branch
+    license_issuer.revoke_credentials(&[RevocationClaim::from("license-1")])?;
+    let schema = any_of_schema(&passport_public, &IssuerPublic::from(&license_issuer));
+    assert!(create_and_verify(
+        &schema,
+        &indexmap! { "license".to_string() => license.credential.into() },
+    )
+    .is_err());
+    create_and_verify(
+        &schema,
+        &indexmap! { "passport".to_string() => passport.credential.clone().into() },
+    )?;
+
+    // Branches can't disclose claims
+    let mut disclosing = branch("passport", &passport_public);
+    disclosing.signature.disclosed = btreeset! {"name".to_string()};
+    let schema = PresentationSchema::new(&[AnyOfStatement {
+        id: "identity document".to_string(),
+        branches: vec![disclosing, branch("license", &license_public)],
+    }
+    .into()]);
+    assert!(Presentation::create(
+        &indexmap! { "passport".to_string() => passport.credential.into() },
+        &schema,
+        b"any of nonce"
+    )
+    .is_err());
+    Ok(())
+}
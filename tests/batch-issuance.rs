@@ -0,0 +1,145 @@
+use credx::claim::{ClaimData, ClaimType, ClaimValidator, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialBundle, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{
+    RegistryEpochPolicy, RevocationStatement, SignatureStatement, SuspensionStatement,
+};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn student(i: usize) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from(format!("student-{:04}", i)).into(),
+        HashedClaim::from(format!("Student {}", i)).into(),
+    ]
+}
+
+fn prove(
+    issuer_public: &IssuerPublic<BbsScheme>,
+    bundle: &CredentialBundle<BbsScheme>,
+) -> CredxResult<()> {
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let acc_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
+    };
+    let sus_st = SuspensionStatement {
+        id: "suspension".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.suspension_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema =
+        PresentationSchema::new(&[sig_st.into(), acc_st.into(), sus_st.into()]);
+    let credentials = indexmap! { "sig".to_string() => bundle.credential.clone().into() };
+    let nonce = b"batch issuance nonce";
+    let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+    presentation.verify(&presentation_schema, nonce)
+}
+
+#[test]
+fn batch_issuance() {
+    setup();
+    let res = test_batch_issuance();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_batch_issuance() -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::Length {
+                min: Some(1),
+                max: Some(32),
+            }],
+            ..Default::default()
+        },
+    ];
+    let schema = CredentialSchema::new(Some("Student"), None, &[], &schema_claims)?;
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let first = issuer.sign_credential(&student(0))?;
+    let suspended_id = issuer.list_issued()[0].id.clone();
+
+    let batch = (1..=24).map(student).collect::<Vec<_>>();
+    let bundles = issuer.sign_credentials_batch(&batch)?;
+    assert_eq!(bundles.len(), batch.len());
+    assert_eq!(issuer.list_issued().len(), batch.len() + 1);
+    assert_eq!(
+        issuer.revocation_registry.store.active.len(),
+        batch.len() + 1
+    );
+    // Issuing doesn't change the registry values
+    assert_eq!(issuer.revocation_registry.epoch, 0);
+
+    let issuer_public = IssuerPublic::from(&issuer);
+    prove(&issuer_public, &first)?;
+    for (bundle, claims) in bundles.iter().zip(&batch) {
+        assert_eq!(&bundle.credential.claims, claims);
+        prove(&issuer_public, bundle)?;
+    }
+
+    // A single invalid credential fails the whole batch
+    let mut invalid = (25..=28).map(student).collect::<Vec<_>>();
+    invalid[2][1] = HashedClaim::from("").into();
+    assert!(issuer.sign_credentials_batch(&invalid).is_err());
+    issuer.revoke_credentials(&[RevocationClaim::from("student-0003")])?;
+    let mut revoked = (25..=28).map(student).collect::<Vec<_>>();
+    revoked.push(student(3));
+    assert!(issuer.sign_credentials_batch(&revoked).is_err());
+    issuer.suspend_credential(&suspended_id)?;
+    let mut suspended = (25..=28).map(student).collect::<Vec<_>>();
+    suspended.push(student(0));
+    assert!(issuer.sign_credentials_batch(&suspended).is_err());
+    assert_eq!(issuer.list_issued().len(), batch.len() + 1);
+    assert!(!issuer.revocation_registry.contains("student-0025"));
+
+    assert!(issuer.sign_credentials_batch(&[])?.is_empty());
+
+    // Issuer chosen ids can be used to manage the credentials
+    let ids = ["enrolment-25", "enrolment-26"].map(String::from);
+    let batch = (25..=26).map(student).collect::<Vec<_>>();
+    assert!(issuer
+        .sign_credentials_batch_with_ids(&ids[..1], &batch)
+        .is_err());
+    assert!(issuer
+        .sign_credentials_batch_with_ids(&[ids[0].clone(), ids[0].clone()], &batch)
+        .is_err());
+    assert!(issuer
+        .sign_credentials_batch_with_ids(&[suspended_id, ids[1].clone()], &batch)
+        .is_err());
+    assert!(!issuer.revocation_registry.contains("student-0025"));
+    issuer.sign_credentials_batch_with_ids(&ids, &batch)?;
+    assert_eq!(
+        issuer.ledger.get(&ids[1]).unwrap().revocation_value,
+        "student-0026"
+    );
+    issuer.revoke_credential(&ids[0])?;
+    assert!(!issuer.revocation_registry.is_active("student-0025"));
+    Ok(())
+}
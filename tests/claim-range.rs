@@ -0,0 +1,200 @@
+use chrono::NaiveDate;
+use credx::claim::{
+    ClaimData, ClaimType, DateClaim, DecimalClaim, HashedClaim, NumberClaim, RevocationClaim,
+};
+use credx::create_domain_proof_generator;
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::short_group_traits::{
+    ProofOfSignatureKnowledge, ShortGroupSignatureScheme,
+};
+use credx::presentation::{
+    Presentation, PresentationCredential, PresentationProofs, PresentationSchema,
+};
+use credx::statement::{ClaimRangeStatement, SignatureStatement, Statements};
+use credx::CredxResult;
+use indexmap::{indexmap, IndexMap};
+use maplit::btreeset;
+use std::str::FromStr;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn credential_schema() -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Number,
+            label: "credit score".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Date,
+            label: "dob".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema::decimal("balance", 2),
+    ];
+    CredentialSchema::new(Some("Claim range"), None, &[], &schema_claims)
+}
+
+fn create_and_verify<S: ShortGroupSignatureScheme>(
+    schema: &PresentationSchema<S>,
+    credentials: &IndexMap<String, PresentationCredential<S>>,
+) -> CredxResult<Presentation<S>> {
+    let nonce = b"claim range nonce";
+    let presentation = Presentation::create(credentials, schema, nonce)?;
+    presentation.verify(schema, nonce)?;
+    let bytes = serde_bare::to_vec(&presentation).unwrap();
+    let presentation: Presentation<S> = serde_bare::from_slice(&bytes).unwrap();
+    presentation.verify(schema, nonce)?;
+    Ok(presentation)
+}
+
+#[test]
+fn claim_range_bbs() {
+    setup();
+    let res = test_claim_range::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn claim_range_ps() {
+    setup();
+    let res = test_claim_range::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_claim_range<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&credential_schema()?);
+    let credential = issuer.sign_credential(&[
+        RevocationClaim::from("claim-range-1").into(),
+        HashedClaim::from("John Doe").into(),
+        NumberClaim::from(720).into(),
+        DateClaim::from(NaiveDate::from_ymd_opt(1990, 5, 17).unwrap()).into(),
+        DecimalClaim::from_str("1234.5")?.into(),
+    ])?;
+    let credentials = indexmap! { "sig".to_string() => credential.credential.into() };
+    let signature = |disclosed| -> Statements<S> {
+        SignatureStatement {
+            disclosed,
+            id: "sig".to_string(),
+            issuer: issuer_public.clone(),
+        }
+        .into()
+    };
+    let today = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+    // Number and date ranges without commitment statements
+    let schema = PresentationSchema::new(&[
+        signature(btreeset! {"name".to_string()}),
+        ClaimRangeStatement::new("score", "sig", "credit score", Some(700), Some(850)).into(),
+        ClaimRangeStatement::older_than_as_of("adult", "sig", "dob", 18, today)?.into(),
+    ]);
+    let presentation = create_and_verify(&schema, &credentials)?;
+    assert_eq!(presentation.proofs.len(), 3);
+
+    // The commitment must not be blinded with the claim's schnorr nonce
+    // otherwise C - h * s_m = m * (g - c * h) leaks the claim
+    let (PresentationProofs::Signature(sig), PresentationProofs::ClaimRange(score)) =
+        (&presentation.proofs["sig"], &presentation.proofs["score"])
+    else {
+        panic!("unexpected proof types");
+    };
+    let disclosed: Vec<_> = sig
+        .disclosed_messages
+        .iter()
+        .map(|(i, m)| (*i, *m))
+        .collect();
+    let s_m = sig.pok.get_hidden_message_proofs(
+        issuer_public.verifying_key_for_epoch(sig.key_epoch)?,
+        &disclosed,
+    )?[&2];
+    let g = create_domain_proof_generator(b"credx claim range message generator");
+    let h = create_domain_proof_generator(b"credx claim range blinder generator");
+    let m = ClaimData::from(NumberClaim::from(720)).to_scalar();
+    let c = presentation.challenge;
+    assert_ne!(score.commitment - h * s_m, (g - h * c) * m);
+
+    // Decimal bounds use the scale from the issuer schema
+    let sig = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let balance = |lower: &str| -> CredxResult<Statements<S>> {
+        let lower = Some(DecimalClaim::from_str(lower)?);
+        Ok(ClaimRangeStatement::decimal_range("balance", &sig, "balance", lower, None)?.into())
+    };
+    let schema = PresentationSchema::new(&[sig.clone().into(), balance("1234.50")?]);
+    create_and_verify(&schema, &credentials)?;
+    let schema = PresentationSchema::new(&[sig.clone().into(), balance("1234.51")?]);
+    assert!(create_and_verify(&schema, &credentials).is_err());
+    assert!(balance("1234.501").is_err());
+    assert!(
+        ClaimRangeStatement::decimal_range("balance", &sig, "credit score", None, None).is_err()
+    );
+
+    // The verifier's bounds are bound to the proof
+    let other_bounds = PresentationSchema::new(&[
+        signature(btreeset! {"name".to_string()}),
+        ClaimRangeStatement::new("score", "sig", "credit score", Some(750), Some(850)).into(),
+        ClaimRangeStatement::older_than_as_of("adult", "sig", "dob", 18, today)?.into(),
+    ]);
+    assert!(presentation
+        .verify(&other_bounds, b"claim range nonce")
+        .is_err());
+
+    // The claim is outside the range
+    let schema = PresentationSchema::new(&[
+        signature(btreeset! {}),
+        ClaimRangeStatement::new("score", "sig", "credit score", Some(750), None).into(),
+    ]);
+    assert!(create_and_verify(&schema, &credentials).is_err());
+    let schema = PresentationSchema::new(&[
+        signature(btreeset! {}),
+        ClaimRangeStatement::younger_than_as_of("young", "sig", "dob", 21, today)?.into(),
+    ]);
+    assert!(create_and_verify(&schema, &credentials).is_err());
+
+    // Unknown, non-numeric and revealed claims are rejected
+    for (disclosed, label) in [
+        (btreeset! {}, "age"),
+        (btreeset! {}, "name"),
+        (btreeset! {"credit score".to_string()}, "credit score"),
+    ] {
+        let schema = PresentationSchema::new(&[
+            signature(disclosed),
+            ClaimRangeStatement::new("score", "sig", label, Some(700), None).into(),
+        ]);
+        assert!(create_and_verify(&schema, &credentials).is_err());
+    }
+
+    // The signature statement must exist
+    let schema = PresentationSchema::new(&[
+        signature(btreeset! {}),
+        ClaimRangeStatement::new("score", "other", "credit score", Some(700), None).into(),
+    ]);
+    assert!(create_and_verify(&schema, &credentials).is_err());
+    Ok(())
+}
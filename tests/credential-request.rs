@@ -0,0 +1,133 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialRequest, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::CredxResult;
+use maplit::btreemap;
+
+const CRED_ID: &str = "6c1f0b7e-2d4a-4b8e-9c3f-5a7d1e9b2c40";
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn schema_claims() -> Vec<ClaimSchema> {
+    vec![
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "country".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            default: Some(HashedClaim::from("AU").into()),
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Number,
+            label: "age".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ]
+}
+
+#[test]
+fn sign_credential_map() {
+    setup();
+    let res = test_sign_credential_map();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_sign_credential_map() -> CredxResult<()> {
+    let schema = CredentialSchema::new(Some("Request"), None, &[], &schema_claims())?;
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    let bundle = issuer.sign_credential_map(&btreemap! {
+        "age".to_string() => NumberClaim::from(42).into(),
+        "name".to_string() => HashedClaim::from("John Doe").into(),
+        "identifier".to_string() => RevocationClaim::from(CRED_ID).into(),
+    })?;
+    let expected: Vec<ClaimData> = vec![
+        RevocationClaim::from(CRED_ID).into(),
+        HashedClaim::from("John Doe").into(),
+        HashedClaim::from("AU").into(),
+        NumberClaim::from(42).into(),
+    ];
+    assert_eq!(bundle.credential.claims, expected);
+
+    let res = issuer.sign_credential_map(&btreemap! {
+        "identifier".to_string() => RevocationClaim::from(CRED_ID).into(),
+        "nmae".to_string() => HashedClaim::from("John Doe").into(),
+        "country".to_string() => HashedClaim::from("NZ").into(),
+    });
+    match res {
+        Err(Error::ClaimValidation(report)) => {
+            assert_eq!(report.labels(), vec!["name", "age", "nmae"]);
+            let message = report.to_string();
+            assert!(message.contains("claim 'age': claim is missing and has no default"));
+            assert!(message.contains("claim 'nmae': claim is not in the schema"));
+        }
+        res => panic!("expected a validation report, got {:?}", res),
+    }
+    Ok(())
+}
+
+#[test]
+fn sign_credential_request() {
+    setup();
+    let res = test_sign_credential_request();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_sign_credential_request() -> CredxResult<()> {
+    let schema = CredentialSchema::new(Some("Request"), None, &[], &schema_claims())?;
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    let request = CredentialRequest::new(
+        &schema,
+        btreemap! {
+            "identifier".to_string() => RevocationClaim::from(CRED_ID).into(),
+            "name".to_string() => HashedClaim::from("Jane Doe").into(),
+            "country".to_string() => HashedClaim::from("NZ").into(),
+            "age".to_string() => NumberClaim::from(37).into(),
+        },
+    );
+    let json = serde_json::to_string(&request).unwrap();
+    let request: CredentialRequest = serde_json::from_str(&json).unwrap();
+    let bundle = issuer.sign_credential_request(&request)?;
+    assert_eq!(bundle.credential.claims[2], HashedClaim::from("NZ").into());
+
+    let other_schema = CredentialSchema::new(Some("Request"), None, &[], &schema_claims())?;
+    let request = CredentialRequest::new(&other_schema, request.claims);
+    assert!(issuer.sign_credential_request(&request).is_err());
+    Ok(())
+}
+
+#[test]
+fn default_must_match_claim_type() {
+    let mut claims = schema_claims();
+    claims[3].default = Some(HashedClaim::from("42").into());
+    assert!(CredentialSchema::new(Some("Request"), None, &[], &claims).is_err());
+}
+
+#[test]
+fn revocation_claims_cannot_have_a_default() {
+    let mut claims = schema_claims();
+    claims[0].default = Some(RevocationClaim::from(CRED_ID).into());
+    assert!(CredentialSchema::new(Some("Request"), None, &[], &claims).is_err());
+}
@@ -0,0 +1,247 @@
+use credx::claim::{ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{CredentialSelector, Presentation, PresentationSchema, SelectionReason};
+use credx::statement::{AnyOfBranch, AnyOfStatement, SignatureStatement};
+use credx::CredxResult;
+use maplit::btreeset;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn schema(label: &str, claim_label: &str, claim_type: ClaimType) -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type,
+            label: claim_label.to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    CredentialSchema::new(Some(label), None, &[], &schema_claims)
+}
+
+#[test]
+fn credential_selection() {
+    setup();
+    let res = test_credential_selection();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_credential_selection() -> CredxResult<()> {
+    let id_schema = schema("Identity", "name", ClaimType::Hashed)?;
+    let degree_schema = schema("Degree", "year", ClaimType::Number)?;
+    let (government_public, mut government) = Issuer::<BbsScheme>::new(&id_schema);
+    let (_, mut other_government) = Issuer::<BbsScheme>::new(&id_schema);
+    let (university_public, mut university) = Issuer::<BbsScheme>::new(&degree_schema);
+
+    let wallet = vec![
+        university.sign_credential(&[
+            RevocationClaim::from("degree-1").into(),
+            NumberClaim::from(2015).into(),
+        ])?,
+        government.sign_credential(&[
+            RevocationClaim::from("passport-1").into(),
+            HashedClaim::from("John Doe").into(),
+        ])?,
+        other_government.sign_credential(&[
+            RevocationClaim::from("passport-2").into(),
+            HashedClaim::from("John Doe").into(),
+        ])?,
+        government.sign_credential(&[
+            RevocationClaim::from("license-1").into(),
+            HashedClaim::from("John Doe").into(),
+        ])?,
+    ];
+    let selector = CredentialSelector::new(&wallet);
+
+    let id_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "identity".to_string(),
+        issuer: government_public.clone(),
+    };
+    let degree_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "degree".to_string(),
+        issuer: university_public.clone(),
+    };
+    assert_eq!(selector.candidates(&id_st), vec![1, 3]);
+    let presentation_schema = PresentationSchema::new(&[id_st.into(), degree_st.clone().into()]);
+    let assignments = selector.select(&presentation_schema)?;
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(assignments[0].selections["identity"], 1);
+    assert_eq!(assignments[1].selections["identity"], 3);
+    for assignment in &assignments {
+        assert_eq!(assignment.selections["degree"], 0);
+        let credentials = selector.credentials(assignment);
+        let nonce = b"credential selection nonce";
+        let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+        presentation.verify(&presentation_schema, nonce)?;
+    }
+
+    // Unsatisfiable statements explain why each credential was rejected
+    let missing_st = SignatureStatement {
+        disclosed: btreeset! {"address".to_string()},
+        id: "address".to_string(),
+        issuer: government_public.clone(),
+    };
+    let presentation_schema = PresentationSchema::new(&[missing_st.into(), degree_st.into()]);
+    match selector.select(&presentation_schema) {
+        Err(Error::CredentialSelection(report)) => {
+            assert_eq!(report.failures.len(), 1);
+            let failure = &report.failures[0];
+            assert_eq!(failure.statement_id, "address");
+            assert_eq!(
+                failure.rejected[0].1,
+                SelectionReason::WrongIssuer {
+                    expected: government_public.id.clone(),
+                    found: university_public.id.clone(),
+                }
+            );
+            assert_eq!(
+                failure.rejected[1].1,
+                SelectionReason::MissingClaim {
+                    label: "address".to_string()
+                }
+            );
+            assert!(report.to_string().contains("credential 3"));
+        }
+        res => panic!("expected a selection report, found {:?}", res),
+    }
+
+    // Credentials signed with a retired key are not candidates
+    government.rotate_signing_key()?;
+    let government_public = government.retire_key_epoch(0)?;
+    let id_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "identity".to_string(),
+        issuer: government_public,
+    };
+    assert!(selector.candidates(&id_st).is_empty());
+    match selector.select(&PresentationSchema::new(&[id_st.into()])) {
+        Err(Error::CredentialSelection(report)) => {
+            assert_eq!(
+                report.failures[0].rejected[1].1,
+                SelectionReason::RetiredKey { epoch: 0 }
+            );
+        }
+        res => panic!("expected a selection report, found {:?}", res),
+    }
+    Ok(())
+}
+
+#[test]
+fn credential_selection_paths_and_any_of() {
+    setup();
+    let res = test_credential_selection_paths_and_any_of();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_credential_selection_paths_and_any_of() -> CredxResult<()> {
+    let address_schema = schema("Address", "/address/street", ClaimType::Hashed)?;
+    let degree_schema = schema("Degree", "year", ClaimType::Number)?;
+    let (address_public, mut address_issuer) = Issuer::<BbsScheme>::new(&address_schema);
+    let (university_public, mut university) = Issuer::<BbsScheme>::new(&degree_schema);
+    let wallet = vec![
+        address_issuer.sign_credential(&[
+            RevocationClaim::from("address-1").into(),
+            HashedClaim::from("1 Main St").into(),
+        ])?,
+        university.sign_credential(&[
+            RevocationClaim::from("degree-1").into(),
+            NumberClaim::from(2015).into(),
+        ])?,
+    ];
+    let selector = CredentialSelector::new(&wallet);
+
+    // Disclosing an object path matches the claims under it
+    let signature = |disclosed: &str| SignatureStatement {
+        disclosed: btreeset! {disclosed.to_string()},
+        id: "address".to_string(),
+        issuer: address_public.clone(),
+    };
+    assert_eq!(selector.candidates(&signature("/address")), vec![0]);
+    assert_eq!(selector.candidates(&signature("/address/street")), vec![0]);
+    assert!(selector.candidates(&signature("/addr")).is_empty());
+    assert!(selector.candidates(&signature("address")).is_empty());
+
+    // Any of statements are satisfied by a credential for any branch
+    let branch = |id: &str, issuer| AnyOfBranch {
+        signature: SignatureStatement {
+            disclosed: btreeset! {},
+            id: id.to_string(),
+            issuer,
+        },
+        predicates: vec![],
+    };
+    let any_of = AnyOfStatement {
+        id: "evidence".to_string(),
+        branches: vec![
+            branch("address", address_public.clone()),
+            branch("degree", university_public.clone()),
+        ],
+    };
+    let presentation_schema = PresentationSchema::new(&[any_of.into()]);
+    let assignments = selector.select(&presentation_schema)?;
+    assert_eq!(assignments.len(), 2);
+    assert_eq!(assignments[0].selections["address"], 0);
+    assert_eq!(assignments[1].selections["degree"], 1);
+    for assignment in &assignments {
+        assert_eq!(assignment.selections.len(), 1);
+        let credentials = selector.credentials(assignment);
+        let nonce = b"credential selection nonce";
+        let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+        presentation.verify(&presentation_schema, nonce)?;
+    }
+    match selector.select(&PresentationSchema::new(&[AnyOfStatement {
+        id: "evidence".to_string(),
+        branches: vec![branch("other", Issuer::<BbsScheme>::new(&degree_schema).0)],
+    }
+    .into()]))
+    {
+        Err(Error::CredentialSelection(report)) => {
+            assert_eq!(report.failures[0].statement_id, "evidence");
+            assert_eq!(report.failures[0].rejected.len(), 2);
+        }
+        res => panic!("expected a selection report, found {:?}", res),
+    }
+
+    // Any of branches only prove with the current key epoch
+    // while plain signature statements accept older unretired keys
+    let rotated_public = address_issuer.rotate_signing_key()?;
+    let rotated = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "address".to_string(),
+        issuer: rotated_public.clone(),
+    };
+    assert_eq!(selector.candidates(&rotated), vec![0]);
+    match selector.select(&PresentationSchema::new(&[AnyOfStatement {
+        id: "evidence".to_string(),
+        branches: vec![branch("address", rotated_public)],
+    }
+    .into()]))
+    {
+        Err(Error::CredentialSelection(report)) => {
+            assert_eq!(
+                report.failures[0].rejected[0].1,
+                SelectionReason::WrongKeyEpoch {
+                    expected: 1,
+                    found: 0
+                }
+            );
+        }
+        res => panic!("expected a selection report, found {:?}", res),
+    }
+    Ok(())
+}
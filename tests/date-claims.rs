@@ -0,0 +1,209 @@
+use blsful::inner_types::*;
+use chrono::{DateTime, NaiveDate, Utc};
+use credx::claim::{
+    ClaimData, ClaimType, ClaimValidator, DateClaim, DateTimeClaim, HashedClaim, RevocationClaim,
+};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{CommitmentStatement, RangeStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+use rand_core::RngCore;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn date_schema() -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Date,
+            label: "dob".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::DateRange {
+                min: Some(DateClaim::parse_rfc3339_date("1900-01-01")?),
+                max: None,
+            }],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::DateTime,
+            label: "issued_at".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::DateTimeRange {
+                min: None,
+                max: Some(DateTimeClaim::parse_rfc3339_datetime(
+                    "2100-01-01T00:00:00Z",
+                )?),
+            }],
+            ..Default::default()
+        },
+    ];
+    CredentialSchema::new(Some("Dates"), None, &[], &schema_claims)
+}
+
+fn claims(dob: &str) -> CredxResult<Vec<ClaimData>> {
+    Ok(vec![
+        RevocationClaim::from("0e3c43d8-8fd5-4d44-9e1a-2f1b4bd3a6a0").into(),
+        HashedClaim::from("John Doe").into(),
+        DateClaim::parse_rfc3339_date(dob)?.into(),
+        DateTimeClaim::parse_rfc3339_datetime("2024-06-01T12:30:00Z")?.into(),
+    ])
+}
+
+fn prove_range(
+    issuer_public: &IssuerPublic<BbsScheme>,
+    credential: &Credential<BbsScheme>,
+    schema: &CredentialSchema,
+    label: &str,
+    range_st: impl FnOnce(&str, &str, &str, usize) -> CredxResult<RangeStatement>,
+) -> CredxResult<()> {
+    let claim = schema.claim_indices.get_index_of(label).unwrap();
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let comm_st = CommitmentStatement {
+        id: "comm".to_string(),
+        reference_id: sig_st.id.clone(),
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        claim,
+    };
+    let range_st = range_st("range", &comm_st.id, &sig_st.id, claim)?;
+    let presentation_schema =
+        PresentationSchema::new(&[sig_st.into(), comm_st.into(), range_st.into()]);
+    let credentials = indexmap! { "sig".to_string() => credential.clone().into() };
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+#[test]
+fn date_claims() {
+    setup();
+    let res = test_date_claims();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_date_claims() -> CredxResult<()> {
+    let schema = date_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let credential = issuer.sign_credential(&claims("2000-02-17")?)?.credential;
+
+    // The validators reject dates outside of the schema bounds
+    assert!(issuer.sign_credential(&claims("1899-12-31")?).is_err());
+
+    let today = NaiveDate::from_ymd_opt(2018, 2, 17).unwrap();
+    prove_range(
+        &issuer_public,
+        &credential,
+        &schema,
+        "dob",
+        |id, r, s, c| RangeStatement::older_than_as_of(id, r, s, c, 18, today),
+    )?;
+    prove_range(
+        &issuer_public,
+        &credential,
+        &schema,
+        "dob",
+        |id, r, s, c| RangeStatement::older_than(id, r, s, c, 18),
+    )?;
+    prove_range(
+        &issuer_public,
+        &credential,
+        &schema,
+        "dob",
+        |id, r, s, c| RangeStatement::younger_than_as_of(id, r, s, c, 21, today),
+    )?;
+    // The day before their 18th birthday
+    let today = NaiveDate::from_ymd_opt(2018, 2, 16).unwrap();
+    assert!(prove_range(
+        &issuer_public,
+        &credential,
+        &schema,
+        "dob",
+        |id, r, s, c| RangeStatement::older_than_as_of(id, r, s, c, 18, today)
+    )
+    .is_err());
+
+    let lower: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+    let upper: DateTime<Utc> = "2024-12-31T23:59:59Z".parse().unwrap();
+    prove_range(
+        &issuer_public,
+        &credential,
+        &schema,
+        "issued_at",
+        |id, r, s, c| {
+            Ok(RangeStatement::datetime_range(
+                id,
+                r,
+                s,
+                c,
+                Some(lower),
+                Some(upper),
+            ))
+        },
+    )?;
+    let lower: DateTime<Utc> = "2024-06-01T12:30:01Z".parse().unwrap();
+    assert!(prove_range(
+        &issuer_public,
+        &credential,
+        &schema,
+        "issued_at",
+        |id, r, s, c| Ok(RangeStatement::datetime_range(
+            id,
+            r,
+            s,
+            c,
+            Some(lower),
+            None
+        )),
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn date_claim_text_round_trip() {
+    let claim = ClaimData::from(DateClaim::parse_rfc3339_date("2000-02-17").unwrap());
+    assert_eq!(claim.to_text(), "dte:11004");
+    assert_eq!(ClaimData::from_text(&claim.to_text()).unwrap(), claim);
+    assert_eq!(
+        ClaimData::from_bytes(ClaimType::Date, &claim.to_bytes()).unwrap(),
+        claim
+    );
+
+    let claim = ClaimData::from(DateTimeClaim::from(-1));
+    assert_eq!(ClaimData::from_text(&claim.to_text()).unwrap(), claim);
+    assert_eq!(
+        ClaimData::from_bytes(ClaimType::DateTime, &claim.to_bytes()).unwrap(),
+        claim
+    );
+}
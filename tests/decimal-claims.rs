@@ -0,0 +1,151 @@
+use blsful::inner_types::*;
+use credx::claim::{ClaimData, ClaimType, DecimalClaim, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{CommitmentStatement, RangeStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+use rand_core::RngCore;
+use std::str::FromStr;
+
+const SCALE: u8 = 2;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn decimal_schema() -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema::decimal("balance", SCALE),
+    ];
+    CredentialSchema::new(Some("Bank Account"), None, &[], &schema_claims)
+}
+
+fn claims(balance: &str) -> CredxResult<Vec<ClaimData>> {
+    Ok(vec![
+        RevocationClaim::from("3f2a7c1e-5b8d-4e60-9a2f-1c7d3e5b9f80").into(),
+        HashedClaim::from("John Doe").into(),
+        DecimalClaim::from_str(balance)?.into(),
+    ])
+}
+
+fn prove_balance(
+    issuer_public: &IssuerPublic<BbsScheme>,
+    credential: &Credential<BbsScheme>,
+    lower: Option<&str>,
+    upper: Option<&str>,
+) -> CredxResult<()> {
+    let claim = issuer_public
+        .schema
+        .claim_indices
+        .get_index_of("balance")
+        .unwrap();
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let comm_st = CommitmentStatement {
+        id: "comm".to_string(),
+        reference_id: sig_st.id.clone(),
+        message_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"message generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        blinder_generator: G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(
+            b"blinder generator",
+            b"BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        ),
+        claim,
+    };
+    let range_st = RangeStatement::decimal_range(
+        "range",
+        &comm_st.id,
+        &sig_st,
+        claim,
+        lower.map(DecimalClaim::from_str).transpose()?,
+        upper.map(DecimalClaim::from_str).transpose()?,
+    )?;
+    let presentation_schema =
+        PresentationSchema::new(&[sig_st.into(), comm_st.into(), range_st.into()]);
+    let credentials = indexmap! { "sig".to_string() => credential.clone().into() };
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+#[test]
+fn decimal_claims() {
+    setup();
+    let res = test_decimal_claims();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_decimal_claims() -> CredxResult<()> {
+    let schema = decimal_schema()?;
+    let json = serde_json::to_string(&schema).unwrap();
+    let schema2: CredentialSchema = serde_json::from_str(&json).unwrap();
+    assert_eq!(schema2.claims[2].scale, SCALE);
+
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let credential = issuer.sign_credential(&claims("1234.5")?)?.credential;
+    // Claims are signed at the schema scale
+    assert_eq!(
+        credential.claims[2],
+        ClaimData::from(DecimalClaim::new(123450, SCALE))
+    );
+
+    // Issuance rejects values that lose precision
+    assert!(issuer.sign_credential(&claims("1.005")?).is_err());
+    assert!(issuer.sign_credential(&claims("1.050")?).is_ok());
+
+    prove_balance(&issuer_public, &credential, Some("1000"), None)?;
+    prove_balance(&issuer_public, &credential, Some("-10.5"), Some("1234.50"))?;
+    assert!(prove_balance(&issuer_public, &credential, Some("1234.51"), None).is_err());
+    assert!(prove_balance(&issuer_public, &credential, None, Some("1234.49")).is_err());
+    // Bounds that can't be represented at the schema scale are rejected
+    assert!(prove_balance(&issuer_public, &credential, Some("1000.001"), None).is_err());
+
+    // The scale comes from the issuer schema and only decimal claims have one
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let bound = Some(DecimalClaim::from_str("1000")?);
+    let range_st = RangeStatement::decimal_range("range", "comm", &sig_st, 2, bound, None)?;
+    assert_eq!(range_st.lower, Some(100000));
+    assert!(RangeStatement::decimal_range("range", "comm", &sig_st, 1, bound, None).is_err());
+    assert!(RangeStatement::decimal_range("range", "comm", &sig_st, 3, bound, None).is_err());
+    Ok(())
+}
+
+#[test]
+fn decimal_claim_text_round_trip() {
+    let claim = ClaimData::from(DecimalClaim::from_str("-12.340").unwrap());
+    assert_eq!(claim.to_text(), "dec:-12.340");
+    assert_eq!(ClaimData::from_text(&claim.to_text()).unwrap(), claim);
+    assert_eq!(
+        ClaimData::from_bytes(ClaimType::Decimal, &claim.to_bytes()).unwrap(),
+        claim
+    );
+}
@@ -166,6 +166,7 @@ mod reveal_and_equality_tests {
 
     type Disclosures = IndexMap<String, IndexMap<String, ClaimData>>;
 
+    #[rustfmt::skip]
     fn run_test<S: ShortGroupSignatureScheme>(
         issuer_public: &IssuerPublic<S>,
         mut issuer: Issuer<S>,
@@ -203,8 +204,6 @@ mod reveal_and_equality_tests {
         let mut nonce = [0u8; 16];
         thread_rng().fill_bytes(&mut nonce);
 
-        #[cfg_attr(rustfmt, rustfmt_skip)]
-        // when possible: #[rustmt::skip]
         {
             let credentials = indexmap! {
                     sig_st_a.id.clone() => credential_a.credential.into(),
@@ -269,6 +268,7 @@ mod reveal_and_equality_tests {
                     min: Some(3),
                     max: Some(u8::MAX as usize),
                 }],
+                ..Default::default()
             },
             ClaimSchema {
                 claim_type: ClaimType::Hashed,
@@ -278,12 +278,14 @@ mod reveal_and_equality_tests {
                     min: None,
                     max: Some(u8::MAX as usize),
                 }],
+                ..Default::default()
             },
             ClaimSchema {
                 claim_type: ClaimType::Revocation,
                 label: ID_LBL.to_string(),
                 print_friendly: false,
                 validators: vec![],
+                ..Default::default()
             },
         ];
 
@@ -14,8 +14,8 @@ use credx::prelude::{
 };
 use credx::presentation::{Presentation, PresentationSchema};
 use credx::statement::{
-    CommitmentStatement, EqualityStatement, RangeStatement, RevocationStatement,
-    SignatureStatement, VerifiableEncryptionStatement,
+    CommitmentStatement, EqualityStatement, RangeStatement, RegistryEpochPolicy,
+    RevocationStatement, SignatureStatement, VerifiableEncryptionStatement,
 };
 use credx::{
     create_domain_proof_generator, generate_verifiable_encryption_keys, random_string, CredxResult,
@@ -50,6 +50,7 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -59,6 +60,7 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -68,6 +70,7 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
                 min: None,
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -77,6 +80,7 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
                 min: Some(0),
                 max: Some(u16::MAX as isize),
             }],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some(LABEL), Some(DESCRIPTION), &[], &schema_claims)?;
@@ -114,6 +118,8 @@ fn test_presentation_1_credential_works() -> CredxResult<()> {
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let comm_st = CommitmentStatement {
         id: random_string(16, thread_rng()),
@@ -180,6 +186,7 @@ fn presentation_decrypt_claim_works() {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -189,6 +196,7 @@ fn presentation_decrypt_claim_works() {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -198,6 +206,7 @@ fn presentation_decrypt_claim_works() {
                 min: None,
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -207,6 +216,7 @@ fn presentation_decrypt_claim_works() {
                 min: Some(0),
                 max: None,
             }],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some("Test"), Some(""), &[], &schema_claims).unwrap();
@@ -231,6 +241,8 @@ fn presentation_decrypt_claim_works() {
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let verenc_st = VerifiableEncryptionDecryptionStatement {
         message_generator: G1Projective::GENERATOR,
@@ -263,7 +275,7 @@ fn presentation_decrypt_claim_works() {
             .unwrap();
         assert_eq!(decrypted_name.to_bytes(), b"John Doe");
     } else {
-        assert!(false, "expected VerifiableEncryptionDecryption");
+        panic!("expected VerifiableEncryptionDecryption");
     }
 }
 
@@ -276,12 +288,14 @@ fn presentation_decrypt_scalar_claim() {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Scalar,
             label: "name".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -291,6 +305,7 @@ fn presentation_decrypt_scalar_claim() {
                 min: None,
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -300,6 +315,7 @@ fn presentation_decrypt_scalar_claim() {
                 min: Some(0),
                 max: None,
             }],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some("Test"), Some(""), &[], &schema_claims).unwrap();
@@ -324,6 +340,8 @@ fn presentation_decrypt_scalar_claim() {
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let verenc_st1 = VerifiableEncryptionStatement {
         message_generator: G1Projective::GENERATOR,
@@ -372,7 +390,7 @@ fn presentation_decrypt_scalar_claim() {
             .unwrap();
         assert_eq!(decrypted_name.as_str(), "John Doe");
     } else {
-        assert!(false, "expected VerifiableEncryptionDecryption");
+        panic!("expected VerifiableEncryptionDecryption");
     }
 
     if let PresentationProofs::VerifiableEncryption(verenc) = &presentation.proofs[&verenc2_id] {
@@ -383,7 +401,7 @@ fn presentation_decrypt_scalar_claim() {
         let decrypted_phone = NumberClaim::from(decrypted_phone_scalar);
         assert_eq!(decrypted_phone.value, 8018881111);
     } else {
-        assert!(false, "expected VerifiableEncryptionDecryption");
+        panic!("expected VerifiableEncryptionDecryption");
     }
 }
 
@@ -398,6 +416,7 @@ fn presentation_with_domain_proof() {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -407,6 +426,7 @@ fn presentation_with_domain_proof() {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -416,6 +436,7 @@ fn presentation_with_domain_proof() {
                 min: None,
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -425,6 +446,7 @@ fn presentation_with_domain_proof() {
                 min: Some(0),
                 max: Some(u16::MAX as isize),
             }],
+            ..Default::default()
         },
     ];
     let cred_schema =
@@ -468,6 +490,8 @@ fn presentation_with_domain_proof() {
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let comm_st = CommitmentStatement {
         id: random_string(16, thread_rng()),
@@ -558,6 +582,7 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -567,6 +592,7 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
                 min: Some(3),
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -587,6 +613,7 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
                     NumberClaim::from(10).into(),
                 ]),
             ],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -596,6 +623,7 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
                 min: Some(0),
                 max: Some(u32::MAX as isize),
             }],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some(LABEL), Some(DESCRIPTION), &[], &schema_claims)?;
@@ -623,6 +651,8 @@ fn test_presentation_1_credential_alter_revealed_message_fails() -> CredxResult<
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let comm_st = CommitmentStatement {
         id: random_string(16, rand::thread_rng()),
@@ -694,12 +724,14 @@ fn test_blind_sign_request() -> CredxResult<()> {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Scalar,
             label: "link_secret".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -709,6 +741,7 @@ fn test_blind_sign_request() -> CredxResult<()> {
                 min: Some(3),
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
@@ -718,6 +751,7 @@ fn test_blind_sign_request() -> CredxResult<()> {
                 min: None,
                 max: Some(u8::MAX as usize),
             }],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
@@ -727,6 +761,7 @@ fn test_blind_sign_request() -> CredxResult<()> {
                 min: Some(0),
                 max: Some(u16::MAX as isize),
             }],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(
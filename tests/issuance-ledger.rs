@@ -0,0 +1,189 @@
+use credx::blind::BlindCredentialRequest;
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialBundle, CredentialSchema};
+use credx::issuer::{CredentialStatus, Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{
+    RegistryEpochPolicy, RevocationStatement, SignatureStatement, SuspensionStatement,
+};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::{btreemap, btreeset};
+
+const CRED_IDS: [&str; 3] = [
+    "5c8e1a3f-7d2b-4e6c-9a0f-3b5d7e9c1a2b",
+    "6d9f2b4a-8e3c-4f7d-8b1a-4c6e8f0d2b3c",
+    "7e0a3c5b-9f4d-4a8e-9c2b-5d7f9a1e3c4d",
+];
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn claims(id: &str) -> Vec<ClaimData> {
+    vec![
+        RevocationClaim::from(id).into(),
+        HashedClaim::from("John Doe").into(),
+    ]
+}
+
+fn prove(issuer: &Issuer<BbsScheme>, bundle: &CredentialBundle<BbsScheme>) -> CredxResult<()> {
+    let issuer_public = IssuerPublic::from(issuer);
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let acc_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
+    };
+    let sus_st = SuspensionStatement {
+        id: "suspension".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.suspension_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema =
+        PresentationSchema::new(&[sig_st.into(), acc_st.into(), sus_st.into()]);
+    let credentials = indexmap! { "sig".to_string() => bundle.credential.clone().into() };
+    let nonce = b"issuance ledger nonce";
+    let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+    presentation.verify(&presentation_schema, nonce)
+}
+
+#[test]
+fn issuance_ledger() {
+    setup();
+    let res = test_issuance_ledger();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_issuance_ledger() -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    let schema = CredentialSchema::new(Some("Ledger"), None, &["name"], &schema_claims)?;
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    let mut first = issuer.sign_credential_with_id("ticket-1", &claims(CRED_IDS[0]))?;
+    let mut second = issuer.sign_credential_with_id("ticket-2", &claims(CRED_IDS[1]))?;
+    let mut third = issuer.sign_credential(&claims(CRED_IDS[2]))?;
+    assert!(issuer
+        .sign_credential_with_id("ticket-1", &claims(CRED_IDS[2]))
+        .is_err());
+
+    let issued = issuer.list_issued();
+    assert_eq!(issued.len(), 3);
+    assert_eq!(issued[0].id, "ticket-1");
+    assert_eq!(issued[1].revocation_value, CRED_IDS[1]);
+    assert!(issued
+        .iter()
+        .all(|r| r.schema_id == schema.id && r.status == CredentialStatus::Active));
+    let third_id = issuer
+        .ledger
+        .find_by_revocation_value(CRED_IDS[2])
+        .unwrap()
+        .id
+        .clone();
+
+    // Suspended credentials can't prove they aren't suspended
+    let revocation_epoch = issuer.revocation_registry.epoch;
+    let update = issuer.suspend_credential("ticket-2")?;
+    assert_eq!(
+        issuer.ledger.get("ticket-2").unwrap().status,
+        CredentialStatus::Suspended
+    );
+    assert!(second
+        .apply_suspension_updates(std::slice::from_ref(&update))
+        .is_err());
+    third.apply_suspension_updates(&[update])?;
+    prove(&issuer, &third)?;
+    assert!(prove(&issuer, &second).is_err());
+    assert!(issuer.suspend_credential("ticket-2").is_err());
+
+    // Lifting a suspension keeps the signature and revocation handle
+    let update = issuer.reinstate_credential("ticket-2")?;
+    assert_eq!(
+        issuer.ledger.get("ticket-2").unwrap().status,
+        CredentialStatus::Active
+    );
+    third.apply_suspension_updates(&[update])?;
+    prove(&issuer, &third)?;
+    let handle = issuer.update_suspension_handle(RevocationClaim::from(CRED_IDS[1]))?;
+    second.replace_suspension_handle(
+        handle,
+        issuer.suspension_registry.value,
+        issuer.suspension_registry.epoch,
+    );
+    prove(&issuer, &second)?;
+    assert_eq!(issuer.revocation_registry.epoch, revocation_epoch);
+    assert!(issuer.reinstate_credential("ticket-2").is_err());
+
+    // Revoking is permanent
+    let update = issuer.revoke_credential("ticket-1")?;
+    assert!(first.apply_registry_updates(&[update]).is_err());
+    assert_eq!(
+        issuer.ledger.get("ticket-1").unwrap().status,
+        CredentialStatus::Revoked
+    );
+    assert!(issuer.revoke_credential("ticket-1").is_err());
+    assert!(issuer.suspend_credential("ticket-1").is_err());
+    assert!(issuer.reinstate_credential("ticket-1").is_err());
+    assert!(issuer.revoke_credential("unknown").is_err());
+
+    // Suspended credentials can be revoked
+    issuer.suspend_credential("ticket-2")?;
+    let update = issuer.revoke_credential("ticket-2")?;
+    assert!(second.apply_registry_updates(&[update]).is_err());
+    assert!(issuer.reinstate_credential("ticket-2").is_err());
+
+    // Revoking by claim updates the ledger too
+    issuer.revoke_credentials(&[RevocationClaim::from(CRED_IDS[2])])?;
+    assert_eq!(
+        issuer.ledger.get(&third_id).unwrap().status,
+        CredentialStatus::Revoked
+    );
+
+    // Blind signed credentials can be given an id too
+    let issuer_public = IssuerPublic::from(&issuer);
+    let blind_claims = btreemap! { "name".to_string() => HashedClaim::from("Jane Doe").into() };
+    let (request, _) = BlindCredentialRequest::new(&issuer_public, &blind_claims)?;
+    let known =
+        |id: &str| btreemap! { "identifier".to_string() => RevocationClaim::from(id).into() };
+    assert!(issuer
+        .blind_sign_credential_with_id("ticket-1", &request, &known("blind-1"))
+        .is_err());
+    issuer.blind_sign_credential_with_id("ticket-4", &request, &known("blind-1"))?;
+    assert_eq!(
+        issuer.ledger.get("ticket-4").unwrap().revocation_value,
+        "blind-1"
+    );
+    issuer.revoke_credential("ticket-4")?;
+    assert!(!issuer.revocation_registry.is_active("blind-1"));
+
+    let json = serde_json::to_string(&issuer).unwrap();
+    let issuer2: Issuer<BbsScheme> = serde_json::from_str(&json).unwrap();
+    assert_eq!(issuer2.list_issued(), issuer.list_issued());
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use credx::claim::{ClaimType, ClaimValidator, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand_chacha::ChaChaRng;
+use rand_core::SeedableRng;
+
+const SEED: &[u8] = b"credx issuer seed test vector 01";
+const ISSUER_ID: &str = "aa4adc133b76c11d6c8c271bb5994f28";
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn schema() -> CredentialSchema {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::Length {
+                min: Some(3),
+                max: Some(u8::MAX as usize),
+            }],
+            ..Default::default()
+        },
+    ];
+    CredentialSchema::new(Some("Seed Schema"), None, &[], &schema_claims).unwrap()
+}
+
+#[test]
+fn issuer_from_seed_is_reproducible_bbs() {
+    setup();
+    let res = test_issuer_from_seed_is_reproducible::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn issuer_from_seed_is_reproducible_ps() {
+    setup();
+    let res = test_issuer_from_seed_is_reproducible::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_issuer_from_seed_is_reproducible<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let cred_schema = schema();
+    let (issuer_public1, issuer1) = Issuer::<S>::new_with_seed(&cred_schema, SEED);
+    let (issuer_public2, issuer2) = Issuer::<S>::new_with_seed(&cred_schema, SEED);
+
+    assert_eq!(issuer1.id, ISSUER_ID);
+    assert_eq!(
+        serde_json::to_string(&issuer1).unwrap(),
+        serde_json::to_string(&issuer2).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_string(&issuer_public1).unwrap(),
+        serde_json::to_string(&issuer_public2).unwrap()
+    );
+
+    // Different seeds must produce different issuers
+    let (_, issuer3) = Issuer::<S>::new_with_seed(&cred_schema, b"another issuer seed");
+    assert_ne!(issuer1.id, issuer3.id);
+    assert_ne!(
+        serde_json::to_string(&issuer1.signing_key).unwrap(),
+        serde_json::to_string(&issuer3.signing_key).unwrap()
+    );
+    assert_ne!(
+        issuer1.revocation_key.to_bytes(),
+        issuer3.revocation_key.to_bytes()
+    );
+
+    // The same rng state yields the same issuer
+    let (_, issuer4) = Issuer::<S>::new_with_rng(&cred_schema, ChaChaRng::from_seed([7u8; 32]));
+    let (_, issuer5) = Issuer::<S>::new_with_rng(&cred_schema, ChaChaRng::from_seed([7u8; 32]));
+    assert_eq!(
+        serde_json::to_string(&issuer4).unwrap(),
+        serde_json::to_string(&issuer5).unwrap()
+    );
+
+    // A rebuilt issuer can sign credentials that verify against the original public data
+    let (_, mut rebuilt) = Issuer::<S>::new_with_seed(&cred_schema, SEED);
+    let credential = rebuilt.sign_credential(&[
+        RevocationClaim::from("c2b5d1a4-3a53-44f5-9b4f-3b1e2f4d7a10").into(),
+        HashedClaim::from("John Doe").into(),
+    ])?;
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public1.clone(),
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into()]);
+    let credentials = indexmap! { "signature".to_string() => credential.credential.into() };
+    let presentation = Presentation::create(&credentials, &presentation_schema, b"nonce")?;
+    presentation.verify(&presentation_schema, b"nonce")
+}
@@ -0,0 +1,113 @@
+use credx::claim::{ClaimType, ClaimValidator, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+use rand_core::RngCore;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn create_and_verify<S: ShortGroupSignatureScheme>(
+    issuer_public: &IssuerPublic<S>,
+    credential: &Credential<S>,
+) -> CredxResult<()> {
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "signature".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into()]);
+    let credentials = indexmap! { "signature".to_string() => credential.clone().into() };
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    let proof_data = serde_bare::to_vec(&presentation).unwrap();
+    let presentation: Presentation<S> = serde_bare::from_slice(&proof_data).unwrap();
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+#[test]
+fn key_rotation_bbs() {
+    setup();
+    let res = test_key_rotation::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn key_rotation_ps() {
+    setup();
+    let res = test_key_rotation::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_key_rotation<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::Length {
+                min: Some(3),
+                max: Some(u8::MAX as usize),
+            }],
+            ..Default::default()
+        },
+    ];
+    let cred_schema = CredentialSchema::new(Some("Rotation"), None, &[], &schema_claims)?;
+    let (issuer_public_epoch0, mut issuer) = Issuer::<S>::new(&cred_schema);
+
+    let old_credential = issuer.sign_credential(&[
+        RevocationClaim::from("5a0b9bc4-9ba5-4d2a-8a3e-3e2b3a9e2c01").into(),
+        HashedClaim::from("John Doe").into(),
+    ])?;
+    assert_eq!(old_credential.credential.key_epoch, 0);
+    create_and_verify(&issuer_public_epoch0, &old_credential.credential)?;
+
+    let issuer_public_epoch1 = issuer.rotate_signing_key()?;
+    assert_eq!(issuer_public_epoch1.key_epoch, 1);
+    assert_eq!(issuer_public_epoch1.key_history.len(), 1);
+    assert_eq!(issuer_public_epoch1.key_history[0].epoch, 0);
+    assert!(issuer_public_epoch1.key_history[0].valid_from.is_none());
+
+    let new_credential = issuer.sign_credential(&[
+        RevocationClaim::from("5a0b9bc4-9ba5-4d2a-8a3e-3e2b3a9e2c02").into(),
+        HashedClaim::from("Jane Doe").into(),
+    ])?;
+    assert_eq!(new_credential.credential.key_epoch, 1);
+
+    // Both credentials verify through the rollover
+    create_and_verify(&issuer_public_epoch1, &old_credential.credential)?;
+    create_and_verify(&issuer_public_epoch1, &new_credential.credential)?;
+    // The previous issuer public data doesn't know the new key
+    assert!(create_and_verify(&issuer_public_epoch0, &new_credential.credential).is_err());
+
+    // Once retired, credentials from the old epoch are rejected
+    assert!(issuer.retire_key_epoch(1).is_err());
+    let issuer_public_retired = issuer.retire_key_epoch(0)?;
+    assert!(create_and_verify(&issuer_public_retired, &old_credential.credential).is_err());
+    create_and_verify(&issuer_public_retired, &new_credential.credential)?;
+
+    let issuer_public_epoch2 = issuer.rotate_signing_key()?;
+    assert_eq!(issuer_public_epoch2.key_history.len(), 2);
+    assert_eq!(
+        issuer_public_epoch2.key_history[1].valid_from,
+        Some(issuer_public_epoch2.key_history[0].valid_until)
+    );
+    create_and_verify(&issuer_public_epoch2, &new_credential.credential)
+}
@@ -0,0 +1,115 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, Credential, CredentialSchema, MembershipBundle};
+use credx::issuer::{Issuer, IssuerPublic, MembershipIssuer};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{MembershipStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+use rand_core::RngCore;
+
+const VENDORS: [&str; 3] = ["Acme Corp", "Globex", "Initech"];
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn vendor(name: &str) -> ClaimData {
+    HashedClaim::from(name).into()
+}
+
+fn prove(
+    issuer_public: &IssuerPublic<BbsScheme>,
+    employee: &Credential<BbsScheme>,
+    vendors: &MembershipBundle,
+) -> CredxResult<()> {
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "employee".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let mem_st = MembershipStatement {
+        id: "approved vendor".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: vendors.issuer.registry,
+        verification_key: vendors.issuer.verification_key,
+        claim: 1,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), mem_st.into()]);
+    let credentials = indexmap! {
+        "employee".to_string() => employee.clone().into(),
+        "approved vendor".to_string() => vendors.credential.into(),
+    };
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)
+}
+
+#[test]
+fn approved_vendor_membership() {
+    setup();
+    let res = test_approved_vendor_membership();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_approved_vendor_membership() -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "employer".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    let schema = CredentialSchema::new(Some("Employee"), None, &[], &schema_claims)?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let employee =
+        issuer.sign_credential(&[RevocationClaim::from("e-0001").into(), vendor(VENDORS[1])])?;
+    let outsider =
+        issuer.sign_credential(&[RevocationClaim::from("e-0002").into(), vendor("Umbrella")])?;
+
+    let (_, mut vendors) = MembershipIssuer::new();
+    let claims = VENDORS.iter().map(|v| vendor(v)).collect::<Vec<_>>();
+    let added = vendors.add_members(&claims)?;
+    assert_eq!(added.epoch, 1);
+    assert!(vendors.add_members(&claims[..1]).is_err());
+    assert!(vendors.is_member(&vendor(VENDORS[1])));
+    assert!(vendors.issue(&vendor("Umbrella")).is_err());
+
+    let mut globex = vendors.issue(&employee.credential.claims[1])?;
+    assert_eq!(globex.issuer.epoch, 1);
+    prove(&issuer_public, &employee.credential, &globex)?;
+    // A membership credential only binds to the claim it was issued for
+    assert!(prove(&issuer_public, &outsider.credential, &globex).is_err());
+
+    // Holders follow registry updates without going back to the issuer
+    let mut acme = vendors.issue(&vendor(VENDORS[0]))?;
+    let stale = globex.clone();
+    let added = vendors.add_members(&[vendor("Hooli")])?;
+    let removed = vendors.remove_members(&[vendor(VENDORS[0])])?;
+    assert!(vendors.remove_members(&[vendor(VENDORS[0])]).is_err());
+    globex.apply_registry_updates(&[removed.clone(), added.clone()])?;
+    assert_eq!(globex.issuer.epoch, 3);
+    assert_eq!(globex.issuer.registry, vendors.registry);
+    prove(&issuer_public, &employee.credential, &globex)?;
+    let mut skipped = stale.clone();
+    assert!(skipped
+        .apply_registry_updates(std::slice::from_ref(&removed))
+        .is_err());
+
+    // Removed vendors can't follow the update
+    assert!(acme.apply_registry_updates(&[added, removed]).is_err());
+    assert_eq!(acme.issuer.epoch, 1);
+    Ok(())
+}
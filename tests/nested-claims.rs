@@ -0,0 +1,170 @@
+use credx::claim::{
+    ClaimData, ClaimType, HashedClaim, NestedClaimData, NumberClaim, RevocationClaim,
+};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::{btreemap, btreeset};
+use rand::thread_rng;
+use rand_core::RngCore;
+use std::collections::BTreeMap;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn hashed(label: &str) -> ClaimSchema {
+    ClaimSchema {
+        claim_type: ClaimType::Hashed,
+        label: label.to_string(),
+        print_friendly: true,
+        validators: vec![],
+        ..Default::default()
+    }
+}
+
+fn nested_schema() -> CredentialSchema {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        hashed("name"),
+        ClaimSchema::object(
+            "address",
+            &[
+                hashed("street"),
+                hashed("city"),
+                ClaimSchema {
+                    claim_type: ClaimType::Number,
+                    label: "zip/code".to_string(),
+                    print_friendly: true,
+                    validators: vec![],
+                    ..Default::default()
+                },
+            ],
+        ),
+        ClaimSchema::array("nationalities", &hashed("country"), 3),
+    ];
+    CredentialSchema::new(Some("Nested"), None, &[], &schema_claims).unwrap()
+}
+
+fn nested_claims() -> BTreeMap<String, NestedClaimData> {
+    btreemap! {
+        "identifier".to_string() => ClaimData::from(RevocationClaim::from("7d6f5e4c-3b2a-4190-8f7e-6d5c4b3a2918")).into(),
+        "name".to_string() => ClaimData::from(HashedClaim::from("John Doe")).into(),
+        "address".to_string() => btreemap! {
+            "street".to_string() => ClaimData::from(HashedClaim::from("42 Wallaby Way")).into(),
+            "city".to_string() => ClaimData::from(HashedClaim::from("Sydney")).into(),
+            "zip/code".to_string() => ClaimData::from(NumberClaim::from(2000)).into(),
+        }.into(),
+        "nationalities".to_string() => vec![
+            ClaimData::from(HashedClaim::from("AU")).into(),
+            ClaimData::from(HashedClaim::from("NZ")).into(),
+            ClaimData::from(HashedClaim::from("GB")).into(),
+        ].into(),
+    }
+}
+
+#[test]
+fn nested_schema_expands_to_json_pointers() {
+    let schema = nested_schema();
+    let labels = schema.claim_indices.iter().cloned().collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![
+            "identifier",
+            "name",
+            "/address/street",
+            "/address/city",
+            "/address/zip~1code",
+            "/nationalities/0",
+            "/nationalities/1",
+            "/nationalities/2",
+        ]
+    );
+    assert_eq!(schema.claims.len(), 8);
+    assert_eq!(schema.nested_claims.len(), 4);
+
+    let json = serde_json::to_string(&schema).unwrap();
+    let schema2: CredentialSchema = serde_json::from_str(&json).unwrap();
+    assert_eq!(schema2.claim_indices, schema.claim_indices);
+    assert_eq!(schema2.nested_claims.len(), 4);
+}
+
+#[test]
+fn nested_claims_round_trip() {
+    let schema = nested_schema();
+    let claims = nested_claims();
+    let flattened = schema.flatten_claims(&claims).unwrap();
+    assert_eq!(flattened.len(), 8);
+    assert_eq!(flattened[4], ClaimData::from(NumberClaim::from(2000)));
+
+    let by_label = schema
+        .claim_indices
+        .iter()
+        .cloned()
+        .zip(flattened)
+        .collect();
+    assert_eq!(schema.nest_claims(&by_label), claims);
+
+    let mut bad = claims.clone();
+    bad.insert(
+        "nationalities".to_string(),
+        vec![ClaimData::from(HashedClaim::from("AU")).into()].into(),
+    );
+    assert!(schema.flatten_claims(&bad).is_err());
+    let mut bad = claims;
+    bad.insert(
+        "address".to_string(),
+        ClaimData::from(HashedClaim::from("42 Wallaby Way Sydney")).into(),
+    );
+    assert!(schema.flatten_claims(&bad).is_err());
+}
+
+#[test]
+fn nested_claims_presentation() {
+    setup();
+    let res = test_nested_claims_presentation();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_nested_claims_presentation() -> CredxResult<()> {
+    let schema = nested_schema();
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let credential = issuer.sign_nested_credential(&nested_claims())?;
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"/address".to_string(), "/nationalities/1".to_string()},
+        id: "nested".to_string(),
+        issuer: issuer_public,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into()]);
+    let credentials = indexmap! { "nested".to_string() => credential.credential.into() };
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)?;
+
+    assert_eq!(presentation.disclosed_messages["nested"].len(), 4);
+    let nested = presentation.nested_disclosed_messages(&presentation_schema);
+    let expected = btreemap! {
+        "address".to_string() => btreemap! {
+            "street".to_string() => ClaimData::from(HashedClaim::from("42 Wallaby Way")).into(),
+            "city".to_string() => ClaimData::from(HashedClaim::from("Sydney")).into(),
+            "zip/code".to_string() => ClaimData::from(NumberClaim::from(2000)).into(),
+        }.into(),
+        "nationalities".to_string() => NestedClaimData::Array(btreemap! {
+            1 => ClaimData::from(HashedClaim::from("NZ")).into(),
+        }),
+    };
+    assert_eq!(nested["nested"], expected);
+    Ok(())
+}
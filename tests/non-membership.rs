@@ -0,0 +1,147 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::prelude::{
+    MembershipClaim, MembershipRegistry, MembershipSigningKey, MembershipVerificationKey,
+    NonMembershipCredential,
+};
+use credx::presentation::{Presentation, PresentationCredential, PresentationSchema};
+use credx::statement::{NonMembershipStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::{indexmap, IndexMap};
+use maplit::btreeset;
+use rand::thread_rng;
+use rand_core::RngCore;
+
+const SANCTIONED: [&str; 3] = ["X1234567", "Y7654321", "Z5550123"];
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn passport_element(number: &str) -> MembershipClaim {
+    MembershipClaim::from(ClaimData::from(HashedClaim::from(number)))
+}
+
+fn create_and_verify(
+    presentation_schema: &PresentationSchema<BbsScheme>,
+    credentials: &IndexMap<String, PresentationCredential<BbsScheme>>,
+) -> CredxResult<()> {
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(credentials, presentation_schema, &nonce)?;
+    presentation.verify(presentation_schema, &nonce)?;
+    let proof_data = serde_bare::to_vec(&presentation).unwrap();
+    let presentation: Presentation<BbsScheme> = serde_bare::from_slice(&proof_data).unwrap();
+    presentation.verify(presentation_schema, &nonce)
+}
+
+#[test]
+fn sanctions_list_non_membership() {
+    setup();
+    let res = test_sanctions_list_non_membership();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_sanctions_list_non_membership() -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "passport_number".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    let schema = CredentialSchema::new(Some("Passport"), None, &[], &schema_claims)?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let cleared = issuer.sign_credential(&[
+        RevocationClaim::from("0b6e8f1a-2c3d-4e5f-8a9b-0c1d2e3f4a5b").into(),
+        HashedClaim::from("P0001112").into(),
+    ])?;
+    let sanctioned = issuer.sign_credential(&[
+        RevocationClaim::from("1c7f9a2b-3d4e-4f6a-9b0c-1d2e3f4a5b6c").into(),
+        HashedClaim::from(SANCTIONED[1]).into(),
+    ])?;
+
+    // The sanctions authority runs its own accumulator
+    let sanctions_sk = MembershipSigningKey::new(None);
+    let sanctions_vk = MembershipVerificationKey::from(&sanctions_sk);
+    let denied = SANCTIONED
+        .iter()
+        .map(|n| passport_element(n).0)
+        .collect::<Vec<_>>();
+    let sanctions_list = MembershipRegistry::with_elements(&sanctions_sk, &denied);
+    let witness = NonMembershipCredential::new(
+        MembershipClaim::from(&cleared.credential.claims[1]).0,
+        &denied,
+        &sanctions_sk,
+    )
+    .unwrap();
+    assert!(witness.verify(
+        MembershipClaim::from(&cleared.credential.claims[1]).0,
+        sanctions_vk,
+        sanctions_list
+    ));
+    // Sanctioned passport numbers can't get a witness
+    assert!(NonMembershipCredential::new(
+        MembershipClaim::from(&sanctioned.credential.claims[1]).0,
+        &denied,
+        &sanctions_sk,
+    )
+    .is_none());
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "passport".to_string(),
+        issuer: issuer_public,
+    };
+    let nm_st = NonMembershipStatement {
+        id: "sanctions".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: sanctions_list,
+        verification_key: sanctions_vk,
+        claim: 1,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.clone().into(), nm_st.into()]);
+    let credentials = indexmap! {
+        "passport".to_string() => cleared.credential.clone().into(),
+        "sanctions".to_string() => witness.into(),
+    };
+    create_and_verify(&presentation_schema, &credentials)?;
+
+    // A witness for another passport number doesn't bind to the signed claim
+    let credentials = indexmap! {
+        "passport".to_string() => sanctioned.credential.into(),
+        "sanctions".to_string() => witness.into(),
+    };
+    assert!(create_and_verify(&presentation_schema, &credentials).is_err());
+
+    // Once the passport number is added to the list the old witness stops working
+    let sanctions_list = sanctions_list.add(
+        &sanctions_sk,
+        MembershipClaim::from(&cleared.credential.claims[1]).0,
+    );
+    let nm_st = NonMembershipStatement {
+        id: "sanctions".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: sanctions_list,
+        verification_key: sanctions_vk,
+        claim: 1,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), nm_st.into()]);
+    let credentials = indexmap! {
+        "passport".to_string() => cleared.credential.into(),
+        "sanctions".to_string() => witness.into(),
+    };
+    assert!(create_and_verify(&presentation_schema, &credentials).is_err());
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::short_group_traits::ShortGroupSignatureScheme;
+use credx::presentation::{Presentation, PresentationCredential, PresentationSchema};
+use credx::statement::{NotEqualStatement, NotEqualTarget, SignatureStatement};
+use credx::CredxResult;
+use indexmap::{indexmap, IndexMap};
+use maplit::btreeset;
+use std::collections::BTreeSet;
+
+const ID_IX: usize = 1;
+const NATIONALITY_IX: usize = 2;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn credential_schema(label: &str) -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "id".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "nationality".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    CredentialSchema::new(Some(label), None, &[], &schema_claims)
+}
+
+fn signature<S: ShortGroupSignatureScheme>(
+    id: &str,
+    issuer: &IssuerPublic<S>,
+    disclosed: BTreeSet<String>,
+) -> SignatureStatement<S> {
+    SignatureStatement {
+        disclosed,
+        id: id.to_string(),
+        issuer: issuer.clone(),
+    }
+}
+
+fn create_and_verify<S: ShortGroupSignatureScheme>(
+    schema: &PresentationSchema<S>,
+    credentials: &IndexMap<String, PresentationCredential<S>>,
+) -> CredxResult<()> {
+    let nonce = b"not equal nonce";
+    let presentation = Presentation::create(credentials, schema, nonce)?;
+    presentation.verify(schema, nonce)?;
+    let json = serde_json::to_string(&presentation).unwrap();
+    let presentation: Presentation<S> = serde_json::from_str(&json).unwrap();
+    presentation.verify(schema, nonce)
+}
+
+#[test]
+fn not_equal_bbs() {
+    setup();
+    let res = test_not_equal::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn not_equal_ps() {
+    setup();
+    let res = test_not_equal::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_not_equal<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let (a_public, mut a_issuer) = Issuer::<S>::new(&credential_schema("Issuer A")?);
+    let (b_public, mut b_issuer) = Issuer::<S>::new(&credential_schema("Issuer B")?);
+    let a = a_issuer.sign_credential(&[
+        RevocationClaim::from("a-1").into(),
+        HashedClaim::from("123456").into(),
+        HashedClaim::from("Utopia").into(),
+    ])?;
+    let b = b_issuer.sign_credential(&[
+        RevocationClaim::from("b-1").into(),
+        HashedClaim::from("654321").into(),
+        HashedClaim::from("Utopia").into(),
+    ])?;
+    let credentials = indexmap! {
+        "a".to_string() => a.credential.clone().into(),
+        "b".to_string() => b.credential.clone().into(),
+    };
+    let value_statement = |value: &str| NotEqualStatement {
+        id: "nationality".to_string(),
+        reference_id: "a".to_string(),
+        claim: NATIONALITY_IX,
+        other: NotEqualTarget::Value(HashedClaim::from(value).into()),
+    };
+    let claim_statement = |claim: usize| NotEqualStatement {
+        id: "different".to_string(),
+        reference_id: "a".to_string(),
+        claim,
+        other: NotEqualTarget::Claim {
+            reference_id: "b".to_string(),
+            claim,
+        },
+    };
+
+    // A claim differs from a public value
+    let schema = PresentationSchema::new(&[
+        signature("a", &a_public, btreeset! {}).into(),
+        value_statement("Atlantis").into(),
+    ]);
+    let single = indexmap! { "a".to_string() => a.credential.clone().into() };
+    create_and_verify(&schema, &single)?;
+    let presentation = Presentation::create(&single, &schema, b"not equal nonce")?;
+    let other_value = PresentationSchema::new(&[
+        signature("a", &a_public, btreeset! {}).into(),
+        value_statement("Oceania").into(),
+    ]);
+    assert!(presentation
+        .verify(&other_value, b"not equal nonce")
+        .is_err());
+
+    // The claim is equal to the value
+    let schema = PresentationSchema::new(&[
+        signature("a", &a_public, btreeset! {}).into(),
+        value_statement("Utopia").into(),
+    ]);
+    assert!(Presentation::create(&single, &schema, b"not equal nonce").is_err());
+
+    // Claims from two credentials differ
+    let schema = PresentationSchema::new(&[
+        signature("a", &a_public, btreeset! {}).into(),
+        signature("b", &b_public, btreeset! {}).into(),
+        claim_statement(ID_IX).into(),
+    ]);
+    create_and_verify(&schema, &credentials)?;
+
+    // Claims from two credentials are equal
+    let schema = PresentationSchema::new(&[
+        signature("a", &a_public, btreeset! {}).into(),
+        signature("b", &b_public, btreeset! {}).into(),
+        claim_statement(NATIONALITY_IX).into(),
+    ]);
+    assert!(Presentation::create(&credentials, &schema, b"not equal nonce").is_err());
+
+    // Claims must come from different signature statements
+    let schema = PresentationSchema::new(&[
+        signature("a", &a_public, btreeset! {}).into(),
+        NotEqualStatement {
+            id: "different".to_string(),
+            reference_id: "a".to_string(),
+            claim: ID_IX,
+            other: NotEqualTarget::Claim {
+                reference_id: "a".to_string(),
+                claim: NATIONALITY_IX,
+            },
+        }
+        .into(),
+    ]);
+    assert!(Presentation::create(&single, &schema, b"not equal nonce").is_err());
+
+    // Revealed claims can't be used
+    let schema = PresentationSchema::new(&[
+        signature("a", &a_public, btreeset! {"nationality".to_string()}).into(),
+        value_statement("Atlantis").into(),
+    ]);
+    assert!(Presentation::create(&single, &schema, b"not equal nonce").is_err());
+    Ok(())
+}
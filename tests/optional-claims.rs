@@ -0,0 +1,213 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, ClaimValidationReason, Credential, CredentialSchema};
+use credx::error::Error;
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::{PresenceStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::{btreemap, btreeset};
+use rand::thread_rng;
+use rand_core::RngCore;
+use std::collections::BTreeSet;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn optional_schema() -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "first_name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "middle_name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            optional: true,
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "last_name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    CredentialSchema::new(Some("Identity"), None, &[], &schema_claims)
+}
+
+fn sign(
+    issuer: &mut Issuer<BbsScheme>,
+    middle_name: Option<&str>,
+) -> CredxResult<Credential<BbsScheme>> {
+    let mut claims = btreemap! {
+        "identifier".to_string() => RevocationClaim::from("5d0a8e3c-7b1f-4c29-a6e4-9f3b2d8c1e07").into(),
+        "first_name".to_string() => HashedClaim::from("John").into(),
+        "last_name".to_string() => HashedClaim::from("Doe").into(),
+    };
+    if let Some(middle_name) = middle_name {
+        claims.insert(
+            "middle_name".to_string(),
+            HashedClaim::from(middle_name).into(),
+        );
+    }
+    Ok(issuer.sign_credential_map(&claims)?.credential)
+}
+
+fn prove(
+    issuer_public: &IssuerPublic<BbsScheme>,
+    credential: &Credential<BbsScheme>,
+    disclosed: BTreeSet<String>,
+    present: Option<bool>,
+) -> CredxResult<Presentation<BbsScheme>> {
+    let sig_st = SignatureStatement {
+        disclosed,
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let mut statements = vec![sig_st.into()];
+    if let Some(present) = present {
+        statements.push(
+            PresenceStatement {
+                id: "presence".to_string(),
+                reference_id: "sig".to_string(),
+                claim: issuer_public
+                    .schema
+                    .claim_indices
+                    .get_index_of("middle_name")
+                    .unwrap(),
+                present,
+            }
+            .into(),
+        );
+    }
+    let presentation_schema = PresentationSchema::new(&statements);
+    let credentials = indexmap! { "sig".to_string() => credential.clone().into() };
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    let json = serde_json::to_string(&presentation).unwrap();
+    let presentation: Presentation<BbsScheme> = serde_json::from_str(&json).unwrap();
+    presentation.verify(&presentation_schema, &nonce)?;
+    Ok(presentation)
+}
+
+#[test]
+fn optional_claims() {
+    setup();
+    let res = test_optional_claims();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_optional_claims() -> CredxResult<()> {
+    let schema = optional_schema()?;
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    let without = sign(&mut issuer, None)?;
+    assert_eq!(without.claims[2], ClaimData::Absent);
+    let with = sign(&mut issuer, Some("Fitzgerald"))?;
+    assert_eq!(with.claims[2], HashedClaim::from("Fitzgerald").into());
+
+    // Only optional claims may be absent
+    let mut claims = with.claims.clone();
+    claims[1] = ClaimData::Absent;
+    match issuer.sign_credential(&claims) {
+        Err(Error::ClaimValidation(report)) => {
+            assert_eq!(report.labels(), vec!["first_name"]);
+            assert_eq!(
+                report.failures[0].reason,
+                ClaimValidationReason::NotOptional
+            );
+        }
+        res => panic!("expected a validation report, got {:?}", res),
+    }
+    Ok(())
+}
+
+#[test]
+fn presence_proofs() {
+    setup();
+    let res = test_presence_proofs();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_presence_proofs() -> CredxResult<()> {
+    let schema = optional_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let without = sign(&mut issuer, None)?;
+    let with = sign(&mut issuer, Some("Fitzgerald"))?;
+
+    prove(&issuer_public, &with, BTreeSet::new(), Some(true))?;
+    prove(&issuer_public, &without, BTreeSet::new(), Some(false))?;
+    assert!(prove(&issuer_public, &with, BTreeSet::new(), Some(false)).is_err());
+    assert!(prove(&issuer_public, &without, BTreeSet::new(), Some(true)).is_err());
+    // A revealed claim can't be used in a presence proof
+    assert!(prove(
+        &issuer_public,
+        &with,
+        btreeset! {"middle_name".to_string()},
+        Some(true)
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn absent_claims_disclosed_separately() {
+    setup();
+    let res = test_absent_claims_disclosed_separately();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_absent_claims_disclosed_separately() -> CredxResult<()> {
+    let schema = optional_schema()?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let without = sign(&mut issuer, None)?;
+    let with = sign(&mut issuer, Some("Fitzgerald"))?;
+    let disclosed = btreeset! {"first_name".to_string(), "middle_name".to_string()};
+
+    let presentation = prove(&issuer_public, &without, disclosed.clone(), None)?;
+    assert_eq!(
+        presentation.disclosed_messages["sig"]
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["first_name"]
+    );
+    assert_eq!(
+        presentation.absent_claims["sig"],
+        vec!["middle_name".to_string()]
+    );
+
+    let presentation = prove(&issuer_public, &with, disclosed, None)?;
+    assert_eq!(presentation.disclosed_messages["sig"].len(), 2);
+    assert!(presentation.absent_claims.is_empty());
+    Ok(())
+}
+
+#[test]
+fn revocation_claims_cannot_be_optional() {
+    let schema_claims = [ClaimSchema {
+        claim_type: ClaimType::Revocation,
+        label: "identifier".to_string(),
+        print_friendly: false,
+        validators: vec![],
+        optional: true,
+        ..Default::default()
+    }];
+    assert!(CredentialSchema::new(Some("Identity"), None, &[], &schema_claims).is_err());
+}
@@ -5,7 +5,8 @@ use credx::knox::bbs::BbsScheme;
 use credx::prelude::Issuer;
 use credx::presentation::{Presentation, PresentationSchema};
 use credx::statement::{
-    CommitmentStatement, RangeStatement, RevocationStatement, SignatureStatement,
+    CommitmentStatement, RangeStatement, RegistryEpochPolicy, RevocationStatement,
+    SignatureStatement,
 };
 use credx::{random_string, CredxResult};
 use indexmap::indexmap;
@@ -35,12 +36,14 @@ fn test_out_of_range_panic() -> CredxResult<()> {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some(LABEL), Some(DESCRIPTION), &[], &schema_claims)?;
@@ -63,6 +66,8 @@ fn test_out_of_range_panic() -> CredxResult<()> {
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let comm_st = CommitmentStatement {
         id: random_string(16, rand::thread_rng()),
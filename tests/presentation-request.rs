@@ -0,0 +1,102 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{
+    MemoryNonceStore, PresentationRequest, PresentationResponse, PresentationSchema,
+};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn presentation_request_response() {
+    setup();
+    let res = test_presentation_request_response();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_presentation_request_response() -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    let cred_schema = CredentialSchema::new(Some("Test"), None, &[], &schema_claims)?;
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme>::new(&cred_schema);
+    let credential = issuer.sign_credential(&[
+        RevocationClaim::from("c-0001").into(),
+        HashedClaim::from("John Doe").into(),
+    ])?;
+    let (other_public, _) = Issuer::<BbsScheme>::new(&cred_schema);
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let schema = PresentationSchema::new(&[sig_st.into()]);
+    let credentials = indexmap! { "sig".to_string() => credential.credential.into() };
+
+    let mut request = PresentationRequest::new("verifier", &schema, 60);
+    request.accepted_issuers = vec![other_public.id.clone()];
+    assert!(request.respond(&credentials).is_err());
+    request.accepted_issuers.push(issuer_public.id.clone());
+
+    // The request and response travel between the verifier and holder
+    let json = serde_json::to_string(&request).unwrap();
+    let received: PresentationRequest<BbsScheme> = serde_json::from_str(&json).unwrap();
+    assert_eq!(received.hash(), request.hash());
+    let response = received.respond(&credentials)?;
+    let bytes = serde_bare::to_vec(&response).unwrap();
+    let response: PresentationResponse<BbsScheme> = serde_bare::from_slice(&bytes).unwrap();
+
+    let mut nonce_store = MemoryNonceStore::default();
+    request.verify(&response, &mut nonce_store)?;
+    // Replaying the response is rejected
+    assert!(request.verify(&response, &mut nonce_store).is_err());
+
+    // A response only answers the request it was made for
+    let other_request = PresentationRequest::new("verifier", &schema, 60);
+    assert!(other_request
+        .verify(&response, &mut MemoryNonceStore::default())
+        .is_err());
+    let mut changed = request.clone();
+    changed.verifier_id = "another verifier".to_string();
+    assert!(changed
+        .verify(&response, &mut MemoryNonceStore::default())
+        .is_err());
+    let mut forged = response.clone();
+    forged.request_hash = other_request.hash();
+    assert!(other_request
+        .verify(&forged, &mut MemoryNonceStore::default())
+        .is_err());
+
+    // Expired requests can't be answered or verified
+    let mut expired = PresentationRequest::new("verifier", &schema, 0);
+    expired.expires -= 1;
+    assert!(expired.respond(&credentials).is_err());
+    let mut late = request.clone();
+    late.nonce = expired.nonce.clone();
+    late.expires = expired.expires;
+    assert!(late
+        .verify(&response, &mut MemoryNonceStore::default())
+        .is_err());
+    Ok(())
+}
@@ -58,24 +58,28 @@ fn test_range_proof_works(
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "name".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "age".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
     ];
     let cred_schema = CredentialSchema::new(Some(LABEL), Some(DESCRIPTION), &[], &schema_claims)
@@ -11,8 +11,8 @@ use credx::{
     presentation::{Presentation, PresentationCredential, PresentationSchema},
     random_string,
     statement::{
-        CommitmentStatement, EqualityStatement, RangeStatement, RevocationStatement,
-        SignatureStatement, Statements, VerifiableEncryptionStatement,
+        CommitmentStatement, EqualityStatement, RangeStatement, RegistryEpochPolicy,
+        RevocationStatement, SignatureStatement, Statements, VerifiableEncryptionStatement,
     },
     CredxResult,
 };
@@ -90,24 +90,21 @@ fn create_real_id_presentation_with_selective_disclosure<S: ShortGroupSignatureS
     let mut bank_statement_sig_st_id = Default::default();
 
     for (_, v) in real_id_presentation_schema.statements.iter() {
-        match v {
-            Statements::Signature(sig) => {
-                // println!("Signature {:?} {:?}", sig.id, sig.issuer.schema.label);
-                let label = sig.issuer.schema.label.clone().unwrap();
-                match label.as_str() {
-                    SOC_SEC_CRED_LABEL => {
-                        soc_sec_sig_st_id = sig.id.clone();
-                    }
-                    PASSPORT_CRED_LABEL => {
-                        dos_passport_sig_st_id = sig.id.clone();
-                    }
-                    BANK_STMT_CRED_LABEL => {
-                        bank_statement_sig_st_id = sig.id.clone();
-                    }
-                    &_ => println!("Not expected"),
+        if let Statements::Signature(sig) = v {
+            // println!("Signature {:?} {:?}", sig.id, sig.issuer.schema.label);
+            let label = sig.issuer.schema.label.clone().unwrap();
+            match label.as_str() {
+                SOC_SEC_CRED_LABEL => {
+                    soc_sec_sig_st_id = sig.id.clone();
                 }
+                PASSPORT_CRED_LABEL => {
+                    dos_passport_sig_st_id = sig.id.clone();
+                }
+                BANK_STMT_CRED_LABEL => {
+                    bank_statement_sig_st_id = sig.id.clone();
+                }
+                &_ => println!("Not expected"),
             }
-            _ => (),
         }
     }
 
@@ -117,28 +114,26 @@ fn create_real_id_presentation_with_selective_disclosure<S: ShortGroupSignatureS
         bank_statement_sig_st_id => vc_bank_stmt.credential.clone().into(),
     };
 
-    let alice_real_id_presentation = Presentation::create(
+    Presentation::create(
         &alice_credentials_for_real_id,
-        &real_id_presentation_schema,
-        &nonce,
-    );
-
-    alice_real_id_presentation
+        real_id_presentation_schema,
+        nonce,
+    )
 }
 
 fn create_real_id_presentation_schema<S: ShortGroupSignatureScheme>(
     vdr: &HashMap<String, IssuerPublic<S>>,
 ) -> PresentationSchema<S> {
     // Claims needed from Social Security Card issued by SSA
-    let ssa_soc_sec_statements = create_soc_sec_statements_for_realid(&vdr);
+    let ssa_soc_sec_statements = create_soc_sec_statements_for_realid(vdr);
     let soc_security_schema = vdr.get(SSA_DID).unwrap().schema.clone();
 
     // Claims needed from passport issued by DoS
-    let dos_passport_statements = create_dos_passport_statements_for_realid(&vdr);
+    let dos_passport_statements = create_dos_passport_statements_for_realid(vdr);
     let passport_schema = vdr.get(DOS_DID).unwrap().schema.clone();
 
     // Claims need from Bank Statement
-    let bank_statement_statements = create_bank_statement_statements_for_realid(&vdr);
+    let bank_statement_statements = create_bank_statement_statements_for_realid(vdr);
     let bank_statement_schema = vdr.get(BANK_DID).unwrap().schema.clone();
 
     // EqualityStatement is used to check that a non-disclosed claim is the same across multiple other statements.
@@ -163,15 +158,13 @@ fn create_real_id_presentation_schema<S: ShortGroupSignatureScheme>(
     real_id_statements.append(&mut bank_statement_statements.to_vec());
     real_id_statements.append(&mut [real_id_eq_st_name.into()].to_vec());
 
-    let real_id_presentation_schema = PresentationSchema::new(&real_id_statements);
-
-    real_id_presentation_schema
+    PresentationSchema::new(&real_id_statements)
 }
 
 fn create_bank_statement_statements_for_realid<S: ShortGroupSignatureScheme>(
     vdr: &HashMap<String, IssuerPublic<S>>,
 ) -> [Statements<S>; 5] {
-    let bank_public = vdr.get(&BANK_DID.to_string()).unwrap();
+    let bank_public = vdr.get(BANK_DID).unwrap();
     let current_date = Local::now().date_naive();
     let schema = bank_public.schema.clone();
 
@@ -198,6 +191,8 @@ fn create_bank_statement_statements_for_realid<S: ShortGroupSignatureScheme>(
         accumulator: bank_public.revocation_registry,
         verification_key: bank_public.revocation_verifying_key,
         claim: schema.claim_indices.get_index_of("identifier").unwrap(),
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
 
     // CommtimentStatement creates a unique value based on a claim. Is also used to link to range statements.
@@ -259,7 +254,7 @@ fn create_bank_statement_statements_for_realid<S: ShortGroupSignatureScheme>(
 fn create_dos_passport_statements_for_realid<S: ShortGroupSignatureScheme>(
     vdr: &HashMap<String, IssuerPublic<S>>,
 ) -> [Statements<S>; 7] {
-    let dos_public = vdr.get(&DOS_DID.to_string()).unwrap();
+    let dos_public = vdr.get(DOS_DID).unwrap();
     let schema = dos_public.schema.clone();
     let current_date = Local::now().date_naive();
 
@@ -281,6 +276,8 @@ fn create_dos_passport_statements_for_realid<S: ShortGroupSignatureScheme>(
         accumulator: dos_public.revocation_registry,
         verification_key: dos_public.revocation_verifying_key,
         claim: schema.claim_indices.get_index_of("identifier").unwrap(),
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
 
     // CommtimentStatement creates a unique value based on a claim. Is also used to link to range statements.
@@ -319,7 +316,7 @@ fn create_dos_passport_statements_for_realid<S: ShortGroupSignatureScheme>(
     // dob, date of expiration
 
     // dob lower is Jan 1, 1900
-    let dob_lower_date = chrono::NaiveDate::from_ymd_opt(1900, 01, 01).unwrap();
+    let dob_lower_date = chrono::NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
     let dob_lower = days_since_1_jan_1900(dob_lower_date).unwrap();
 
     // dob upper is the current date minus 16 years
@@ -387,7 +384,7 @@ fn create_dos_passport_statements_for_realid<S: ShortGroupSignatureScheme>(
 fn create_soc_sec_statements_for_realid<S: ShortGroupSignatureScheme>(
     vdr: &HashMap<String, IssuerPublic<S>>,
 ) -> [Statements<S>; 3] {
-    let ssa_public = vdr.get(&SSA_DID.to_string()).unwrap();
+    let ssa_public = vdr.get(SSA_DID).unwrap();
     let schema = ssa_public.schema.clone();
 
     let soc_sec_sig_st = SignatureStatement {
@@ -402,6 +399,8 @@ fn create_soc_sec_statements_for_realid<S: ShortGroupSignatureScheme>(
         accumulator: ssa_public.revocation_registry,
         verification_key: ssa_public.revocation_verifying_key,
         claim: schema.claim_indices.get_index_of("identifier").unwrap(),
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
 
     let soc_sec_verenc_st = VerifiableEncryptionStatement {
@@ -422,12 +421,15 @@ fn create_soc_sec_statements_for_realid<S: ShortGroupSignatureScheme>(
     soc_sec_statements
 }
 
-fn issuer_setup<S: ShortGroupSignatureScheme>() -> (
+type IssuerWallet<S> = HashMap<String, Issuer<S>>;
+type IssuerSetup<S> = (
     HashMap<String, IssuerPublic<S>>,
-    HashMap<String, Issuer<S>>,
-    HashMap<String, Issuer<S>>,
-    HashMap<String, Issuer<S>>,
-) {
+    IssuerWallet<S>,
+    IssuerWallet<S>,
+    IssuerWallet<S>,
+);
+
+fn issuer_setup<S: ShortGroupSignatureScheme>() -> IssuerSetup<S> {
     // issuer setup
     let mut vdr: HashMap<String, IssuerPublic<S>> = HashMap::new();
     // setup bank
@@ -516,18 +518,21 @@ fn define_soc_sec_claims_schemas() -> Vec<ClaimSchema> {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "soc_sec_number".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "first_last_name".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
     ];
     schema_claims.to_vec()
@@ -540,60 +545,70 @@ fn define_passport_claims_schemas() -> Vec<ClaimSchema> {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "passport_number".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "first_last_name".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "nationality".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "dob".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "sex".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "place_of_birth".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "date_of_issue".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "date_of_expiration".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "claim_issuing_authority".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
     ];
     schema_claims.to_vec()
@@ -606,54 +621,63 @@ fn define_bank_statement_claims_schemas() -> Vec<ClaimSchema> {
             label: "identifier".to_string(),
             print_friendly: false,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "account_number".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "first_last_name".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "start_date".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Number,
             label: "end_date".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_line1".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_line2".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_state".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
         ClaimSchema {
             claim_type: ClaimType::Hashed,
             label: "address_zip".to_string(),
             print_friendly: true,
             validators: vec![],
+            ..Default::default()
         },
     ];
     schema_claims.to_vec()
@@ -663,7 +687,7 @@ fn request_bank_statement_credential<S: ShortGroupSignatureScheme>(
     bank_wallet: HashMap<String, Issuer<S>>,
 ) -> CredxResult<CredentialBundle<S>> {
     let current_date = Local::now().date_naive();
-    let mut bank_a: Issuer<S> = bank_wallet.get(&BANK_DID.to_string()).unwrap().to_owned();
+    let mut bank_a: Issuer<S> = bank_wallet.get(BANK_DID).unwrap().to_owned();
 
     // Start date is 45 days in the past. Map dates to integers - it's the number of days since 1/1/1900
     let forty_five_days_ago = current_date - Duration::days(45);
@@ -692,11 +716,11 @@ fn request_passport_credential<S: ShortGroupSignatureScheme>(
     dos_wallet: HashMap<String, Issuer<S>>,
 ) -> CredxResult<CredentialBundle<S>> {
     // Map dates to integers - it's the number of days since 1/1/1900
-    let dob = chrono::NaiveDate::from_ymd_opt(2000, 02, 17).unwrap();
+    let dob = chrono::NaiveDate::from_ymd_opt(2000, 2, 17).unwrap();
     let dob_since_1900 = days_since_1_jan_1900(dob)?;
-    let date_of_issue = chrono::NaiveDate::from_ymd_opt(2020, 02, 15).unwrap();
+    let date_of_issue = chrono::NaiveDate::from_ymd_opt(2020, 2, 15).unwrap();
     let date_of_issue_since_1900 = days_since_1_jan_1900(date_of_issue)?;
-    let date_of_expiration = chrono::NaiveDate::from_ymd_opt(2030, 02, 14).unwrap();
+    let date_of_expiration = chrono::NaiveDate::from_ymd_opt(2030, 2, 14).unwrap();
     let date_of_expiration_since_1900 = days_since_1_jan_1900(date_of_expiration)?;
 
     let claims: [ClaimData; 10] = [
@@ -712,7 +736,7 @@ fn request_passport_credential<S: ShortGroupSignatureScheme>(
         HashedClaim::from("US DoS").into(),
     ];
 
-    let mut dos = dos_wallet.get(&DOS_DID.to_string()).unwrap().to_owned();
+    let mut dos = dos_wallet.get(DOS_DID).unwrap().to_owned();
     let vc_passport = dos.sign_credential(&claims)?;
     Ok(vc_passport)
 }
@@ -726,14 +750,14 @@ fn request_social_security_credential<S: ShortGroupSignatureScheme>(
         HashedClaim::from("Alice Verifiable").into(),
     ];
 
-    let mut ssa = ssa_wallet.get(&SSA_DID.to_string()).unwrap().to_owned();
+    let mut ssa = ssa_wallet.get(SSA_DID).unwrap().to_owned();
     let vc_soc_sec = ssa.sign_credential(&claims)?;
 
     Ok(vc_soc_sec)
 }
 
 fn days_since_1_jan_1900(date: NaiveDate) -> CredxResult<i64> {
-    let base_date = chrono::NaiveDate::from_ymd_opt(1900, 01, 01).unwrap();
+    let base_date = chrono::NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
     let days_since_base = (date - base_date).num_days();
     Ok(days_since_base)
 }
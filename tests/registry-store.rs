@@ -0,0 +1,231 @@
+use credx::claim::{ClaimData, ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::accumulator::vb20;
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::revocation_registry::{
+    FileRegistryStore, MemoryRegistryStore, RegistryStore, RevocationRegistry,
+};
+use credx::statement::{RegistryEpochPolicy, RevocationStatement, SignatureStatement};
+use credx::{random_string, CredxResult};
+use indexmap::indexmap;
+use maplit::btreeset;
+use rand::thread_rng;
+use std::path::PathBuf;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn log_path() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "credx-registry-{}.log",
+        random_string(16, thread_rng())
+    ))
+}
+
+#[test]
+fn file_registry_store() {
+    setup();
+    let path = log_path();
+    let res = test_file_registry_store(&path);
+    let _ = std::fs::remove_file(&path);
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_file_registry_store(path: &PathBuf) -> CredxResult<()> {
+    let sk = vb20::SecretKey::new(None);
+    let elements = ["alice", "bob", "carol"]
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>();
+
+    let mut file = RevocationRegistry::with_store(FileRegistryStore::open(path)?, thread_rng())?;
+    let mut memory = RevocationRegistry::with_store(MemoryRegistryStore::default(), thread_rng())?;
+    memory.value = file.value;
+    memory.store.history = file.history().to_vec();
+
+    apply_changes(&mut file, &sk, &elements)?;
+    apply_changes(&mut memory, &sk, &elements)?;
+    assert_eq!(file.value, memory.value);
+    assert_eq!(file.epoch, 2);
+    assert_eq!(file.history(), memory.history());
+    assert!(file.contains("bob"));
+    assert!(!file.is_active("bob"));
+    assert!(file.is_active("carol"));
+    assert!(!file.contains("dave"));
+
+    // Reopening the log restores the elements, history and latest value
+    drop(file);
+    let reopened = RevocationRegistry::with_store(FileRegistryStore::open(path)?, thread_rng())?;
+    assert_eq!(reopened.value, memory.value);
+    assert_eq!(reopened.epoch, memory.epoch);
+    assert_eq!(reopened.history(), memory.history());
+    assert_eq!(reopened.store.len(), 3);
+    assert_eq!(reopened.store.active_len(), 1);
+    assert!(!reopened.is_active("bob"));
+
+    // Only the log path is serialized
+    let json = serde_json::to_string(&reopened).unwrap();
+    assert!(!json.contains("carol"));
+    let restored: RevocationRegistry<FileRegistryStore> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.store.path(), path.as_path());
+    assert_eq!(restored.value, memory.value);
+    assert_eq!(restored.history(), memory.history());
+    Ok(())
+}
+
+fn apply_changes<R: RegistryStore>(
+    registry: &mut RevocationRegistry<R>,
+    sk: &vb20::SecretKey,
+    elements: &[String],
+) -> CredxResult<()> {
+    registry.add(elements)?;
+    registry.revoke(sk, &elements[..1])?;
+    registry.revoke(sk, &elements[1..2])?;
+    Ok(())
+}
+
+#[test]
+fn issuer_with_file_registry_store() {
+    setup();
+    let path = log_path();
+    let res = test_issuer_with_file_registry_store(&path);
+    let _ = std::fs::remove_file(&path);
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_issuer_with_file_registry_store(path: &PathBuf) -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    let cred_schema = CredentialSchema::new(Some("Test"), None, &[], &schema_claims)?;
+    let seed = b"file registry store issuer seed";
+    let (_, mut issuer) = Issuer::<BbsScheme, FileRegistryStore>::new_with_store(
+        &cred_schema,
+        seed,
+        FileRegistryStore::open(path)?,
+    )?;
+    let claims = |id: &str, name: &str| -> Vec<ClaimData> {
+        vec![
+            RevocationClaim::from(id).into(),
+            HashedClaim::from(name).into(),
+        ]
+    };
+    let mut alice = issuer.sign_credential(&claims("alice-id", "Alice"))?;
+    let _ = issuer.sign_credential(&claims("bob-id", "Bob"))?;
+    issuer.revoke_credentials(&[RevocationClaim::from("bob-id")])?;
+    let value = issuer.revocation_registry.value;
+    drop(issuer);
+
+    // The issuer keys come from the seed and the registry from the log
+    let (issuer_public, mut issuer) = Issuer::<BbsScheme, FileRegistryStore>::new_with_store(
+        &cred_schema,
+        seed,
+        FileRegistryStore::open(path)?,
+    )?;
+    assert_eq!(issuer.revocation_registry.value, value);
+    assert_eq!(issuer.revocation_registry.epoch, 1);
+    assert!(issuer.sign_credential(&claims("bob-id", "Bob")).is_err());
+
+    let latest = *issuer.revocation_registry.history().last().unwrap();
+    alice.replace_revocation_handle(
+        issuer.update_revocation_handle(RevocationClaim::from("alice-id"))?,
+        latest,
+    );
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"name".to_string()},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let acc_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), acc_st.into()]);
+    let credentials = indexmap! { "sig".to_string() => alice.credential.into() };
+    let nonce = b"file registry store nonce";
+    let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+    presentation.verify(&presentation_schema, nonce)
+}
+
+#[test]
+fn file_registry_store_torn_write() {
+    setup();
+    let path = log_path();
+    let res = test_file_registry_store_torn_write(&path);
+    let _ = std::fs::remove_file(&path);
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_file_registry_store_torn_write(path: &PathBuf) -> CredxResult<()> {
+    let sk = vb20::SecretKey::new(None);
+    let elements = ["alice", "bob", "carol"]
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>();
+    let mut registry =
+        RevocationRegistry::with_store(FileRegistryStore::open(path)?, thread_rng())?;
+    registry.add(&elements)?;
+
+    // A revocation is one record so the active set and history can't diverge
+    let before = std::fs::read(path).unwrap();
+    registry.revoke(&sk, &elements[..1])?;
+    let after = std::fs::read(path).unwrap();
+    assert_eq!(
+        after[before.len()..]
+            .iter()
+            .filter(|b| **b == b'\n')
+            .count(),
+        1
+    );
+    drop(registry);
+
+    // Crash while writing the next revocation
+    let mut torn = after.clone();
+    torn.extend_from_slice(&after[before.len()..after.len() - 10]);
+    std::fs::write(path, &torn).unwrap();
+    let mut registry =
+        RevocationRegistry::with_store(FileRegistryStore::open(path)?, thread_rng())?;
+    assert_eq!(std::fs::read(path).unwrap(), after);
+    assert_eq!(registry.epoch, 1);
+    assert!(!registry.is_active("alice"));
+    assert!(registry.is_active("bob"));
+
+    // The log stays usable after the torn line is removed
+    registry.revoke(&sk, &elements[1..2])?;
+    let value = registry.value;
+    drop(registry);
+    let registry = RevocationRegistry::with_store(FileRegistryStore::open(path)?, thread_rng())?;
+    assert_eq!(registry.value, value);
+    assert_eq!(registry.epoch, 2);
+    assert!(!registry.is_active("bob"));
+    drop(registry);
+
+    // A complete line that can't be read is corruption, not a torn write
+    let mut corrupt = after[..before.len()].to_vec();
+    corrupt.extend_from_slice(b"{\"Revoke\":\n");
+    corrupt.extend_from_slice(&after[before.len()..]);
+    std::fs::write(path, &corrupt).unwrap();
+    assert!(FileRegistryStore::open(path).is_err());
+    Ok(())
+}
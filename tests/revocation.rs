@@ -1,14 +1,20 @@
 use credx::claim::{ClaimData, ClaimType, ClaimValidator, HashedClaim, RevocationClaim};
-use credx::credential::{ClaimSchema, CredentialSchema};
-use credx::issuer::Issuer;
+use credx::credential::{ClaimSchema, Credential, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
 use credx::knox::accumulator::vb20::Element;
 use credx::knox::bbs::BbsScheme;
 use credx::prelude::{
     MembershipClaim, MembershipCredential, MembershipRegistry, MembershipSigningKey,
     MembershipStatement, MembershipVerificationKey,
 };
-use credx::presentation::{Presentation, PresentationCredential, PresentationSchema};
-use credx::statement::{RevocationStatement, SignatureStatement};
+use credx::presentation::{
+    Presentation, PresentationCredential, PresentationProofs, PresentationSchema,
+};
+use credx::revocation_registry::{RegistryEpoch, RegistryUpdate, SignedAccumulatorState};
+use credx::statement::{
+    RegistryEpochPolicy, RevocationStatement, SignatureStatement, SignedStatePolicy,
+    SuspensionStatement,
+};
 use credx::{random_string, CredxResult};
 use indexmap::{indexmap, IndexMap};
 use maplit::btreeset;
@@ -26,6 +32,7 @@ macro_rules! setup_issuer {
                 label: "identifier".to_string(),
                 print_friendly: false,
                 validators: vec![],
+                ..Default::default()
             },
             ClaimSchema {
                 claim_type: ClaimType::Hashed,
@@ -35,6 +42,7 @@ macro_rules! setup_issuer {
                     min: None,
                     max: Some(u8::MAX as usize),
                 }],
+                ..Default::default()
             },
         ];
         let cred_schema =
@@ -111,6 +119,8 @@ fn test_revocation() {
         accumulator: issuer_public.revocation_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let presentation_schema = PresentationSchema::new(&[sig_st.clone().into(), acc_st.into()]);
 
@@ -128,6 +138,8 @@ fn test_revocation() {
         accumulator: updated_registry,
         verification_key: issuer_public.revocation_verifying_key,
         claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
     };
     let presentation_schema_updated =
         PresentationSchema::new(&[sig_st.clone().into(), acc_st_updated.into()]);
@@ -269,3 +281,349 @@ fn test_explicit_membership_witness_sharing_attempt_after_removal() {
     mem_st.id.clone() => dummy_membership_credential_2_updated.into() };
     assert!(create_and_verify(presentation_schema, credentials_1_cheating).is_err())
 }
+
+const CRED_ID_3: &str = "71742856-6eda-45fb-a709-d22ebb5ec8a5";
+
+#[test]
+fn test_registry_update_sync() {
+    setup_issuer!(issuer, issuer_public);
+    setup_cred!(issuer, credential_1, CRED_ID_1, STR_FOR_MEMBERSHIP_1);
+    setup_cred!(issuer, credential_2, CRED_ID_2, STR_FOR_MEMBERSHIP_2);
+    let mut credential_3 = issuer
+        .sign_credential(&[
+            RevocationClaim::from(CRED_ID_3).into(),
+            HashedClaim::from(STR_FOR_MEMBERSHIP_1).into(),
+        ])
+        .unwrap();
+    assert_eq!(issuer_public.revocation_epoch, 0);
+
+    let update_1 = issuer
+        .revoke_credentials(&[RevocationClaim::from(CRED_ID_1)])
+        .unwrap();
+    let update_2 = issuer
+        .revoke_credentials(&[RevocationClaim::from(CRED_ID_2)])
+        .unwrap();
+    assert_eq!(update_1.epoch, 1);
+    assert_eq!(update_2.epoch, 2);
+    assert_eq!(update_2.deletions.len(), 1);
+    // Revoking an unknown or already revoked credential doesn't publish an update
+    assert!(issuer
+        .revoke_credentials(&[RevocationClaim::from(CRED_ID_1)])
+        .is_err());
+    assert_eq!(issuer.revocation_registry.epoch, 2);
+
+    // Updates are published, so make sure they survive serialization
+    let updates: Vec<RegistryUpdate> =
+        serde_json::from_str(&serde_json::to_string(&[update_1, update_2]).unwrap()).unwrap();
+
+    // Missing an epoch is an error
+    let mut skipped = credential_3.clone();
+    assert!(skipped.apply_registry_updates(&updates[1..]).is_err());
+    assert_eq!(skipped.issuer.revocation_epoch, 0);
+
+    // Revoked credentials can't be updated
+    let mut revoked = credential_1.clone();
+    assert!(revoked.apply_registry_updates(&updates).is_err());
+    let mut revoked = credential_2.clone();
+    assert!(revoked.apply_registry_updates(&updates[..1]).is_ok());
+    assert!(revoked.apply_registry_updates(&updates).is_err());
+
+    credential_3.apply_registry_updates(&updates[..1]).unwrap();
+    credential_3.apply_registry_updates(&updates).unwrap();
+    assert_eq!(credential_3.issuer.revocation_epoch, 2);
+    // Already applied updates are skipped
+    credential_3.apply_registry_updates(&updates).unwrap();
+
+    let issuer_public = IssuerPublic::from(&issuer);
+    assert_eq!(
+        credential_3.issuer.revocation_registry,
+        issuer_public.revocation_registry
+    );
+    define_sig_st!(issuer_public, sig_st);
+    let acc_st = RevocationStatement {
+        id: random_string(16, rand::thread_rng()),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.clone().into(), acc_st.into()]);
+    let credentials = indexmap! { sig_st.id.clone() => credential_3.credential.into() };
+    assert!(create_and_verify(presentation_schema.clone(), credentials).is_ok());
+    let credentials = indexmap! { sig_st.id.clone() => credential_1.credential.into() };
+    assert!(create_and_verify(presentation_schema, credentials).is_err());
+}
+
+#[test]
+fn test_registry_epoch_policies() {
+    setup_issuer!(issuer, issuer_public);
+    setup_cred!(issuer, credential_1, CRED_ID_1, STR_FOR_MEMBERSHIP_1);
+    setup_cred!(issuer, credential_2, CRED_ID_2, STR_FOR_MEMBERSHIP_2);
+    assert_eq!(credential_2.credential.revocation_epoch, 0);
+    let update = issuer
+        .revoke_credentials(&[RevocationClaim::from(CRED_ID_1)])
+        .unwrap();
+    assert_eq!(issuer.revocation_registry.history().len(), 2);
+    assert_eq!(
+        issuer.revocation_registry.value_at(1),
+        Some(&RegistryEpoch::from(&update))
+    );
+    let epoch_0 = *issuer.revocation_registry.value_at(0).unwrap();
+    let epoch_1 = *issuer.revocation_registry.value_at(1).unwrap();
+    assert_eq!(epoch_0.value, issuer_public.revocation_registry);
+
+    define_sig_st!(issuer_public, sig_st);
+    let schema = |epochs| {
+        let acc_st = RevocationStatement {
+            id: "revocation".to_string(),
+            reference_id: sig_st.id.clone(),
+            accumulator: issuer.revocation_registry.value,
+            verification_key: issuer_public.revocation_verifying_key,
+            claim: 0,
+            epochs,
+            signed_states: None,
+        };
+        PresentationSchema::new(&[sig_st.clone().into(), acc_st.into()])
+    };
+    let prove = |schema: &PresentationSchema<BbsScheme>, credential: &Credential<BbsScheme>| {
+        let credentials = indexmap! { sig_st.id.clone() => credential.clone().into() };
+        create_and_verify(schema.clone(), credentials)
+    };
+
+    // Presentations against a pinned historical epoch still verify, e.g. in an audit
+    let pinned_0 = schema(RegistryEpochPolicy::Pinned(epoch_0));
+    assert!(prove(&pinned_0, &credential_1.credential).is_ok());
+    assert!(prove(&pinned_0, &credential_2.credential).is_ok());
+    let pinned_1 = schema(RegistryEpochPolicy::Pinned(epoch_1));
+    assert!(prove(&pinned_1, &credential_2.credential).is_err());
+
+    // Any recent epoch is accepted
+    let history = issuer.revocation_registry.recent(60).to_vec();
+    assert_eq!(history.len(), 2);
+    let policy = |history: &[RegistryEpoch], as_of| {
+        schema(RegistryEpochPolicy::Recent {
+            history: history.to_vec(),
+            max_age: 60,
+            as_of,
+        })
+    };
+    let recent = policy(&history, None);
+    assert!(prove(&recent, &credential_2.credential).is_ok());
+    // Epoch 0 was replaced more than a minute before, e.g. a delayed audit
+    let audit_time = history[1].timestamp + 61;
+    let stale = policy(&history, Some(audit_time));
+    assert!(prove(&stale, &credential_2.credential).is_err());
+    assert_eq!(
+        issuer.revocation_registry.recent_as_of(60, audit_time),
+        &history[1..]
+    );
+    assert_eq!(
+        issuer.revocation_registry.recent_as_of(60, audit_time - 1),
+        &history[..]
+    );
+    assert!(prove(
+        &policy(&history, Some(audit_time - 1)),
+        &credential_2.credential
+    )
+    .is_ok());
+    // The age is measured from the verifier's clock, not the newest value in the history
+    let mut old = history.clone();
+    old.iter_mut()
+        .for_each(|e| e.timestamp = e.timestamp.saturating_sub(120));
+    assert!(prove(&policy(&old, None), &credential_2.credential).is_err());
+
+    let mut synced = credential_2.clone();
+    synced.apply_registry_updates(&[update]).unwrap();
+    assert_eq!(synced.credential.revocation_epoch, 1);
+    assert!(prove(&pinned_1, &synced.credential).is_ok());
+    assert!(prove(&stale, &synced.credential).is_ok());
+    assert!(prove(&pinned_0, &synced.credential).is_err());
+
+    // The presentation commits to the epoch it used
+    let credentials = indexmap! { sig_st.id.clone() => synced.credential.clone().into() };
+    let nonce = b"registry epoch nonce";
+    let mut presentation = Presentation::create(&credentials, &recent, nonce).unwrap();
+    match presentation.proofs.get_mut("revocation") {
+        Some(PresentationProofs::Revocation(proof)) => {
+            assert_eq!(proof.epoch, 1);
+            proof.epoch = 0;
+        }
+        _ => panic!("expected a revocation proof"),
+    }
+    assert!(presentation.verify(&recent, nonce).is_err());
+}
+
+#[test]
+fn test_signed_accumulator_states() {
+    setup_issuer!(issuer, issuer_public);
+    setup_cred!(issuer, _credential_1, CRED_ID_1, STR_FOR_MEMBERSHIP_1);
+    setup_cred!(issuer, credential_2, CRED_ID_2, STR_FOR_MEMBERSHIP_2);
+    let update = issuer
+        .revoke_credentials(&[RevocationClaim::from(CRED_ID_1)])
+        .unwrap();
+    let signed_0 = issuer.sign_revocation_registry_epoch(0).unwrap();
+    let signed_1 = issuer.sign_revocation_registry().unwrap();
+    assert_eq!(signed_1.epoch, 1);
+    assert_eq!(signed_1.value, update.value);
+    assert!(issuer.sign_revocation_registry_epoch(2).is_err());
+    assert!(signed_1
+        .verify(&issuer_public.registry_verifying_key)
+        .is_ok());
+    let (other_public, _) = Issuer::<BbsScheme>::new(&issuer.schema);
+    assert!(signed_1
+        .verify(&other_public.registry_verifying_key)
+        .is_err());
+    let mut forged = signed_1.clone();
+    forged.value = signed_0.value;
+    assert!(forged
+        .verify(&issuer_public.registry_verifying_key)
+        .is_err());
+
+    define_sig_st!(issuer_public, sig_st);
+    let schema_with_policy = |policy: SignedStatePolicy| {
+        let acc_st = RevocationStatement {
+            id: "revocation".to_string(),
+            reference_id: sig_st.id.clone(),
+            accumulator: issuer.revocation_registry.value,
+            verification_key: issuer_public.revocation_verifying_key,
+            claim: 0,
+            epochs: RegistryEpochPolicy::Recent {
+                history: issuer.revocation_registry.history().to_vec(),
+                max_age: 60,
+                as_of: None,
+            },
+            signed_states: Some(policy),
+        };
+        PresentationSchema::new(&[sig_st.clone().into(), acc_st.into()])
+    };
+    let schema = |states: Vec<SignedAccumulatorState>| {
+        schema_with_policy(SignedStatePolicy::new(&issuer_public, states))
+    };
+    let prove = |schema: &PresentationSchema<BbsScheme>, credential: &Credential<BbsScheme>| {
+        let credentials = indexmap! { sig_st.id.clone() => credential.clone().into() };
+        create_and_verify(schema.clone(), credentials)
+    };
+
+    let mut synced = credential_2.clone();
+    synced.apply_registry_updates(&[update]).unwrap();
+
+    // Only registry values with a signed state are accepted
+    let latest = schema(vec![signed_1.clone()]);
+    assert!(prove(&latest, &synced.credential).is_ok());
+    assert!(prove(&latest, &credential_2.credential).is_err());
+    let both = schema(vec![signed_0.clone(), signed_1.clone()]);
+    assert!(prove(&both, &synced.credential).is_ok());
+    assert!(prove(&both, &credential_2.credential).is_ok());
+
+    // Forged or foreign signed states are rejected
+    assert!(prove(&schema(vec![forged]), &synced.credential).is_err());
+    let (_, other) = Issuer::<BbsScheme>::new(&issuer.schema);
+    let mut foreign = other.sign_revocation_registry().unwrap();
+    foreign.registry_id = issuer_public.id.clone();
+    foreign.epoch = 1;
+    foreign.value = signed_1.value;
+    assert!(prove(&schema(vec![foreign]), &synced.credential).is_err());
+    let mut renamed = signed_1.clone();
+    renamed.registry_id = other.id.clone();
+    assert!(prove(&schema(vec![renamed]), &synced.credential).is_err());
+
+    // The policy must be for the issuer of the signature statement
+    let other_public = IssuerPublic::from(&other);
+    let vouched = SignedAccumulatorState::new(
+        &other_public.id,
+        1,
+        signed_1.value,
+        &other.registry_signing_key,
+    )
+    .unwrap();
+    let policy = SignedStatePolicy::new(&other_public, vec![vouched]);
+    assert!(policy.check(1, signed_1.value).is_ok());
+    assert!(prove(&schema_with_policy(policy), &synced.credential).is_err());
+}
+
+#[test]
+fn test_suspension_registry_updates() {
+    setup_issuer!(issuer, issuer_public);
+    setup_cred!(issuer, credential_1, CRED_ID_1, STR_FOR_MEMBERSHIP_1);
+    setup_cred!(issuer, credential_2, CRED_ID_2, STR_FOR_MEMBERSHIP_2);
+    let mut credential_3 = issuer
+        .sign_credential(&[
+            RevocationClaim::from(CRED_ID_3).into(),
+            HashedClaim::from(STR_FOR_MEMBERSHIP_1).into(),
+        ])
+        .unwrap();
+    assert_eq!(issuer_public.suspension_epoch, 0);
+
+    let id_1 = issuer
+        .ledger
+        .find_by_revocation_value(CRED_ID_1)
+        .unwrap()
+        .id
+        .clone();
+    let id_2 = issuer
+        .ledger
+        .find_by_revocation_value(CRED_ID_2)
+        .unwrap()
+        .id
+        .clone();
+    let update_1 = issuer.suspend_credential(&id_1).unwrap();
+    let update_2 = issuer.suspend_credential(&id_2).unwrap();
+    let update_3 = issuer.reinstate_credential(&id_1).unwrap();
+    assert_eq!(update_3.epoch, 3);
+    assert_eq!(issuer.revocation_registry.epoch, 0);
+
+    let updates: Vec<RegistryUpdate> =
+        serde_json::from_str(&serde_json::to_string(&[update_1, update_2, update_3]).unwrap())
+            .unwrap();
+    credential_3.apply_suspension_updates(&updates).unwrap();
+    assert_eq!(credential_3.credential.suspension_epoch, 3);
+    let mut suspended = credential_2.clone();
+    assert!(suspended.apply_suspension_updates(&updates).is_err());
+
+    let issuer_public = IssuerPublic::from(&issuer);
+    assert_eq!(
+        credential_3.issuer.suspension_registry,
+        issuer_public.suspension_registry
+    );
+    define_sig_st!(issuer_public, sig_st);
+    let acc_st = RevocationStatement {
+        id: random_string(16, rand::thread_rng()),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
+    };
+    let sus_st = SuspensionStatement {
+        id: random_string(16, rand::thread_rng()),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.suspension_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+    };
+    let presentation_schema =
+        PresentationSchema::new(&[sig_st.clone().into(), acc_st.into(), sus_st.into()]);
+    let credentials = indexmap! { sig_st.id.clone() => credential_3.credential.into() };
+    assert!(create_and_verify(presentation_schema.clone(), credentials).is_ok());
+    let credentials = indexmap! { sig_st.id.clone() => credential_2.credential.into() };
+    assert!(create_and_verify(presentation_schema.clone(), credentials).is_err());
+
+    // The lifted credential only needs a new suspension handle
+    let mut lifted = credential_1.clone();
+    let handle = issuer
+        .update_suspension_handle(RevocationClaim::from(CRED_ID_1))
+        .unwrap();
+    lifted.replace_suspension_handle(
+        handle,
+        issuer.suspension_registry.value,
+        issuer.suspension_registry.epoch,
+    );
+    let credentials = indexmap! { sig_st.id.clone() => lifted.credential.into() };
+    assert!(create_and_verify(presentation_schema, credentials).is_ok());
+    assert!(issuer
+        .update_suspension_handle(RevocationClaim::from(CRED_ID_2))
+        .is_err());
+}
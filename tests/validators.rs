@@ -0,0 +1,268 @@
+use blsful::inner_types::*;
+use credx::blind::BlindCredentialRequest;
+use credx::claim::{
+    ChecksumAlgorithm, ClaimData, ClaimType, ClaimValidator, HashedClaim, RevocationClaim,
+    ScalarClaim,
+};
+use credx::credential::{
+    ClaimSchema, ClaimValidationFailure, ClaimValidationReason, CredentialSchema,
+};
+use credx::error::Error;
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::CredxResult;
+use maplit::btreemap;
+use rand::thread_rng;
+use std::collections::BTreeMap;
+
+const CRED_ID: &str = "2b8e4f6a-1c3d-4e5f-8a9b-0c1d2e3f4a5b";
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn validator_schema() -> CredxResult<CredentialSchema> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Scalar,
+            label: "link_secret".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "card_number".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::And(vec![
+                ClaimValidator::Length {
+                    min: Some(16),
+                    max: Some(16),
+                },
+                ClaimValidator::Checksum(ChecksumAlgorithm::Luhn),
+                ClaimValidator::Or(vec![
+                    ClaimValidator::Prefix("4".to_string()),
+                    ClaimValidator::Prefix("5".to_string()),
+                ]),
+            ])],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "email".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::Not(Box::new(ClaimValidator::Suffix(
+                "@example.invalid".to_string(),
+            )))],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "country".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::NotAnyOf(vec![
+                HashedClaim::from("KP").into(),
+                HashedClaim::from("IR").into(),
+            ])],
+            ..Default::default()
+        },
+    ];
+    CredentialSchema::new(Some("Card"), None, &["link_secret"], &schema_claims)
+}
+
+fn known_claims(card_number: &str, email: &str, country: &str) -> BTreeMap<String, ClaimData> {
+    btreemap! {
+        "identifier".to_string() => RevocationClaim::from(CRED_ID).into(),
+        "card_number".to_string() => HashedClaim::from(card_number).into(),
+        "email".to_string() => HashedClaim::from(email).into(),
+        "country".to_string() => HashedClaim::from(country).into(),
+    }
+}
+
+fn sign(
+    issuer: &mut Issuer<BbsScheme>,
+    card_number: &str,
+    email: &str,
+    country: &str,
+) -> CredxResult<()> {
+    let mut claims = known_claims(card_number, email, country);
+    claims.insert(
+        "link_secret".to_string(),
+        ScalarClaim::from(Scalar::random(thread_rng())).into(),
+    );
+    let schema = issuer.schema.clone();
+    let claims = schema
+        .claim_indices
+        .iter()
+        .map(|label| claims[label].clone())
+        .collect::<Vec<_>>();
+    issuer.sign_credential(&claims).map(|_| ())
+}
+
+fn blind_sign(
+    issuer: &mut Issuer<BbsScheme>,
+    card_number: &str,
+    email: &str,
+    country: &str,
+) -> CredxResult<()> {
+    let issuer_public = IssuerPublic::from(&*issuer);
+    let blind_claims = btreemap! {
+        "link_secret".to_string() => ScalarClaim::from(Scalar::random(thread_rng())).into(),
+    };
+    let (request, _) = BlindCredentialRequest::new(&issuer_public, &blind_claims)?;
+    issuer
+        .blind_sign_credential(&request, &known_claims(card_number, email, country))
+        .map(|_| ())
+}
+
+#[test]
+fn validators_enforced_at_issuance() {
+    setup();
+    let res = test_validators_enforced_at_issuance();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_validators_enforced_at_issuance() -> CredxResult<()> {
+    let schema = validator_schema()?;
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+
+    for f in [sign, blind_sign] {
+        f(&mut issuer, "4111111111111111", "john@example.com", "AU")?;
+        // Fails the Luhn check
+        assert!(f(&mut issuer, "4111111111111112", "john@example.com", "AU").is_err());
+        // Wrong prefix
+        assert!(f(&mut issuer, "6011111111111117", "john@example.com", "AU").is_err());
+        assert!(f(
+            &mut issuer,
+            "4111111111111111",
+            "john@example.invalid",
+            "AU"
+        )
+        .is_err());
+        assert!(f(&mut issuer, "4111111111111111", "john@example.com", "KP").is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn validation_report() {
+    setup();
+    let res = test_validation_report();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_validation_report() -> CredxResult<()> {
+    let schema = validator_schema()?;
+    let claims = |card_number, email, country| {
+        let mut claims = known_claims(card_number, email, country);
+        claims.insert(
+            "link_secret".to_string(),
+            ScalarClaim::from(Scalar::random(thread_rng())).into(),
+        );
+        schema
+            .claim_indices
+            .iter()
+            .map(|label| claims[label].clone())
+            .collect::<Vec<_>>()
+    };
+    schema.validate(&claims("4111111111111111", "john@example.com", "AU"))?;
+
+    let mut invalid = claims("4111111111111112", "john@example.com", "KP");
+    invalid[3] = RevocationClaim::from("john@example.com").into();
+    let report = match schema.validate(&invalid) {
+        Err(Error::ClaimValidation(report)) => report,
+        res => panic!("expected a validation report, got {:?}", res),
+    };
+    assert!(!report.is_valid());
+    assert_eq!(report.labels(), vec!["card_number", "email", "country"]);
+    assert_eq!(
+        report.failures[0].validator.as_ref(),
+        schema.claims[2].validators.first()
+    );
+    assert_eq!(report.failures[0].reason, ClaimValidationReason::NotValid);
+    assert_eq!(
+        report.failures[1],
+        ClaimValidationFailure {
+            label: "email".to_string(),
+            validator: None,
+            reason: ClaimValidationReason::WrongType {
+                expected: ClaimType::Hashed,
+                found: ClaimType::Revocation,
+            },
+        }
+    );
+    let message = report.to_string();
+    let last = message.lines().last().unwrap();
+    assert!(last.starts_with(r#"claim 'country' failed validator {"NotAnyOf":"#));
+    assert!(last.ends_with("claim is not valid"));
+
+    // Issuance returns the same report
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    assert_eq!(
+        issuer.sign_credential(&invalid).unwrap_err(),
+        Error::ClaimValidation(report)
+    );
+    let res = blind_sign(&mut issuer, "4111111111111112", "john@example.com", "KP");
+    match res {
+        Err(Error::ClaimValidation(report)) => {
+            assert_eq!(report.labels(), vec!["card_number", "country"])
+        }
+        res => panic!("expected a validation report, got {:?}", res),
+    }
+    Ok(())
+}
+
+#[test]
+fn or_skips_branches_for_other_claim_types() {
+    setup();
+    let res = test_or_skips_branches_for_other_claim_types();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_or_skips_branches_for_other_claim_types() -> CredxResult<()> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "code".to_string(),
+            print_friendly: true,
+            validators: vec![ClaimValidator::Or(vec![
+                ClaimValidator::Range {
+                    min: Some(0),
+                    max: Some(9),
+                },
+                ClaimValidator::Prefix("A".to_string()),
+            ])],
+            ..Default::default()
+        },
+    ];
+    let schema = CredentialSchema::new(Some("Code"), None, &[], &schema_claims)?;
+    let claims = |code: &str| {
+        vec![
+            RevocationClaim::from(CRED_ID).into(),
+            HashedClaim::from(code).into(),
+        ]
+    };
+    // The range only applies to numbers so the prefix decides
+    schema.validate(&claims("A123"))?;
+    match schema.validate(&claims("B123")) {
+        Err(Error::ClaimValidation(report)) => {
+            assert_eq!(report.failures[0].reason, ClaimValidationReason::NotValid)
+        }
+        res => panic!("expected a validation report, got {:?}", res),
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use blsful::inner_types::Scalar;
+use credx::claim::{ClaimData, ClaimType, HashedClaim, NumberClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialSchema};
+use credx::issuer::Issuer;
+use credx::knox::bbs::BbsScheme;
+use credx::knox::ps::PsScheme;
+use credx::knox::short_group_sig_core::short_group_traits::{
+    ProofOfSignatureKnowledge, ProofOfSignatureKnowledgeContribution, ShortGroupSignatureScheme,
+};
+use credx::knox::short_group_sig_core::{HiddenMessage, ProofMessage};
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::statement::SignatureStatement;
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+use merlin::Transcript;
+use rand::thread_rng;
+use rand_core::RngCore;
+
+const CLAIM_COUNT: usize = 200;
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn wide_schema_bbs() {
+    setup();
+    let res = test_wide_schema::<BbsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn wide_schema_ps() {
+    setup();
+    let res = test_wide_schema::<PsScheme>();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[test]
+fn bbs_keys_sign_more_messages_than_they_were_created_for() {
+    let (pk, sk) = BbsScheme::new_keys(2.try_into().unwrap(), thread_rng()).unwrap();
+    let messages = (0..CLAIM_COUNT)
+        .map(|i| Scalar::from(i as u64))
+        .collect::<Vec<_>>();
+    let signature = BbsScheme::sign(&sk, &messages).unwrap();
+    assert!(bool::from(signature.verify(&pk, &messages)));
+    assert!(!bool::from(
+        signature.verify(&pk, &messages[..CLAIM_COUNT - 1])
+    ));
+
+    // The key format is unchanged so keys still round trip
+    let bytes = serde_bare::to_vec(&pk).unwrap();
+    let pk2: <BbsScheme as ShortGroupSignatureScheme>::PublicKey =
+        serde_bare::from_slice(&bytes).unwrap();
+    assert_eq!(pk, pk2);
+
+    let proof_messages = messages
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            if i % 50 == 0 {
+                ProofMessage::Revealed(*m)
+            } else {
+                ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(*m))
+            }
+        })
+        .collect::<Vec<_>>();
+    let revealed = messages
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i % 50 == 0)
+        .map(|(i, m)| (i, *m))
+        .collect::<Vec<_>>();
+    let pok =
+        BbsScheme::commit_signature_pok(signature, &pk2, &proof_messages, thread_rng()).unwrap();
+    let nonce = Scalar::from(42u64);
+    let mut transcript = Transcript::new(b"signature proof of knowledge");
+    pok.add_proof_contribution(&mut transcript);
+    transcript.append_message(b"nonce", nonce.to_be_bytes().as_ref());
+    let mut res = [0u8; 64];
+    transcript.challenge_bytes(b"signature proof of knowledge", &mut res);
+    let challenge = Scalar::from_bytes_wide(&res);
+    let proof = pok.generate_proof(challenge).unwrap();
+    assert!(BbsScheme::verify_signature_pok(
+        &revealed, &pk2, &proof, nonce, challenge
+    ));
+    let hidden = proof.get_hidden_message_proofs(&pk2, &revealed).unwrap();
+    assert_eq!(hidden.len(), CLAIM_COUNT - revealed.len());
+}
+
+fn test_wide_schema<S: ShortGroupSignatureScheme>() -> CredxResult<()> {
+    let mut schema_claims = Vec::with_capacity(CLAIM_COUNT);
+    schema_claims.push(ClaimSchema {
+        claim_type: ClaimType::Revocation,
+        label: "identifier".to_string(),
+        print_friendly: false,
+        validators: vec![],
+        ..Default::default()
+    });
+    for i in 1..CLAIM_COUNT {
+        schema_claims.push(ClaimSchema {
+            claim_type: if i % 2 == 0 {
+                ClaimType::Number
+            } else {
+                ClaimType::Hashed
+            },
+            label: format!("field{}", i),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        });
+    }
+    let cred_schema = CredentialSchema::new(Some("Wide"), None, &[], &schema_claims)?;
+    let (issuer_public, mut issuer) = Issuer::<S>::new(&cred_schema);
+
+    let mut claims: Vec<ClaimData> = Vec::with_capacity(CLAIM_COUNT);
+    claims.push(RevocationClaim::from("1b1c8e64-2f38-4c8e-9f5c-0d0f8a6b7c11").into());
+    for i in 1..CLAIM_COUNT {
+        if i % 2 == 0 {
+            claims.push(NumberClaim::from(i).into());
+        } else {
+            claims.push(HashedClaim::from(format!("value {}", i)).into());
+        }
+    }
+    let credential = issuer.sign_credential(&claims)?;
+
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {"field1".to_string(), "field150".to_string(), "field199".to_string()},
+        id: "wide".to_string(),
+        issuer: issuer_public,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into()]);
+    let credentials = indexmap! { "wide".to_string() => credential.credential.into() };
+    let mut nonce = [0u8; 16];
+    thread_rng().fill_bytes(&mut nonce);
+    let presentation = Presentation::create(&credentials, &presentation_schema, &nonce)?;
+    presentation.verify(&presentation_schema, &nonce)?;
+
+    let disclosed = &presentation.disclosed_messages["wide"];
+    assert_eq!(disclosed.len(), 3);
+    assert_eq!(disclosed["field150"], NumberClaim::from(150).into());
+    Ok(())
+}
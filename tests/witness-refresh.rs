@@ -0,0 +1,192 @@
+use credx::claim::{ClaimType, HashedClaim, RevocationClaim};
+use credx::credential::{ClaimSchema, CredentialBundle, CredentialSchema};
+use credx::issuer::{Issuer, IssuerPublic};
+use credx::knox::bbs::BbsScheme;
+use credx::presentation::{Presentation, PresentationSchema};
+use credx::revocation_registry::{
+    LocalWitnessRefreshServer, SignedAccumulatorState, WitnessRefreshRequest,
+    WitnessRefreshResponse, WitnessRefreshService,
+};
+use credx::statement::{RegistryEpochPolicy, RevocationStatement, SignatureStatement};
+use credx::CredxResult;
+use indexmap::indexmap;
+use maplit::btreeset;
+
+const CRED_IDS: [&str; 3] = [
+    "0f3c5a7e-9b1d-4f2a-8c6e-4d7b9a1c3e5f",
+    "1a4d6b8f-0c2e-4a3b-9d7f-5e8c0b2d4f6a",
+    "2b5e7c9a-1d3f-4b4c-8e8a-6f9d1c3e5a7b",
+];
+
+fn setup() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+fn issue() -> CredxResult<(Issuer<BbsScheme>, Vec<CredentialBundle<BbsScheme>>)> {
+    let schema_claims = [
+        ClaimSchema {
+            claim_type: ClaimType::Revocation,
+            label: "identifier".to_string(),
+            print_friendly: false,
+            validators: vec![],
+            ..Default::default()
+        },
+        ClaimSchema {
+            claim_type: ClaimType::Hashed,
+            label: "name".to_string(),
+            print_friendly: true,
+            validators: vec![],
+            ..Default::default()
+        },
+    ];
+    let schema = CredentialSchema::new(Some("Refresh"), None, &[], &schema_claims)?;
+    let (_, mut issuer) = Issuer::<BbsScheme>::new(&schema);
+    let mut bundles = Vec::with_capacity(CRED_IDS.len());
+    for id in CRED_IDS {
+        bundles.push(issuer.sign_credential(&[
+            RevocationClaim::from(id).into(),
+            HashedClaim::from("John Doe").into(),
+        ])?);
+    }
+    Ok((issuer, bundles))
+}
+
+fn prove(
+    issuer_public: &IssuerPublic<BbsScheme>,
+    bundle: &CredentialBundle<BbsScheme>,
+) -> CredxResult<()> {
+    let sig_st = SignatureStatement {
+        disclosed: btreeset! {},
+        id: "sig".to_string(),
+        issuer: issuer_public.clone(),
+    };
+    let acc_st = RevocationStatement {
+        id: "revocation".to_string(),
+        reference_id: sig_st.id.clone(),
+        accumulator: issuer_public.revocation_registry,
+        verification_key: issuer_public.revocation_verifying_key,
+        claim: 0,
+        epochs: RegistryEpochPolicy::Current,
+        signed_states: None,
+    };
+    let presentation_schema = PresentationSchema::new(&[sig_st.into(), acc_st.into()]);
+    let credentials = indexmap! { "sig".to_string() => bundle.credential.clone().into() };
+    let nonce = b"witness refresh nonce";
+    let presentation = Presentation::create(&credentials, &presentation_schema, nonce)?;
+    presentation.verify(&presentation_schema, nonce)
+}
+
+#[test]
+fn witness_refresh() {
+    setup();
+    let res = test_witness_refresh();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_witness_refresh() -> CredxResult<()> {
+    let (mut issuer, mut bundles) = issue()?;
+    let mut server = LocalWitnessRefreshServer::new(
+        *issuer.revocation_registry.value_at(0).unwrap(),
+        issuer.sign_revocation_registry()?,
+    )?;
+
+    let update_1 = issuer.revoke_credentials(&[RevocationClaim::from(CRED_IDS[0])])?;
+    let signed_1 = issuer.sign_revocation_registry()?;
+    let update_2 = issuer.revoke_credentials(&[RevocationClaim::from(CRED_IDS[1])])?;
+    let signed_2 = issuer.sign_revocation_registry()?;
+    // Updates must be published in order with the signed registry value
+    assert!(server.publish(update_2.clone(), signed_2.clone()).is_err());
+    assert!(server.publish(update_1.clone(), signed_2.clone()).is_err());
+    server.publish(update_1, signed_1)?;
+    server.publish(update_2, signed_2)?;
+    assert_eq!(server.latest().value, issuer.revocation_registry.value);
+
+    let issuer_public = IssuerPublic::from(&issuer);
+    assert!(prove(&issuer_public, &bundles[2]).is_err());
+    bundles[2].refresh_revocation_handle(&server)?;
+    assert_eq!(bundles[2].credential.revocation_epoch, 2);
+    prove(&issuer_public, &bundles[2])?;
+    // Refreshing an up to date handle changes nothing
+    bundles[2].refresh_revocation_handle(&server)?;
+    prove(&issuer_public, &bundles[2])?;
+
+    // Revoked holders can't refresh
+    assert!(bundles[0].refresh_revocation_handle(&server).is_err());
+    assert!(bundles[1].refresh_revocation_handle(&server).is_err());
+
+    // Requests don't identify the holder, so holders at the same epoch get the same response
+    let requests = [
+        WitnessRefreshRequest { epoch: 0 },
+        WitnessRefreshRequest { epoch: 1 },
+        WitnessRefreshRequest { epoch: 0 },
+        WitnessRefreshRequest { epoch: 2 },
+    ];
+    let responses = server.refresh_batch(&requests)?;
+    assert_eq!(responses[0], responses[2]);
+    assert_eq!(responses[0], server.refresh(&requests[0])?);
+    // Every update from the requested epoch is combined into one batch update
+    let update = responses[0].update.as_ref().unwrap();
+    assert_eq!(update.epoch, 2);
+    assert_eq!(update.deletions.len(), 2);
+    assert_eq!(responses[1].update.as_ref().unwrap().deletions.len(), 1);
+    assert!(responses[3].update.is_none());
+    for response in &responses {
+        response.verify(&issuer_public.id, &issuer_public.registry_verifying_key)?;
+    }
+    let json = serde_json::to_string(&responses[1]).unwrap();
+    let response: WitnessRefreshResponse = serde_json::from_str(&json).unwrap();
+    assert_eq!(response, responses[1]);
+    assert!(server.refresh(&WitnessRefreshRequest { epoch: 3 }).is_err());
+    Ok(())
+}
+
+#[test]
+fn witness_refresh_rejects_unsigned_values() {
+    setup();
+    let res = test_witness_refresh_rejects_unsigned_values();
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+fn test_witness_refresh_rejects_unsigned_values() -> CredxResult<()> {
+    let (mut issuer, mut bundles) = issue()?;
+    let (other, _) = issue()?;
+    let genesis = *issuer.revocation_registry.value_at(0).unwrap();
+    let update = issuer.revoke_credentials(&[RevocationClaim::from(CRED_IDS[0])])?;
+
+    // A server can't make up its own registry values
+    let forged = SignedAccumulatorState::new(
+        &issuer.id,
+        update.epoch,
+        update.value,
+        &other.registry_signing_key,
+    )?;
+    let mut server =
+        LocalWitnessRefreshServer::new(genesis, issuer.sign_revocation_registry_epoch(0)?)?;
+    server.publish(update.clone(), forged)?;
+    assert!(bundles[2].refresh_revocation_handle(&server).is_err());
+    assert_eq!(bundles[2].credential.revocation_epoch, 0);
+
+    // Or pass off another registry's signed value
+    let mut server =
+        LocalWitnessRefreshServer::new(genesis, issuer.sign_revocation_registry_epoch(0)?)?;
+    let mut renamed = issuer.sign_revocation_registry()?;
+    renamed.registry_id = other.id.clone();
+    server.publish(update.clone(), renamed)?;
+    assert!(bundles[2].refresh_revocation_handle(&server).is_err());
+
+    // Or tamper with the update polynomial
+    let mut server =
+        LocalWitnessRefreshServer::new(genesis, issuer.sign_revocation_registry_epoch(0)?)?;
+    let mut tampered = update.clone();
+    tampered.coefficients.reverse();
+    tampered.coefficients.push(tampered.coefficients[0]);
+    server.publish(tampered, issuer.sign_revocation_registry()?)?;
+    assert!(bundles[2].refresh_revocation_handle(&server).is_err());
+    assert_eq!(bundles[2].credential.revocation_epoch, 0);
+
+    let mut server =
+        LocalWitnessRefreshServer::new(genesis, issuer.sign_revocation_registry_epoch(0)?)?;
+    server.publish(update, issuer.sign_revocation_registry()?)?;
+    bundles[2].refresh_revocation_handle(&server)?;
+    prove(&IssuerPublic::from(&issuer), &bundles[2])
+}